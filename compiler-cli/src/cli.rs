@@ -1,23 +1,56 @@
 use gleam_core::{
-    build::Telemetry,
+    build::{Telemetry, Timing, TimingsRecorder},
     error::{Error, StandardIoAction},
 };
-use std::io::Write;
+use std::{io::Write, time::Duration};
 use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
 
-#[derive(Debug, Default, Clone)]
-pub struct Reporter;
+#[derive(Debug, Default)]
+pub struct Reporter {
+    timings: Option<TimingsRecorder>,
+}
 
 impl Reporter {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// A reporter that also records the timings of each module.
+    pub fn with_timings() -> Self {
+        Self {
+            timings: Some(TimingsRecorder::new()),
+        }
+    }
+
+    pub fn timings(&self) -> Option<&TimingsRecorder> {
+        self.timings.as_ref()
+    }
 }
 
 impl Telemetry for Reporter {
     fn compiling_package(&self, name: &str) {
         print_compiling(name);
     }
+
+    fn compiled_package(&self, name: &str, duration: Duration) {
+        tracing::info!(package = name, duration = ?duration, "Compiled package");
+    }
+
+    fn timed(&self, timing: &Timing) {
+        if let Some(timings) = &self.timings {
+            timings.record(timing);
+        }
+    }
+}
+
+/// What was done by a build, printed once it has finished.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub packages: usize,
+    pub warnings: usize,
+    pub duration: Duration,
+    /// Whether the packages were only type checked, without generating code
+    pub check: bool,
 }
 
 pub fn ask(question: &str) -> Result<String, Error> {
@@ -47,6 +80,32 @@ pub fn print_compiling(text: &str) {
     print_green_prefix("Compiling", text)
 }
 
+pub fn print_summary(summary: &Summary) {
+    let packages = match summary.packages {
+        1 => "package",
+        _ => "packages",
+    };
+    let warnings = match summary.warnings {
+        0 => String::new(),
+        1 => " with 1 warning".to_string(),
+        n => format!(" with {} warnings", n),
+    };
+    print_green_prefix(
+        if summary.check {
+            "  Checked"
+        } else {
+            " Compiled"
+        },
+        &format!(
+            "{} {} in {:.2}s{}",
+            summary.packages,
+            packages,
+            summary.duration.as_secs_f64(),
+            warnings
+        ),
+    )
+}
+
 pub fn print_running(text: &str) {
     print_green_prefix("  Running", text)
 }
@@ -1,14 +1,15 @@
-use gleam_core::{metadata, type_::Module, Result};
+use gleam_core::{metadata, type_::Module, warning::Warnings, Error, Result};
 use std::{collections::HashMap, path::PathBuf};
 
 use crate::{
     fs::{self, FileSystemAccessor},
-    CompilePackage,
+    CompilePackage, Library,
 };
 
 pub fn command(options: CompilePackage) -> Result<()> {
-    let mut type_manifests = load_libraries(&options.libraries)?;
     let mut defined_modules = HashMap::new();
+    let mut type_manifests = load_libraries(&options.libraries, &mut defined_modules)?;
+    let warnings_as_errors = options.warnings_as_errors;
     let mut warnings = Vec::new();
 
     tracing::info!("Compiling package");
@@ -19,26 +20,160 @@ pub fn command(options: CompilePackage) -> Result<()> {
         .write_metadata(true)
         .compile(&mut warnings, &mut type_manifests, &mut defined_modules)?;
 
-    // Print warnings
-    for warning in warnings {
-        crate::print_warning(&warning);
-    }
-
-    // TODO: Support --warnings-as-errors
-    // TODO: Create a Warnings struct to wrap up this functionality
-
-    Ok(())
+    // Print warnings, failing if they are to be treated as errors
+    let mut all_warnings = Warnings::default();
+    all_warnings.extend(warnings);
+    crate::print_warnings(&all_warnings);
+    all_warnings.check(warnings_as_errors)
 }
 
-fn load_libraries(libs: &[PathBuf]) -> Result<HashMap<String, Module>> {
+fn load_libraries(
+    libs: &[Library],
+    defined_modules: &mut HashMap<String, PathBuf>,
+) -> Result<HashMap<String, Module>> {
     tracing::info!("Reading precompiled module metadata files");
     let mut manifests = HashMap::with_capacity(libs.len() * 10);
     for lib in libs {
-        for module in fs::gleam_modules_metadata_paths(lib)? {
-            let reader = fs::buffered_reader(module)?;
-            let module = metadata::ModuleDecoder::new().read(reader)?;
-            let _ = manifests.insert(module.name.join("/"), module);
+        for path in fs::gleam_modules_metadata_paths(&lib.path)? {
+            let module = read_module_metadata(path.clone())?;
+            let name = module.name.join("/");
+
+            // Ensure the metadata is from the package the library is said to be
+            if let Some(package) = &lib.package {
+                if &module.package != package {
+                    return Err(Error::LibraryPackageMismatch {
+                        path,
+                        module: name,
+                        expected: package.clone(),
+                        found: module.package,
+                    });
+                }
+            }
+
+            // Ensure no other library or source module has this name
+            if let Some(first) = defined_modules.insert(name.clone(), path.clone()) {
+                return Err(Error::DuplicateModule {
+                    module: name,
+                    first,
+                    second: path,
+                });
+            }
+
+            let _ = manifests.insert(name, module);
         }
     }
     Ok(manifests)
 }
+
+/// Decode a `.gleam_module` file, including the path of the file in the error
+/// if it was written by an incompatible version of Gleam.
+pub fn read_module_metadata(path: PathBuf) -> Result<Module> {
+    let reader = fs::buffered_reader(&path)?;
+    metadata::ModuleDecoder::new()
+        .read(reader)
+        .map_err(|error| match error {
+            Error::IncompatibleMetadata { problem, .. } => Error::IncompatibleMetadata {
+                path: Some(path),
+                problem,
+            },
+            error => error,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gleam_core::{build::Origin, io::FileSystemWriter};
+    use std::{path::Path, process};
+
+    fn library(package: Option<&str>, path: &Path) -> Library {
+        Library {
+            package: package.map(str::to_string),
+            path: path.to_path_buf(),
+        }
+    }
+
+    // Writes the metadata for an empty module named `name` from `package`
+    // into `dir`.
+    fn write_module(dir: &Path, package: &str, name: &str) {
+        let module = Module {
+            name: name.split('/').map(str::to_string).collect(),
+            origin: Origin::Src,
+            package: package.to_string(),
+            types: HashMap::new(),
+            values: HashMap::new(),
+            accessors: HashMap::new(),
+        };
+        let path = dir.join(format!("{}.gleam_module", name.replace('/', "@")));
+        let writer = FileSystemAccessor::new().open(&path).expect("open");
+        metadata::ModuleEncoder::new(&module)
+            .write(writer)
+            .expect("write");
+    }
+
+    #[test]
+    fn parse_library() {
+        assert_eq!(
+            "one=build/one".parse::<Library>(),
+            Ok(library(Some("one"), Path::new("build/one")))
+        );
+        assert_eq!(
+            "build/one".parse::<Library>(),
+            Ok(library(None, Path::new("build/one")))
+        );
+        assert_eq!(
+            "one=build=one".parse::<Library>(),
+            Ok(library(Some("one"), Path::new("build=one")))
+        );
+        assert_eq!(
+            "=build/one".parse::<Library>(),
+            Err("Missing package name in library `=build/one`".to_string())
+        );
+    }
+
+    #[test]
+    fn load_libraries_checks_packages_and_module_names() {
+        let dir = std::env::temp_dir().join(format!("gleam-load-libraries-{}", process::id()));
+        fs::delete_dir(&dir).expect("delete");
+        let one = dir.join("one");
+        let two = dir.join("two");
+        write_module(&one, "one", "one/a");
+        write_module(&two, "two", "one/a");
+
+        // Libraries with distinct modules from the expected packages load
+        let mut defined_modules = HashMap::new();
+        let manifests =
+            load_libraries(&[library(Some("one"), &one)], &mut defined_modules).expect("load");
+        assert_eq!(manifests.keys().collect::<Vec<_>>(), vec!["one/a"]);
+        assert_eq!(
+            defined_modules.get("one/a"),
+            Some(&one.join("one@a.gleam_module"))
+        );
+
+        // The metadata must be from the package the library is said to be
+        assert_eq!(
+            load_libraries(&[library(Some("one"), &two)], &mut HashMap::new()),
+            Err(Error::LibraryPackageMismatch {
+                path: two.join("one@a.gleam_module"),
+                module: "one/a".to_string(),
+                expected: "one".to_string(),
+                found: "two".to_string(),
+            })
+        );
+
+        // Two libraries cannot define the same module
+        assert_eq!(
+            load_libraries(
+                &[library(Some("one"), &one), library(None, &two)],
+                &mut HashMap::new()
+            ),
+            Err(Error::DuplicateModule {
+                module: "one/a".to_string(),
+                first: one.join("one@a.gleam_module"),
+                second: two.join("one@a.gleam_module"),
+            })
+        );
+
+        fs::delete_dir(&dir).expect("delete");
+    }
+}
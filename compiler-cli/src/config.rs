@@ -27,10 +27,14 @@ pub fn package_configs(
 pub fn read_project_config(root: impl AsRef<Path>) -> Result<PackageConfig, Error> {
     let config_path = root.as_ref().join("gleam.toml");
     let toml = crate::fs::read(&config_path)?;
-    toml::from_str(&toml).map_err(|e| Error::FileIo {
+    parse_project_config(&config_path, &toml)
+}
+
+pub fn parse_project_config(config_path: &Path, toml: &str) -> Result<PackageConfig, Error> {
+    toml::from_str(toml).map_err(|e| Error::FileIo {
         action: FileIoAction::Parse,
         kind: FileKind::File,
-        path: config_path.clone(),
+        path: config_path.to_path_buf(),
         err: Some(e.to_string()),
     })
 }
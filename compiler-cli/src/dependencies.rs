@@ -0,0 +1,1054 @@
+//! The dependencies of a project: the `gleam add` and `gleam remove` commands
+//! that edit them, and the copying of path and git dependencies into the build
+//! directory.
+//!
+//! `gleam.toml` is edited line by line so that the formatting and comments of
+//! the rest of the file are kept. The edited file is then parsed as a
+//! `PackageConfig` to check that the change had the expected effect.
+
+use crate::config;
+use gleam_core::{
+    build::project_root::ProjectRoot,
+    config::{BuildTool, Dependency, PackageConfig},
+    error::Error,
+    io::OutputFile,
+    semver::Version,
+    Result,
+};
+use hexpm::Client;
+use itertools::Itertools;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// The key used to verify the signatures of packages downloaded from Hex.
+pub const HEXPM_PUBLIC_KEY: &[u8] = b"-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEApqREcFDt5vV21JVe2QNB
+Edvzk6w36aNFhVGWN5toNJRjRJ6m4hIuG4KaXtDWVLjnvct6MYMfqhC79HAGwyF+
+IqR6Q6a5bbFSsImgBJwz1oadoVKD6ZNetAuCIK84cjMrEFRkELtEIPNHblCzUkkM
+3rS9+DPlnfG8hBvGi6tvQIuZmXGCxF/73hU0/MyGhbmEjIKRtG6b0sJYKelRLTPW
+XgK7s5pESgiwf2YC/2MGDXjAJfpfCd0RpLdvd4eRiXtVlE9qO9bND94E7PgQ/xqZ
+J1i2xWFndWa6nfFnRxZmCStCOZWYYPlaxr+FZceFbpMwzTNs4g3d4tLNUcbKAIH4
+0wIDAQAB
+-----END PUBLIC KEY-----
+";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Table {
+    Dependencies,
+    DevDependencies,
+}
+
+impl Table {
+    fn name(self) -> &'static str {
+        match self {
+            Table::Dependencies => "dependencies",
+            Table::DevDependencies => "dev-dependencies",
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Table::Dependencies => Table::DevDependencies,
+            Table::DevDependencies => Table::Dependencies,
+        }
+    }
+
+    fn get(self, config: &PackageConfig) -> &HashMap<String, Dependency> {
+        match self {
+            Table::Dependencies => &config.dependencies,
+            Table::DevDependencies => &config.dev_dependencies,
+        }
+    }
+}
+
+/// A package repository that can be asked which versions of a package have
+/// been published.
+pub trait Registry {
+    /// The versions of a package that have not been retired, returning an
+    /// `UnknownPackage` error if the package does not exist.
+    fn versions(&self, package: &str) -> Result<Vec<String>>;
+}
+
+#[derive(Debug)]
+struct Hex {
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Hex {
+    fn new() -> Self {
+        Self {
+            runtime: tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime"),
+        }
+    }
+}
+
+impl Registry for Hex {
+    fn versions(&self, name: &str) -> Result<Vec<String>> {
+        let package = self
+            .runtime
+            .block_on(hexpm::UnauthenticatedClient::new().get_package(name, HEXPM_PUBLIC_KEY))
+            .map_err(|error| match error {
+                hexpm::GetPackageError::NotFound => Error::UnknownPackage {
+                    name: name.to_string(),
+                },
+                error => Error::Hex(error.to_string()),
+            })?;
+        Ok(package
+            .releases
+            .into_iter()
+            .filter(|release| !release.is_retired())
+            .map(|release| release.version)
+            .collect())
+    }
+}
+
+pub fn add(project_root: String, package: String, dev: bool) -> Result<()> {
+    let root = ProjectRoot::new(PathBuf::from(project_root));
+    let (name, requirement) = match package.split_once('@') {
+        Some((name, requirement)) => (name, Some(requirement)),
+        None => (package.as_str(), None),
+    };
+    let table = if dev {
+        Table::DevDependencies
+    } else {
+        Table::Dependencies
+    };
+
+    let requirement = resolve_requirement(&Hex::new(), name, requirement)?;
+    let (toml_path, toml) = read_toml(&root)?;
+    let toml = add_to_toml(&toml_path, &toml, table, name, &requirement)?;
+    let config = config::parse_project_config(&toml_path, &toml)?;
+
+    let mut outputs = vec![OutputFile {
+        path: toml_path,
+        text: toml,
+    }];
+    let rebar_config_path = root.root.join("rebar.config");
+    if config.tool == BuildTool::Other && rebar_config_path.is_file() {
+        if dev {
+            println!(
+                "rebar.config has not been updated, add {} to the deps of the test profile.",
+                name
+            );
+        } else {
+            let text = crate::fs::read(&rebar_config_path)?;
+            outputs.push(OutputFile {
+                text: add_to_rebar_config(&text, name, &requirement),
+                path: rebar_config_path,
+            });
+        }
+    }
+    crate::fs::write_outputs(&outputs)?;
+
+    println!("Added {} {} to the {}", name, requirement, table.name());
+    Ok(())
+}
+
+pub fn remove(project_root: String, name: String) -> Result<()> {
+    let root = ProjectRoot::new(PathBuf::from(project_root));
+    let (toml_path, toml) = read_toml(&root)?;
+    let (toml, tables) = remove_from_toml(&toml_path, &toml, &name)?;
+    let config = config::parse_project_config(&toml_path, &toml)?;
+
+    let mut outputs = vec![OutputFile {
+        path: toml_path,
+        text: toml,
+    }];
+    let rebar_config_path = root.root.join("rebar.config");
+    if config.tool == BuildTool::Other && rebar_config_path.is_file() {
+        let text = crate::fs::read(&rebar_config_path)?;
+        outputs.push(OutputFile {
+            text: remove_from_rebar_config(&text, &name),
+            path: rebar_config_path,
+        });
+    }
+    crate::fs::write_outputs(&outputs)?;
+
+    for table in tables {
+        println!("Removed {} from the {}", name, table.name());
+    }
+    Ok(())
+}
+
+fn read_toml(root: &ProjectRoot) -> Result<(PathBuf, String)> {
+    let path = root.root.join("gleam.toml");
+    let toml = crate::fs::read(&path)?;
+    Ok((path, toml))
+}
+
+/// Check the package exists and pick the requirement to add it with. Without
+/// a given requirement the latest stable version is used.
+fn resolve_requirement(
+    registry: &impl Registry,
+    name: &str,
+    requirement: Option<&str>,
+) -> Result<String> {
+    if !is_valid_package_name(name) {
+        return Err(Error::InvalidPackageName {
+            name: name.to_string(),
+        });
+    }
+    if let Some(requirement) = requirement {
+        if !is_valid_requirement(requirement) {
+            return Err(Error::InvalidVersionRequirement {
+                requirement: requirement.to_string(),
+            });
+        }
+    }
+
+    let versions = registry.versions(name)?;
+    if let Some(requirement) = requirement {
+        return Ok(requirement.trim().to_string());
+    }
+    versions
+        .iter()
+        .filter(|version| !version.contains(&['-', '+'][..]))
+        .filter_map(|version| Version::parse(version))
+        .max()
+        .map(default_requirement)
+        .ok_or_else(|| {
+            Error::Hex(format!(
+                "{} has no stable releases, so a version requirement must be given",
+                name
+            ))
+        })
+}
+
+/// The `~>` requirement that accepts the releases that should be compatible
+/// with a version. Before 1.0.0 minor releases may contain breaking changes so
+/// only patch releases are accepted.
+fn default_requirement(version: Version) -> String {
+    if version.major == 0 {
+        format!("~> {}", version)
+    } else {
+        format!("~> {}.{}", version.major, version.minor)
+    }
+}
+
+fn is_valid_package_name(name: &str) -> bool {
+    regex::Regex::new("^[a-z][a-z0-9_]*$")
+        .expect("package name regex could not be compiled")
+        .is_match(name)
+}
+
+fn is_valid_requirement(requirement: &str) -> bool {
+    requirement.chars().any(|c| c.is_ascii_digit())
+        && requirement
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " .~<>=!-+".contains(c))
+}
+
+fn add_to_toml(
+    path: &Path,
+    toml: &str,
+    table: Table,
+    name: &str,
+    requirement: &str,
+) -> Result<String> {
+    let _ = config::parse_project_config(path, toml)?;
+    let mut lines: Vec<String> = toml.lines().map(String::from).collect();
+    let _ = remove_key(&mut lines, table.other(), name);
+
+    let entry = format!("{} = \"{}\"", name, requirement);
+    match table_body(&lines, table.name()) {
+        Some(body) => match find_key(&lines, body.clone(), name) {
+            Some(index) => {
+                if let Some(line) = lines.get_mut(index) {
+                    let comment = trailing_comment(line).unwrap_or_default();
+                    *line = format!("{}{}", entry, comment);
+                }
+            }
+            None => {
+                // Insert after the last entry so that any blank lines before
+                // the next table are kept
+                let index = body
+                    .clone()
+                    .rev()
+                    .find(|&index| lines.get(index).is_some_and(|line| is_entry(line)))
+                    .map_or(body.start, |index| index + 1);
+                lines.insert(index, entry);
+            }
+        },
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", table.name()));
+            lines.push(entry);
+        }
+    }
+
+    let edited = join_lines(toml, &lines);
+    let config = parse_edited(path, &edited)?;
+    if table.get(&config).get(name) != Some(&Dependency::Hex(requirement.to_string()))
+        || table.other().get(&config).contains_key(name)
+    {
+        return Err(unable_to_edit(path));
+    }
+    Ok(edited)
+}
+
+/// Remove a package from whichever dependency tables it is in, returning the
+/// edited TOML and the tables it was removed from.
+fn remove_from_toml(path: &Path, toml: &str, name: &str) -> Result<(String, Vec<Table>)> {
+    let config = config::parse_project_config(path, toml)?;
+    let tables: Vec<_> = [Table::Dependencies, Table::DevDependencies]
+        .iter()
+        .copied()
+        .filter(|table| table.get(&config).contains_key(name))
+        .collect();
+    if tables.is_empty() {
+        return Err(Error::DependencyNotFound {
+            name: name.to_string(),
+        });
+    }
+
+    let mut lines: Vec<String> = toml.lines().map(String::from).collect();
+    for table in &tables {
+        let _ = remove_key(&mut lines, *table, name);
+    }
+
+    let edited = join_lines(toml, &lines);
+    let config = parse_edited(path, &edited)?;
+    if tables
+        .iter()
+        .any(|table| table.get(&config).contains_key(name))
+    {
+        return Err(unable_to_edit(path));
+    }
+    Ok((edited, tables))
+}
+
+fn parse_edited(path: &Path, toml: &str) -> Result<PackageConfig> {
+    config::parse_project_config(path, toml).map_err(|_| unable_to_edit(path))
+}
+
+fn unable_to_edit(path: &Path) -> Error {
+    Error::UnableToEditConfig {
+        path: path.to_path_buf(),
+        reason: "Dependencies can only be edited when they are listed in a
+`[dependencies]` or `[dev-dependencies]` table.
+"
+        .to_string(),
+    }
+}
+
+fn join_lines(original: &str, lines: &[String]) -> String {
+    let newline = if original.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut text = lines.join(newline);
+    text.push_str(newline);
+    text
+}
+
+fn remove_key(lines: &mut Vec<String>, table: Table, name: &str) -> bool {
+    match table_body(lines, table.name()).and_then(|body| find_key(lines, body, name)) {
+        Some(index) => {
+            let _ = lines.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The range of lines after the header of a table, up to the next header.
+fn table_body(lines: &[String], table: &str) -> Option<Range<usize>> {
+    let start = lines.iter().position(|line| header(line) == Some(table))? + 1;
+    let end = lines
+        .iter()
+        .skip(start)
+        .position(|line| header(line).is_some())
+        .map_or(lines.len(), |offset| start + offset);
+    Some(start..end)
+}
+
+fn find_key(lines: &[String], range: Range<usize>, name: &str) -> Option<usize> {
+    range
+        .into_iter()
+        .find(|&index| lines.get(index).and_then(|line| key(line)) == Some(name))
+}
+
+fn header(line: &str) -> Option<&str> {
+    let name = line.trim().strip_prefix('[')?.split(']').next()?;
+    Some(name.trim().trim_matches('"'))
+}
+
+fn key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    Some(key.trim_matches('"'))
+}
+
+/// The comment at the end of a line, including the whitespace before it.
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), _) if escaped => escaped = false,
+            (Some('"'), '\\') => escaped = true,
+            (Some(open), _) if open == c => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => {
+                let value_end = line.get(..index)?.trim_end().len();
+                return line.get(value_end..);
+            }
+            (None, _) => (),
+        }
+    }
+    None
+}
+
+fn is_entry(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#')
+}
+
+/// Add a package to the top level `deps` list of a `rebar.config`, replacing
+/// any existing entry for it.
+fn add_to_rebar_config(text: &str, name: &str, requirement: &str) -> String {
+    let entry = format!("{{{}, \"{}\"}}", name, requirement);
+    let (body, items) = match rebar_deps(text) {
+        Some(deps) => deps,
+        None => {
+            let mut text = text.to_string();
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(&format!("\n{{deps, [\n    {}\n]}}.\n", entry));
+            return text;
+        }
+    };
+
+    let mut text = text.to_string();
+    match items
+        .iter()
+        .find(|item| rebar_dep_name(&text, item) == Some(name))
+    {
+        Some(item) => text.replace_range(item.clone(), &entry),
+        None => match items.last() {
+            Some(last) => text.insert_str(last.end, &format!(",\n    {}", entry)),
+            None => text.replace_range(body, &format!("\n    {}\n", entry)),
+        },
+    }
+    text
+}
+
+/// Remove a package from the top level `deps` list of a `rebar.config`,
+/// along with the comma separating it from its neighbours.
+fn remove_from_rebar_config(text: &str, name: &str) -> String {
+    let mut text = text.to_string();
+    let items = match rebar_deps(&text) {
+        Some((_, items)) => items,
+        None => return text,
+    };
+    let index = match items
+        .iter()
+        .position(|item| rebar_dep_name(&text, item) == Some(name))
+    {
+        Some(index) => index,
+        None => return text,
+    };
+
+    let previous = index.checked_sub(1).and_then(|i| items.get(i));
+    let range = match (items.get(index), previous, items.get(index + 1)) {
+        (Some(item), Some(previous), _) => previous.end..item.end,
+        (Some(item), None, Some(next)) => item.start..next.start,
+        (Some(item), None, None) => item.clone(),
+        (None, _, _) => return text,
+    };
+    text.replace_range(range, "");
+    text
+}
+
+fn rebar_dep_name<'a>(text: &'a str, item: &Range<usize>) -> Option<&'a str> {
+    let item = text.get(item.clone())?;
+    let item = item.strip_prefix('{').unwrap_or(item).trim_start();
+    let end = item
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(item.len());
+    item.get(..end)
+}
+
+/// Find the top level `{deps, [...]}` list of a `rebar.config`, returning the
+/// range between its brackets and the ranges of each of its items, without
+/// surrounding whitespace.
+fn rebar_deps(text: &str) -> Option<(Range<usize>, Vec<Range<usize>>)> {
+    let start = regex::Regex::new(r"(?m)^\{\s*deps\s*,\s*\[")
+        .expect("rebar deps regex could not be compiled")
+        .find(text)?
+        .end();
+
+    let mut items = vec![];
+    let mut item_start = None;
+    let mut item_end = start;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut in_comment = false;
+    let mut escaped = false;
+    for (offset, c) in text.get(start..)?.char_indices() {
+        let index = start + offset;
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => (),
+            }
+            item_end = index + 1;
+            continue;
+        }
+        match c {
+            '%' => in_comment = true,
+            c if c.is_whitespace() => (),
+            ',' | ']' if depth == 0 => {
+                if let Some(item_start) = item_start.take() {
+                    items.push(item_start..item_end);
+                }
+                if c == ']' {
+                    return Some((start..index, items));
+                }
+            }
+            _ => {
+                match c {
+                    '"' => in_string = true,
+                    '{' | '[' | '(' => depth += 1,
+                    '}' | ']' | ')' => depth -= 1,
+                    _ => (),
+                }
+                let _ = item_start.get_or_insert(index);
+                item_end = index + c.len_utf8();
+            }
+        }
+    }
+    None
+}
+
+/// Copy the packages that the root package depends on by path or git
+/// repository into the build directory so they are compiled along with it,
+/// along with any path or git packages that they depend on in turn. Packages
+/// from Hex are expected to already be in the build directory.
+pub fn copy_local_packages(root: &ProjectRoot, root_config: &PackageConfig) -> Result<()> {
+    let mut copied = HashSet::new();
+    let _ = copied.insert(root_config.name.clone());
+    let dependencies = root_config
+        .dependencies
+        .iter()
+        .chain(&root_config.dev_dependencies);
+    copy_dependencies(root, &root.root, dependencies, &mut copied)
+}
+
+fn copy_dependencies<'a>(
+    root: &ProjectRoot,
+    base: &Path,
+    dependencies: impl Iterator<Item = (&'a String, &'a Dependency)>,
+    copied: &mut HashSet<String>,
+) -> Result<()> {
+    for (name, dependency) in dependencies.sorted_by(|a, b| a.0.cmp(b.0)) {
+        if copied.contains(name) {
+            continue;
+        }
+        let source = match dependency {
+            Dependency::Hex(_) => continue,
+            Dependency::Path { path } => base.join(path),
+            Dependency::Git { git, reference } => {
+                clone_git_package(root, base, name, git, reference.as_deref())?
+            }
+        };
+        let _ = copied.insert(name.clone());
+
+        tracing::info!("Copying package {} from {:?}", name, source);
+        let config = config::read_project_config(&source)?;
+        // The package is synced rather than copied afresh so that the
+        // compiled code of modules that have not changed is kept
+        let target = root.default_build_lib_package_path(name);
+        crate::fs::mkdir(&target)?;
+        crate::fs::copy(source.join("gleam.toml"), target.join("gleam.toml"))?;
+        crate::fs::sync_dir(&source.join("src"), &target.join("src"))?;
+
+        copy_dependencies(root, &source, config.dependencies.iter(), copied)?;
+    }
+    Ok(())
+}
+
+/// Clone a git repository into the build directory and check out the given
+/// ref, or the default branch, returning the directory. An existing clone is
+/// reused, only fetching from the repository when the ref is not already
+/// checked out, so a branch is not updated until the ref is changed.
+fn clone_git_package(
+    root: &ProjectRoot,
+    base: &Path,
+    name: &str,
+    url: &str,
+    reference: Option<&str>,
+) -> Result<PathBuf> {
+    // Relative paths to local repositories are relative to the package that
+    // depends on them, rather than the current directory
+    let local = base.join(url);
+    let url = if local.exists() {
+        local.to_string_lossy().to_string()
+    } else {
+        url.to_string()
+    };
+    let reference = reference.unwrap_or("origin/HEAD");
+
+    let directory = root.build_path().join("git").join(name);
+    let cloned = git_output(&directory, &["config", "--get", "remote.origin.url"]);
+    if cloned.as_deref() != Some(url.as_str()) {
+        crate::fs::delete_dir(&directory)?;
+        crate::fs::mkdir(&directory)?;
+        let mut clone = process::Command::new("git");
+        let _ = clone.args(["clone", "--quiet", &url, "."]);
+        run_git(clone.current_dir(&directory), "git clone")?;
+    }
+
+    let head = git_output(&directory, &["rev-parse", "HEAD"]);
+    let commit = format!("{}^{{commit}}", reference);
+    let wanted = git_output(&directory, &["rev-parse", "--verify", "--quiet", &commit]);
+    if wanted.is_none() {
+        let mut fetch = process::Command::new("git");
+        let _ = fetch.args(["fetch", "--quiet", "--tags", "origin"]);
+        run_git(fetch.current_dir(&directory), "git fetch")?;
+    }
+    if wanted.is_none() || wanted != head {
+        let mut checkout = process::Command::new("git");
+        let _ = checkout.args(["checkout", "--quiet", reference]);
+        run_git(checkout.current_dir(&directory), "git checkout")?;
+    }
+    Ok(directory)
+}
+
+/// Run a git command in a cloned repository, returning its output, or `None`
+/// if the directory is not a clone or the command failed.
+fn git_output(directory: &Path, args: &[&str]) -> Option<String> {
+    if !directory.join(".git").is_dir() {
+        return None;
+    }
+    let output = process::Command::new("git")
+        .args(args)
+        .current_dir(directory)
+        .output()
+        .ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
+
+fn run_git(command: &mut process::Command, name: &str) -> Result<()> {
+    tracing::trace!("Running OS process {:?}", command);
+    let status = command.status().map_err(|e| Error::ShellCommand {
+        command: name.to_string(),
+        err: Some(e.kind()),
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::ShellCommand {
+            command: name.to_string(),
+            err: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Registry for HashMap<&str, Vec<&str>> {
+        fn versions(&self, package: &str) -> Result<Vec<String>> {
+            self.get(package)
+                .map(|versions| versions.iter().map(|v| v.to_string()).collect())
+                .ok_or_else(|| Error::UnknownPackage {
+                    name: package.to_string(),
+                })
+        }
+    }
+
+    fn registry() -> HashMap<&'static str, Vec<&'static str>> {
+        vec![
+            ("gleam_stdlib", vec!["0.15.0", "0.16.0", "0.17.0-rc1"]),
+            ("gleam_http", vec!["1.2.0", "2.0.0", "2.1.3"]),
+            ("unreleased", vec!["1.0.0-rc1"]),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn add(toml: &str, table: Table, name: &str, requirement: &str) -> Result<String> {
+        add_to_toml(Path::new("gleam.toml"), toml, table, name, requirement)
+    }
+
+    #[test]
+    fn requirement_from_latest_stable_version() {
+        let registry = registry();
+        assert_eq!(
+            resolve_requirement(&registry, "gleam_stdlib", None),
+            Ok("~> 0.16.0".to_string())
+        );
+        assert_eq!(
+            resolve_requirement(&registry, "gleam_http", None),
+            Ok("~> 2.1".to_string())
+        );
+        assert_eq!(
+            resolve_requirement(&registry, "gleam_http", Some("~> 1.2")),
+            Ok("~> 1.2".to_string())
+        );
+        assert!(resolve_requirement(&registry, "unreleased", None).is_err());
+    }
+
+    #[test]
+    fn requirement_for_invalid_package() {
+        let registry = registry();
+        assert_eq!(
+            resolve_requirement(&registry, "nope", None),
+            Err(Error::UnknownPackage {
+                name: "nope".to_string()
+            })
+        );
+        assert_eq!(
+            resolve_requirement(&registry, "Nope", None),
+            Err(Error::InvalidPackageName {
+                name: "Nope".to_string()
+            })
+        );
+        assert_eq!(
+            resolve_requirement(&registry, "gleam_http", Some("\"1\"")),
+            Err(Error::InvalidVersionRequirement {
+                requirement: "\"1\"".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn add_to_existing_table() {
+        let toml = r#"name = "app"
+
+# Runtime dependencies
+[dependencies]
+gleam_stdlib = "~> 0.16.0" # the standard library
+
+[docs]
+pages = []
+"#;
+        assert_eq!(
+            add(toml, Table::Dependencies, "gleam_http", "~> 2.1"),
+            Ok(r#"name = "app"
+
+# Runtime dependencies
+[dependencies]
+gleam_stdlib = "~> 0.16.0" # the standard library
+gleam_http = "~> 2.1"
+
+[docs]
+pages = []
+"#
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn add_replaces_existing_requirement() {
+        let toml = "name = \"app\"\n\n[dependencies]\ngleam_stdlib = \"~> 0.15.0\"\n";
+        assert_eq!(
+            add(toml, Table::Dependencies, "gleam_stdlib", "~> 0.16.0"),
+            Ok("name = \"app\"\n\n[dependencies]\ngleam_stdlib = \"~> 0.16.0\"\n".to_string())
+        );
+    }
+
+    #[test]
+    fn add_keeps_comment_of_replaced_requirement() {
+        let toml = "name = \"app\"\n\n[dependencies]\n\
+                    gleam_stdlib = \"~> 0.15.0\"  # the \"#1\" library\n";
+        assert_eq!(
+            add(toml, Table::Dependencies, "gleam_stdlib", "~> 0.16.0"),
+            Ok("name = \"app\"\n\n[dependencies]\n\
+                gleam_stdlib = \"~> 0.16.0\"  # the \"#1\" library\n"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn add_creates_table() {
+        let toml = "name = \"app\"\n# repository = { type = \"github\" }";
+        assert_eq!(
+            add(toml, Table::DevDependencies, "gleeunit", "~> 0.1.0"),
+            Ok("name = \"app\"\n# repository = { type = \"github\" }\n\n\
+                [dev-dependencies]\ngleeunit = \"~> 0.1.0\"\n"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn add_moves_between_tables() {
+        let toml = "name = \"app\"\n\n[dependencies]\ngleeunit = \"~> 0.1.0\"\n";
+        assert_eq!(
+            add(toml, Table::DevDependencies, "gleeunit", "~> 0.1.0"),
+            Ok("name = \"app\"\n\n[dependencies]\n\n[dev-dependencies]\n\
+                gleeunit = \"~> 0.1.0\"\n"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn add_to_inline_table_is_an_error() {
+        let toml = "name = \"app\"\ndependencies = { gleam_stdlib = \"~> 0.16.0\" }\n";
+        assert!(matches!(
+            add(toml, Table::Dependencies, "gleam_http", "~> 2.1"),
+            Err(Error::UnableToEditConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn remove_from_tables() {
+        let toml = r#"name = "app"
+
+[dependencies]
+gleam_stdlib = "~> 0.16.0"
+"gleam_http" = "~> 2.1"
+
+[dev-dependencies]
+gleeunit = "~> 0.1.0"
+"#;
+        let path = Path::new("gleam.toml");
+        assert_eq!(
+            remove_from_toml(path, toml, "gleam_http"),
+            Ok((
+                r#"name = "app"
+
+[dependencies]
+gleam_stdlib = "~> 0.16.0"
+
+[dev-dependencies]
+gleeunit = "~> 0.1.0"
+"#
+                .to_string(),
+                vec![Table::Dependencies]
+            ))
+        );
+        assert_eq!(
+            remove_from_toml(path, toml, "nope"),
+            Err(Error::DependencyNotFound {
+                name: "nope".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rebar_config_deps() {
+        let config = r#"{erl_opts, [debug_info]}.
+
+{deps, [
+    {gleam_stdlib, "0.16.0"}, % the standard library
+    gleam_otp
+]}.
+"#;
+        let added = add_to_rebar_config(config, "gleam_http", "~> 2.1");
+        assert_eq!(
+            added,
+            r#"{erl_opts, [debug_info]}.
+
+{deps, [
+    {gleam_stdlib, "0.16.0"}, % the standard library
+    gleam_otp,
+    {gleam_http, "~> 2.1"}
+]}.
+"#
+        );
+        assert_eq!(
+            add_to_rebar_config(config, "gleam_otp", "~> 0.1.5"),
+            r#"{erl_opts, [debug_info]}.
+
+{deps, [
+    {gleam_stdlib, "0.16.0"}, % the standard library
+    {gleam_otp, "~> 0.1.5"}
+]}.
+"#
+        );
+        assert_eq!(remove_from_rebar_config(&added, "gleam_http"), config);
+        assert_eq!(
+            remove_from_rebar_config(config, "gleam_stdlib"),
+            r#"{erl_opts, [debug_info]}.
+
+{deps, [
+    gleam_otp
+]}.
+"#
+        );
+    }
+
+    #[test]
+    fn rebar_config_without_deps() {
+        assert_eq!(
+            add_to_rebar_config("{erl_opts, []}.\n{deps, []}.\n", "gleam_http", "~> 2.1"),
+            "{erl_opts, []}.\n{deps, [\n    {gleam_http, \"~> 2.1\"}\n]}.\n"
+        );
+        assert_eq!(
+            add_to_rebar_config("{erl_opts, []}.", "gleam_http", "~> 2.1"),
+            "{erl_opts, []}.\n\n{deps, [\n    {gleam_http, \"~> 2.1\"}\n]}.\n"
+        );
+    }
+
+    #[test]
+    fn parse_dependency_sources() {
+        let toml = r#"name = "app"
+
+[dependencies]
+gleam_stdlib = "~> 0.16.0"
+my_lib = { path = "../my_lib" }
+my_other_lib = { git = "https://example.com/my_other_lib.git", ref = "v1.0.0" }
+
+[dev-dependencies]
+my_test_lib = { git = "../my_test_lib" }
+"#;
+        let config = config::parse_project_config(Path::new("gleam.toml"), toml).expect("parse");
+        assert_eq!(
+            config.dependencies,
+            vec![
+                ("gleam_stdlib", Dependency::Hex("~> 0.16.0".to_string())),
+                (
+                    "my_lib",
+                    Dependency::Path {
+                        path: PathBuf::from("../my_lib")
+                    }
+                ),
+                (
+                    "my_other_lib",
+                    Dependency::Git {
+                        git: "https://example.com/my_other_lib.git".to_string(),
+                        reference: Some("v1.0.0".to_string())
+                    }
+                ),
+            ]
+            .into_iter()
+            .map(|(name, dependency)| (name.to_string(), dependency))
+            .collect()
+        );
+        assert_eq!(
+            config.dev_dependencies.get("my_test_lib"),
+            Some(&Dependency::Git {
+                git: "../my_test_lib".to_string(),
+                reference: None
+            })
+        );
+    }
+
+    fn write_package(path: &Path, name: &str, dependencies: &str) {
+        crate::fs::write_output(&OutputFile {
+            path: path.join("gleam.toml"),
+            text: format!(
+                "name = \"{}\"\ntool = \"gleam\"\n\n[dependencies]\n{}",
+                name, dependencies
+            ),
+        })
+        .expect("write gleam.toml");
+        crate::fs::write_output(&OutputFile {
+            path: path.join("src").join(format!("{}.gleam", name)),
+            text: "pub fn main() { 1 }\n".to_string(),
+        })
+        .expect("write module");
+    }
+
+    fn git(path: &Path, args: &[&str]) {
+        let mut command = process::Command::new("git");
+        let _ = command
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(path);
+        run_git(&mut command, "git").expect("git");
+    }
+
+    #[test]
+    fn copy_path_and_git_packages() {
+        let dir = std::env::temp_dir().join(format!("gleam-local-packages-{}", process::id()));
+        crate::fs::delete_dir(&dir).expect("delete");
+
+        // A git package with an unreleased change after the tagged version
+        let repo = dir.join("git_lib");
+        write_package(&repo, "git_lib", "");
+        git(&repo, &["init", "--quiet"]);
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "--quiet", "-m", "First"]);
+        git(&repo, &["tag", "v1"]);
+        crate::fs::delete(&repo.join("src").join("git_lib.gleam")).expect("delete");
+        crate::fs::write_output(&OutputFile {
+            path: repo.join("src").join("other.gleam"),
+            text: "pub fn main() { 2 }\n".to_string(),
+        })
+        .expect("write module");
+        git(&repo, &["add", "--all"]);
+        git(&repo, &["commit", "--quiet", "-m", "Second"]);
+
+        // A path package that depends on the git package, relative to itself
+        write_package(
+            &dir.join("path_lib"),
+            "path_lib",
+            "git_lib = { git = \"../git_lib\", ref = \"v1\" }\n",
+        );
+        let app = dir.join("app");
+        write_package(
+            &app,
+            "app",
+            "path_lib = { path = \"../path_lib\" }\ngleam_stdlib = \"~> 0.16.0\"\n",
+        );
+
+        let root = ProjectRoot::new(app.clone());
+        let config = config::read_project_config(&app).expect("config");
+        copy_local_packages(&root, &config).expect("copy");
+
+        let lib = root.default_build_lib_path();
+        assert!(lib.join("path_lib/gleam.toml").is_file());
+        assert!(lib.join("path_lib/src/path_lib.gleam").is_file());
+        assert!(lib.join("git_lib/gleam.toml").is_file());
+        assert!(lib.join("git_lib/src/git_lib.gleam").is_file());
+        assert!(!lib.join("git_lib/src/other.gleam").exists());
+        assert!(!lib.join("app").exists());
+        assert!(!lib.join("gleam_stdlib").exists());
+
+        // Compiled code is kept and the clone is reused while the ref is the
+        // same
+        let compiled = lib.join("git_lib/src/git_lib.erl");
+        let clone_file = root.build_path().join("git/git_lib/untracked");
+        crate::fs::write_outputs(&[
+            OutputFile {
+                path: compiled.clone(),
+                text: String::new(),
+            },
+            OutputFile {
+                path: clone_file.clone(),
+                text: String::new(),
+            },
+        ])
+        .expect("write");
+        copy_local_packages(&root, &config).expect("copy");
+        assert!(compiled.is_file());
+        assert!(clone_file.is_file());
+
+        // Changing the ref checks out the new version, removing deleted modules
+        git(&repo, &["tag", "v2"]);
+        write_package(
+            &dir.join("path_lib"),
+            "path_lib",
+            "git_lib = { git = \"../git_lib\", ref = \"v2\" }\n",
+        );
+        copy_local_packages(&root, &config).expect("copy");
+        assert!(!lib.join("git_lib/src/git_lib.gleam").exists());
+        assert!(lib.join("git_lib/src/other.gleam").is_file());
+        assert!(clone_file.is_file());
+
+        crate::fs::delete_dir(&dir).expect("delete");
+    }
+}
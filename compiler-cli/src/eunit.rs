@@ -1,5 +1,7 @@
 use gleam_core::{
     build::{project_root::ProjectRoot, Origin},
+    config::PackageConfig,
+    doctest,
     error::Error,
     io::OutputFile,
 };
@@ -13,13 +15,17 @@ struct EunitFile {
     content: String,
 }
 
-pub fn command(root_string: String) -> Result<(), Error> {
+pub fn command(root_string: String, doctests: bool) -> Result<(), Error> {
     let root_path = PathBuf::from(root_string);
     let root = ProjectRoot::new(root_path.clone());
     let config = crate::config::root_config(&root)?;
 
     // Build project
-    let packages = crate::new_build_main(config, root_path)?;
+    let options = crate::BuildOptions {
+        doctests,
+        ..Default::default()
+    };
+    let packages = crate::new_build_main(config, root_path, &options)?;
 
     crate::cli::print_running("eunit");
 
@@ -99,3 +105,35 @@ pub fn command(root_string: String) -> Result<(), Error> {
         })
     }
 }
+
+/// Write a test module for each module of the root package that has Gleam code
+/// examples in its documentation. The modules are written to the test directory
+/// of the package in _build so they are compiled along with the other tests.
+pub fn write_doctests(root: &ProjectRoot, config: &PackageConfig) -> Result<(), Error> {
+    let src_path = root.src_path();
+    let test_path = root.default_build_lib_package_test_path(&config.name);
+
+    for path in crate::fs::gleam_files(&src_path) {
+        let relative_path = path
+            .strip_prefix(&root.root)
+            .expect("doctest source path within project root");
+        let module = relative_path
+            .strip_prefix("src")
+            .expect("doctest source path within src")
+            .with_extension("")
+            .to_string_lossy()
+            .replace("\\", "/");
+        let src = crate::fs::read(&path)?;
+        let examples = doctest::examples(&path, &src)?;
+        let display_path = relative_path.to_string_lossy();
+
+        if let Some(text) = doctest::test_module(&module, &display_path, &examples) {
+            let name = doctest::test_module_name(&module);
+            crate::fs::write_output(&OutputFile {
+                path: test_path.join(format!("{}.gleam", name)),
+                text,
+            })?;
+        }
+    }
+    Ok(())
+}
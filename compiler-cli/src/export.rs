@@ -0,0 +1,85 @@
+use crate::{config, project, BuildOptions};
+use gleam_core::{
+    build::{Module, Origin},
+    error::Error,
+    io::OutputFile,
+    package_interface::{ModuleInterface, PackageInterface},
+    project::ModuleOrigin,
+    type_,
+};
+use std::{collections::HashMap, path::PathBuf};
+
+pub fn package_interface(project_root: String, out: PathBuf) -> Result<(), Error> {
+    let interface = read_package_interface(project_root)?;
+    crate::fs::write_output(&OutputFile {
+        path: out,
+        text: interface.to_json(),
+    })?;
+    println!("Done!");
+    Ok(())
+}
+
+/// Compile the project and describe the public interface of its `src`
+/// modules.
+pub fn read_package_interface(project_root: String) -> Result<PackageInterface, Error> {
+    analyse_package(project_root).map(|(interface, _)| interface)
+}
+
+/// Compile the project, returning the public interface of its `src` modules
+/// and the modules of its dependencies.
+pub fn analyse_package(
+    project_root: String,
+) -> Result<(PackageInterface, HashMap<String, type_::Module>), Error> {
+    let root = PathBuf::from(&project_root);
+    let root_config = config::read_project_config(&root)?;
+    let mut dependencies = HashMap::new();
+
+    if root_config.tool == gleam_core::config::BuildTool::Gleam {
+        let name = root_config.name.clone();
+        let version = root_config.version.clone();
+        let mut packages = crate::new_build_main(root_config, root, &BuildOptions::default())?;
+        let modules = packages
+            .remove(&name)
+            .map(|package| package.modules)
+            .unwrap_or_default();
+        for module in packages.into_values().flat_map(|package| package.modules) {
+            let _ = dependencies.insert(module.name, module.ast.type_info);
+        }
+        return Ok((package_interface_of(name, version, &modules), dependencies));
+    }
+
+    let mut interface =
+        PackageInterface::new(root_config.name.clone(), root_config.version.clone());
+    let (_config, analysed) = project::read_and_analyse(&root)?;
+    for mut module in analysed {
+        match module.origin {
+            ModuleOrigin::Src => {
+                module.attach_doc_and_module_comments();
+                interface.insert_module(
+                    ModuleInterface::from_typed_module(&module.ast),
+                    module.name.join("/"),
+                );
+            }
+            ModuleOrigin::Dependency => {
+                let _ = dependencies.insert(module.name.join("/"), module.type_info);
+            }
+            ModuleOrigin::Test => (),
+        }
+    }
+    Ok((interface, dependencies))
+}
+
+/// Describe the public interface of the `src` modules of a package compiled by
+/// the build tool.
+pub fn package_interface_of(name: String, version: String, modules: &[Module]) -> PackageInterface {
+    let mut interface = PackageInterface::new(name, version);
+    for module in modules {
+        if module.origin == Origin::Src {
+            interface.insert_module(
+                ModuleInterface::from_typed_module(&module.ast),
+                module.name.clone(),
+            );
+        }
+    }
+    interface
+}
@@ -43,6 +43,10 @@ impl gleam_core::io::FileSystemReader for FileSystemAccessor {
     {
         read(path)
     }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
 }
 
 impl FileSystemWriter for FileSystemAccessor {
@@ -72,6 +76,10 @@ impl FileSystemWriter for FileSystemAccessor {
 
         Ok(WrappedWriter::new(path, Box::new(file)))
     }
+
+    fn delete(&self, path: &Path) -> Result<(), Error> {
+        delete(path)
+    }
 }
 
 impl FileSystemIO for FileSystemAccessor {}
@@ -91,20 +99,20 @@ pub fn delete_dir(dir: &Path) -> Result<(), Error> {
     Ok(())
 }
 
-// pub fn delete(file: &PathBuf) -> Result<(), Error> {
-//     tracing::trace!("Deleting file {:?}", file);
-//     if file.exists() {
-//         std::fs::remove_file(&file).map_err(|e| Error::FileIO {
-//             action: FileIOAction::Delete,
-//             kind: FileKind::File,
-//             path: file.clone(),
-//             err: Some(e.to_string()),
-//         })?;
-//     } else {
-//         tracing::trace!("Did not exist for deletion: {:?}", file);
-//     }
-//     Ok(())
-// }
+pub fn delete(file: &Path) -> Result<(), Error> {
+    tracing::trace!("Deleting file {:?}", file);
+    if file.exists() {
+        std::fs::remove_file(file).map_err(|e| Error::FileIo {
+            action: FileIoAction::Delete,
+            kind: FileKind::File,
+            path: file.to_path_buf(),
+            err: Some(e.to_string()),
+        })?;
+    } else {
+        tracing::trace!("Did not exist for deletion: {:?}", file);
+    }
+    Ok(())
+}
 
 pub fn write_outputs(outputs: &[OutputFile]) -> Result<(), Error> {
     for file in outputs {
@@ -283,6 +291,17 @@ pub fn read(path: impl AsRef<Path> + Debug) -> Result<String, Error> {
     })
 }
 
+pub fn read_bytes(path: impl AsRef<Path> + Debug) -> Result<Vec<u8>, Error> {
+    tracing::trace!("Reading file {:?}", path);
+
+    std::fs::read(&path).map_err(|err| Error::FileIo {
+        action: FileIoAction::Read,
+        kind: FileKind::File,
+        path: PathBuf::from(path.as_ref()),
+        err: Some(err.to_string()),
+    })
+}
+
 pub fn buffered_reader<P: AsRef<Path> + Debug>(path: P) -> Result<impl BufRead, Error> {
     tracing::trace!("Opening {:?} for reading", path);
     let reader = File::open(&path).map_err(|err| Error::FileIo {
@@ -321,3 +340,46 @@ pub fn copy_dir(path: impl AsRef<Path> + Debug, to: impl AsRef<Path> + Debug) ->
         })
         .map(|_| ())
 }
+
+/// Copy the files of a directory into another, skipping those that are
+/// already the same. Gleam modules in the destination that are not in the
+/// source are deleted, while other files such as compiled code are kept.
+pub fn sync_dir(path: &Path, to: &Path) -> Result<(), Error> {
+    tracing::trace!("Syncing directory {:?} to {:?}", path, to);
+    mkdir(to)?;
+
+    for entry in walkdir::WalkDir::new(path).follow_links(true).min_depth(1) {
+        let entry = entry.map_err(|err| Error::FileIo {
+            kind: FileKind::Directory,
+            path: err.path().unwrap_or(path).to_path_buf(),
+            action: FileIoAction::Read,
+            err: Some(err.to_string()),
+        })?;
+        let target = to.join(
+            entry
+                .path()
+                .strip_prefix(path)
+                .expect("sync_dir(): strip_prefix"),
+        );
+        if entry.file_type().is_dir() {
+            mkdir(&target)?;
+        } else if !target.is_file() || read_bytes(entry.path())? != read_bytes(&target)? {
+            copy(entry.path(), &target)?;
+        }
+    }
+
+    let removed: Vec<_> = walkdir::WalkDir::new(to)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|entry| entry.into_path())
+        .filter(|target| target.extension().and_then(OsStr::to_str) == Some("gleam"))
+        .filter(|target| {
+            let relative = target.strip_prefix(to).expect("sync_dir(): strip_prefix");
+            !path.join(relative).is_file()
+        })
+        .collect();
+    for target in removed {
+        delete(&target)?;
+    }
+    Ok(())
+}
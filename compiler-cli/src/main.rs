@@ -53,13 +53,16 @@ extern crate pretty_assertions;
 mod cli;
 mod compile_package;
 mod config;
+mod dependencies;
 mod docs;
 mod eunit;
+mod export;
 mod format;
 mod fs;
 mod new;
 mod panic;
 mod project;
+mod semver;
 mod shell;
 
 pub use gleam_core::{
@@ -72,11 +75,12 @@ use gleam_core::{
     config::PackageConfig,
     io::OutputFile,
     project::Analysed,
+    warning::Warnings,
 };
 
-use std::{collections::HashMap, path::PathBuf, process};
+use std::{collections::HashMap, path::PathBuf, process, sync::Arc, time::Instant};
 use structopt::{clap::AppSettings, StructOpt};
-use strum::VariantNames;
+use strum::{Display, EnumString, EnumVariantNames, VariantNames};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -93,6 +97,30 @@ enum Command {
         /// Emit compile time warnings as errors
         #[structopt(long)]
         warnings_as_errors: bool,
+
+        /// Write the time taken by each phase of compiling each module to a file
+        #[structopt(long)]
+        timings: Option<PathBuf>,
+
+        /// The format of the timings file
+        #[structopt(
+            long,
+            possible_values = &TimingsFormat::VARIANTS,
+            case_insensitive = true,
+            default_value = "json"
+        )]
+        timings_format: TimingsFormat,
+    },
+
+    /// Type check the project without generating or compiling any code
+    Check {
+        /// Location of the project root
+        #[structopt(default_value = ".")]
+        project_root: String,
+
+        /// Emit compile time warnings as errors
+        #[structopt(long)]
+        warnings_as_errors: bool,
     },
 
     /// Render HTML documentation
@@ -101,6 +129,48 @@ enum Command {
     /// Create a new project
     New(NewOptions),
 
+    /// Export something useful from the Gleam project
+    Export(Export),
+
+    /// Add a package as a dependency of the project
+    Add {
+        /// The package to add, optionally followed by `@` and a version
+        /// requirement, such as `gleam_http@~> 2.1`
+        package: String,
+
+        /// Location of the project root
+        #[structopt(default_value = ".")]
+        project_root: String,
+
+        /// Add the package as a dependency that is only used by tests
+        #[structopt(long)]
+        dev: bool,
+    },
+
+    /// Remove a package from the dependencies of the project
+    Remove {
+        /// The package to remove
+        package: String,
+
+        /// Location of the project root
+        #[structopt(default_value = ".")]
+        project_root: String,
+    },
+
+    /// Check the version is suitable for the changes since a previous release
+    CheckSemver {
+        /// Location of the project root
+        #[structopt(default_value = ".")]
+        project_root: String,
+
+        /// The previous release to compare against, given as a version of the
+        /// package published to Hex, the path of a package interface JSON file
+        /// written by `gleam export package-interface`, or the path of a
+        /// directory of `.gleam_module` metadata files
+        #[structopt(long)]
+        against: String,
+    },
+
     /// Format source code
     Format {
         /// Files to format
@@ -125,11 +195,15 @@ enum Command {
     },
 
     /// Run eunit tests
-    #[structopt(setting = AppSettings::Hidden)]
+    #[structopt(setting = AppSettings::Hidden, alias = "test")]
     Eunit {
         /// Location of the project root
         #[structopt(default_value = ".")]
         project_root: String,
+
+        /// Also run the Gleam code examples in documentation comments
+        #[structopt(long)]
+        doc: bool,
     },
 
     /// Compile a single Gleam package
@@ -158,6 +232,11 @@ pub struct NewOptions {
         default_value = "lib"
     )]
     pub template: new::Template,
+
+    /// A directory to copy as the project instead of a built in template.
+    /// Placeholders such as `{{name}}` in file names and contents are replaced
+    #[structopt(long, conflicts_with = "template")]
+    pub template_dir: Option<PathBuf>,
 }
 
 #[derive(StructOpt, Debug)]
@@ -171,9 +250,9 @@ pub struct CompilePackage {
     #[structopt(long = "name")]
     package_name: String,
 
-    /// A directory of source Gleam code
-    #[structopt(long = "src")]
-    src_directory: PathBuf,
+    /// A directory of source Gleam code. May be given multiple times
+    #[structopt(long = "src", required = true, min_values = 1)]
+    src_directories: Vec<PathBuf>,
 
     /// A directory of test Gleam code
     #[structopt(long = "test")]
@@ -183,9 +262,14 @@ pub struct CompilePackage {
     #[structopt(long = "out")]
     output_directory: PathBuf,
 
-    /// A path to a compiled dependency library
+    /// A compiled dependency library, given as `name=path` where `name` is the
+    /// name of the package the library was compiled from
     #[structopt(long = "lib")]
-    libraries: Vec<PathBuf>,
+    libraries: Vec<Library>,
+
+    /// Emit compile time warnings as errors
+    #[structopt(long)]
+    warnings_as_errors: bool,
 }
 
 impl CompilePackage {
@@ -193,13 +277,54 @@ impl CompilePackage {
         package_compiler::Options {
             target: self.target,
             name: self.package_name,
-            src_path: self.src_directory,
+            src_paths: self.src_directories,
             test_path: self.test_directory,
             out_path: self.output_directory,
         }
     }
 }
 
+/// A precompiled library given to `compile-package`. The package name may be
+/// omitted, in which case the metadata is not checked against it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Library {
+    pub package: Option<String>,
+    pub path: PathBuf,
+}
+
+impl std::str::FromStr for Library {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(""), Some(_)) => Err(format!("Missing package name in library `{}`", s)),
+            (Some(package), Some(path)) => Ok(Self {
+                package: Some(package.to_string()),
+                path: PathBuf::from(path),
+            }),
+            _ => Ok(Self {
+                package: None,
+                path: PathBuf::from(s),
+            }),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+enum Export {
+    /// A JSON description of the public API of the package
+    PackageInterface {
+        /// Location of the project root
+        #[structopt(default_value = ".")]
+        project_root: String,
+
+        /// The path to write the JSON file to
+        #[structopt(long)]
+        out: PathBuf,
+    },
+}
+
 #[derive(StructOpt, Debug)]
 enum Docs {
     /// Render HTML docs locally
@@ -248,7 +373,21 @@ fn main() {
         Command::Build {
             project_root,
             warnings_as_errors,
-        } => command_build(project_root, warnings_as_errors),
+            timings,
+            timings_format,
+        } => command_build(
+            project_root,
+            BuildOptions {
+                warnings_as_errors,
+                timings: timings.map(|path| (path, timings_format)),
+                ..BuildOptions::default()
+            },
+        ),
+
+        Command::Check {
+            project_root,
+            warnings_as_errors,
+        } => command_check(project_root, warnings_as_errors),
 
         Command::Docs(Docs::Build {
             project_root,
@@ -271,9 +410,29 @@ fn main() {
 
         Command::New(options) => new::create(options, VERSION),
 
+        Command::Export(Export::PackageInterface { project_root, out }) => {
+            export::package_interface(project_root, out)
+        }
+
+        Command::Add {
+            package,
+            project_root,
+            dev,
+        } => dependencies::add(project_root, package, dev),
+
+        Command::Remove {
+            package,
+            project_root,
+        } => dependencies::remove(project_root, package),
+
+        Command::CheckSemver {
+            project_root,
+            against,
+        } => semver::command(project_root, against),
+
         Command::Shell { project_root } => shell::command(project_root),
 
-        Command::Eunit { project_root } => eunit::command(project_root),
+        Command::Eunit { project_root, doc } => eunit::command(project_root, doc),
 
         Command::CompilePackage(opts) => compile_package::command(opts),
     };
@@ -295,30 +454,25 @@ fn main() {
     }
 }
 
-fn command_build(root: String, warnings_as_errors: bool) -> Result<(), Error> {
+fn command_build(root: String, options: BuildOptions) -> Result<(), Error> {
     let root = PathBuf::from(&root);
     let config = config::read_project_config(&root)?;
 
     // Use new build tool
     if config.tool == gleam_core::config::BuildTool::Gleam {
-        return new_build_main(config, root).map(|_| ());
+        return new_build_main(config, root, &options).map(|_| ());
     }
 
     // Read and type check project
-    let (_config, analysed) = project::read_and_analyse(&root)?;
+    let (config, analysed) = project::read_and_analyse(&root)?;
 
     // Generate Erlang code
     let output_files = gleam_core::erl::generate_erlang(&analysed);
 
-    // Print warnings
-    let warning_count = print_warnings(&analysed);
-
-    // Exit if warnings_as_errors and warnings
-    if warnings_as_errors && warning_count > 0 {
-        return Err(Error::ForbiddenWarnings {
-            count: warning_count,
-        });
-    }
+    // Print warnings, exiting if any are to be treated as errors
+    let warnings = analysed_warnings(&config, &analysed);
+    print_warnings(&warnings);
+    warnings.check(options.warnings_as_errors)?;
 
     // Reset output directory
     fs::delete_dir(&root.join(project::OUTPUT_DIR_NAME))?;
@@ -331,6 +485,27 @@ fn command_build(root: String, warnings_as_errors: bool) -> Result<(), Error> {
     Ok(())
 }
 
+fn command_check(root: String, warnings_as_errors: bool) -> Result<(), Error> {
+    let root = PathBuf::from(&root);
+    let config = config::read_project_config(&root)?;
+
+    // Use new build tool
+    if config.tool == gleam_core::config::BuildTool::Gleam {
+        let options = BuildOptions {
+            check: true,
+            warnings_as_errors,
+            ..BuildOptions::default()
+        };
+        return new_build_main(config, root, &options).map(|_| ());
+    }
+
+    // Read and type check project
+    let (config, analysed) = project::read_and_analyse(&root)?;
+    let warnings = analysed_warnings(&config, &analysed);
+    print_warnings(&warnings);
+    warnings.check(warnings_as_errors)
+}
+
 fn initialise_logger() {
     tracing_subscriber::fmt()
         .with_env_filter(&std::env::var("GLEAM_LOG").unwrap_or_else(|_| "off".to_string()))
@@ -339,26 +514,93 @@ fn initialise_logger() {
         .init();
 }
 
+/// Options for how the new build tool builds a project.
+#[derive(Debug, Default, Clone)]
+pub struct BuildOptions {
+    /// Generate and compile test modules for the code examples in the
+    /// documentation of the root package.
+    pub doctests: bool,
+
+    /// Write the timings of each module to this file in this format.
+    pub timings: Option<(PathBuf, TimingsFormat)>,
+
+    /// Only parse and type check the project, without generating code or
+    /// compiling it to BEAM bytecode.
+    pub check: bool,
+
+    /// Fail if there are any warnings, rather than only if there are warnings
+    /// that are denied in `gleam.toml`.
+    pub warnings_as_errors: bool,
+}
+
+#[derive(Debug, Display, EnumString, EnumVariantNames, Clone, Copy, PartialEq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum TimingsFormat {
+    /// A JSON array of timings
+    Json,
+    /// The Chrome trace event format
+    Chrome,
+}
+
 pub fn new_build_main(
     root_config: PackageConfig,
     path: PathBuf,
+    options: &BuildOptions,
 ) -> Result<HashMap<String, Package>, Error> {
+    let start = Instant::now();
     let root = ProjectRoot::new(path);
-    let telemetry = Box::new(cli::Reporter::new());
+    let reporter = Arc::new(match options.timings {
+        Some(_) => cli::Reporter::with_timings(),
+        None => cli::Reporter::new(),
+    });
     let io = fs::FileSystemAccessor::new();
 
     tracing::info!("Copying root package to _build");
     copy_root_package_to_build(&root, &root_config)?;
 
+    tracing::info!("Copying path and git dependencies to _build");
+    dependencies::copy_local_packages(&root, &root_config)?;
+
+    if options.doctests {
+        tracing::info!("Writing documentation example tests to _build");
+        eunit::write_doctests(&root, &root_config)?;
+    }
+
     tracing::info!("Reading package configs from _build");
     let configs = config::package_configs(&root, &root_config.name)?;
 
     tracing::info!("Compiling packages");
-    let packages = ProjectCompiler::new(&root, root_config, configs, telemetry, io).compile()?;
+    let target = root_config.target;
+    let (packages, warnings) =
+        ProjectCompiler::new(&root, root_config, configs, reporter.clone(), io)
+            .codegen(!options.check)
+            .compile()?;
+
+    print_warnings(&warnings);
+    warnings.check(options.warnings_as_errors)?;
+
+    if let (Some((path, format)), Some(timings)) = (&options.timings, reporter.timings()) {
+        tracing::info!("Writing timings to {:?}", path);
+        fs::write_output(&OutputFile {
+            path: path.clone(),
+            text: match format {
+                TimingsFormat::Json => timings.to_json(),
+                TimingsFormat::Chrome => timings.to_chrome_trace(),
+            },
+        })?;
+    }
 
-    tracing::info!("Compiling Erlang source code to BEAM bytecode");
-    compile_erlang_to_beam(&root)?;
+    if target == Target::Erlang && !options.check {
+        tracing::info!("Compiling Erlang source code to BEAM bytecode");
+        compile_erlang_to_beam(&root)?;
+    }
 
+    cli::print_summary(&cli::Summary {
+        packages: packages.len(),
+        warnings: warnings.len(),
+        duration: start.elapsed(),
+        check: options.check,
+    });
     Ok(packages)
 }
 
@@ -413,12 +655,16 @@ fn copy_root_package_to_build(
     Ok(())
 }
 
-fn print_warnings(analysed: &[Analysed]) -> usize {
-    analysed
-        .iter()
-        .flat_map(|a| &a.warnings)
-        .inspect(|w| print_warning(w))
-        .count()
+fn analysed_warnings(config: &PackageConfig, analysed: &[Analysed]) -> Warnings {
+    let mut warnings = Warnings::new(config.warnings.clone());
+    warnings.extend(analysed.iter().flat_map(|a| a.warnings.iter().cloned()));
+    warnings
+}
+
+fn print_warnings(warnings: &Warnings) {
+    for warning in warnings.iter() {
+        print_warning(warning);
+    }
 }
 
 fn print_warning(w: &Warning) {
@@ -15,6 +15,7 @@ const GLEAM_STDLIB_VERSION: &str = "0.16.0";
 const GLEAM_OTP_VERSION: &str = "0.1.5";
 const ERLANG_OTP_VERSION: &str = "23.2";
 const PROJECT_VERSION: &str = "0.1.0";
+const NODE_VERSION: &str = "16";
 
 #[derive(Debug, Serialize, Deserialize, Display, EnumString, EnumVariantNames, Clone, Copy)]
 #[strum(serialize_all = "kebab_case")]
@@ -23,6 +24,34 @@ pub enum Template {
     App,
     GleamLib,
     Escript,
+    JavascriptLib,
+    JavascriptApp,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageJson<'a> {
+    name: &'a str,
+    version: &'a str,
+    description: &'a str,
+    #[serde(rename = "type")]
+    type_: &'a str,
+    main: &'a str,
+    exports: PackageJsonExports<'a>,
+    scripts: PackageJsonScripts<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageJsonExports<'a> {
+    #[serde(rename = ".")]
+    root: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageJsonScripts<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<&'a str>,
+    build: &'a str,
+    test: &'a str,
 }
 
 #[derive(Debug)]
@@ -57,6 +86,10 @@ impl Creator {
     }
 
     fn run(&self) -> Result<()> {
+        if let Some(template_dir) = &self.options.template_dir {
+            return self.copy_template_dir(template_dir);
+        }
+
         crate::fs::mkdir(&self.root)?;
         crate::fs::mkdir(&self.src)?;
         crate::fs::mkdir(&self.test)?;
@@ -107,11 +140,112 @@ impl Creator {
                 self.src_module()?;
                 self.gleam_test_module()?;
             }
+
+            Template::JavascriptLib => {
+                self.javascript_gitignore()?;
+                self.javascript_github_ci()?;
+                self.javascript_lib_readme()?;
+                self.javascript_gleam_toml()?;
+                self.package_json()?;
+                self.javascript_test_runner()?;
+                self.src_module()?;
+                self.gleam_test_module()?;
+            }
+
+            Template::JavascriptApp => {
+                self.javascript_gitignore()?;
+                self.javascript_github_ci()?;
+                self.javascript_app_readme()?;
+                self.javascript_gleam_toml()?;
+                self.package_json()?;
+                self.index_html()?;
+                self.javascript_test_runner()?;
+                self.src_javascript_app_module()?;
+                self.gleam_test_module()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy a user supplied template directory to the project root, replacing
+    /// placeholders in the names and contents of the files. Files that are not
+    /// valid UTF-8 are copied unchanged.
+    fn copy_template_dir(&self, template_dir: &Path) -> Result<()> {
+        if !template_dir.is_dir() {
+            return Err(Error::FileIo {
+                kind: FileKind::Directory,
+                path: template_dir.to_path_buf(),
+                action: FileIoAction::Read,
+                err: Some("The template must be a directory".to_string()),
+            });
+        }
+
+        // The project cannot be created inside the template as it would then be
+        // copied into itself
+        if absolute_path(&self.root).starts_with(absolute_path(template_dir)) {
+            return Err(Error::FileIo {
+                kind: FileKind::Directory,
+                path: template_dir.to_path_buf(),
+                action: FileIoAction::Copy,
+                err: Some("The project cannot be created inside the template".to_string()),
+            });
+        }
+
+        crate::fs::mkdir(&self.root)?;
+
+        let entries = walkdir::WalkDir::new(template_dir)
+            .follow_links(true)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != ".git");
+
+        for entry in entries {
+            let entry = entry.map_err(|err| Error::FileIo {
+                kind: FileKind::Directory,
+                path: err.path().unwrap_or(template_dir).to_path_buf(),
+                action: FileIoAction::Read,
+                err: Some(err.to_string()),
+            })?;
+            let relative = entry
+                .path()
+                .strip_prefix(template_dir)
+                .expect("Template entry outside of template directory");
+            let path = self
+                .root
+                .join(self.replace_placeholders(&relative.to_string_lossy()));
+
+            if entry.file_type().is_dir() {
+                crate::fs::mkdir(&path)?;
+                continue;
+            }
+
+            match String::from_utf8(crate::fs::read_bytes(entry.path())?) {
+                Ok(text) => write(path, &self.replace_placeholders(&text))?,
+                Err(error) => write_bytes(path, error.as_bytes())?,
+            }
         }
 
         Ok(())
     }
 
+    fn replace_placeholders(&self, text: &str) -> String {
+        let placeholders = [
+            ("name", self.project_name.as_str()),
+            ("description", self.options.description.as_str()),
+            ("version", PROJECT_VERSION),
+            ("gleam_version", self.gleam_version),
+            ("erlang_otp_version", ERLANG_OTP_VERSION),
+            ("gleam_stdlib_version", GLEAM_STDLIB_VERSION),
+            ("gleam_otp_version", GLEAM_OTP_VERSION),
+        ];
+        placeholders
+            .iter()
+            .fold(text.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{{{}}}}}", name), value)
+            })
+    }
+
     fn src_escript_module(&self) -> Result<()> {
         write(
             self.src.join(format!("{}.gleam", self.project_name)),
@@ -164,6 +298,26 @@ pub fn stop(_state: Dynamic) {
         )
     }
 
+    fn src_javascript_app_module(&self) -> Result<()> {
+        write(
+            self.src.join(format!("{}.gleam", self.project_name)),
+            &format!(
+                r#"pub fn main() {{
+  log(hello_world())
+}}
+
+pub fn hello_world() -> String {{
+  "Hello, from {}!"
+}}
+
+external fn log(String) -> Nil =
+  "" "console.log"
+"#,
+                self.project_name
+            ),
+        )
+    }
+
     fn src_module(&self) -> Result<()> {
         write(
             self.src.join(format!("{}.gleam", self.project_name)),
@@ -262,6 +416,117 @@ pub fn stop(_state: Dynamic) {
         )
     }
 
+    /// The path of a compiled JavaScript module of the project, relative to
+    /// the project root.
+    fn javascript_module_path(&self, module: &str) -> String {
+        format!(
+            "_build/default/lib/{name}/src/{module}.js",
+            name = self.project_name,
+            module = module
+        )
+    }
+
+    fn package_json(&self) -> Result<()> {
+        let main = format!("./{}", self.javascript_module_path(&self.project_name));
+        let start = match self.options.template {
+            Template::JavascriptApp => Some("gleam build && npx serve ."),
+            _ => None,
+        };
+        let package = PackageJson {
+            name: &self.project_name,
+            version: PROJECT_VERSION,
+            description: &self.options.description,
+            type_: "module",
+            main: &main,
+            exports: PackageJsonExports { root: &main },
+            scripts: PackageJsonScripts {
+                start,
+                build: "gleam build",
+                test: "gleam build && node test_runner.mjs",
+            },
+        };
+        let mut json = serde_json::to_string_pretty(&package).expect("package.json serialisation");
+        json.push('\n');
+        write(self.root.join("package.json"), &json)
+    }
+
+    fn index_html(&self) -> Result<()> {
+        write(
+            self.root.join("index.html"),
+            &format!(
+                r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>{name}</title>
+    <script type="module">
+      import {{ main }} from "./{main}";
+      main();
+    </script>
+  </head>
+  <body></body>
+</html>
+"#,
+                name = self.project_name,
+                main = self.javascript_module_path(&self.project_name),
+            ),
+        )
+    }
+
+    fn javascript_test_runner(&self) -> Result<()> {
+        write(
+            self.root.join("test_runner.mjs"),
+            &format!(
+                r#"// Runs each public function ending in `_test` in the compiled test modules,
+// failing if any of them throw.
+
+import {{ readdirSync }} from "fs";
+import {{ join }} from "path";
+import {{ pathToFileURL }} from "url";
+
+function* testModules(directory) {{
+  for (const entry of readdirSync(directory, {{ withFileTypes: true }})) {{
+    const path = join(directory, entry.name);
+    if (entry.isDirectory()) yield* testModules(path);
+    else if (entry.name.endsWith("_test.js")) yield path;
+  }}
+}}
+
+let passed = 0;
+let failed = 0;
+
+for (const path of testModules("_build/default/lib/{name}/src")) {{
+  const module = await import(pathToFileURL(path));
+  for (const [name, test] of Object.entries(module)) {{
+    if (!name.endsWith("_test")) continue;
+    try {{
+      test();
+      passed++;
+    }} catch (error) {{
+      failed++;
+      console.error(`${{path}} ${{name}} failed:`, error);
+    }}
+  }}
+}}
+
+console.log(`${{passed}} passed, ${{failed}} failed`);
+process.exit(failed === 0 ? 0 : 1);
+"#,
+                name = self.project_name,
+            ),
+        )
+    }
+
+    fn javascript_gitignore(&self) -> Result<()> {
+        write(
+            self.root.join(".gitignore"),
+            "_build
+node_modules
+npm-debug.log
+",
+        )
+    }
+
     fn gitignore(&self) -> Result<()> {
         write(
             self.root.join(".gitignore"),
@@ -367,6 +632,65 @@ gleam shell
         )
     }
 
+    fn javascript_lib_readme(&self) -> Result<()> {
+        write(
+            self.root.join("README.md"),
+            &format!(
+                r#"# {name}
+
+{description}
+
+## Quick start
+
+```sh
+# Compile the project to JavaScript
+gleam build
+
+# Run the tests with Node
+npm test
+```
+
+## Usage
+
+The compiled ES modules can be imported from JavaScript:
+
+```javascript
+import {{ hello_world }} from "{name}";
+```
+"#,
+                name = self.project_name,
+                description = self.options.description
+            ),
+        )
+    }
+
+    fn javascript_app_readme(&self) -> Result<()> {
+        write(
+            self.root.join("README.md"),
+            &format!(
+                r#"# {name}
+
+{description}
+
+## Quick start
+
+```sh
+# Compile the project to JavaScript
+gleam build
+
+# Run the tests with Node
+npm test
+
+# Serve the app, then open it in a browser
+npm start
+```
+"#,
+                name = self.project_name,
+                description = self.options.description
+            ),
+        )
+    }
+
     fn lib_readme(&self) -> Result<()> {
         write(
             self.root.join("README.md"),
@@ -434,6 +758,38 @@ jobs:
         )
     }
 
+    fn javascript_github_ci(&self) -> Result<()> {
+        write(
+            self.workflows.join("test.yml"),
+            &format!(
+                r#"name: test
+
+on:
+  push:
+    branches:
+      - master
+      - main
+  pull_request:
+
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v2.0.0
+      - uses: actions/setup-node@v2
+        with:
+          node-version: {}
+      - uses: gleam-lang/setup-gleam@v1.0.2
+        with:
+          gleam-version: {}
+      - run: npm test
+      - run: gleam format --check src test
+"#,
+                NODE_VERSION, self.gleam_version
+            ),
+        )
+    }
+
     fn github_ci(&self) -> Result<()> {
         write(
             self.workflows.join("test.yml"),
@@ -481,6 +837,23 @@ description = "A Gleam library"
         )
     }
 
+    fn javascript_gleam_toml(&self) -> Result<()> {
+        write(
+            self.root.join("gleam.toml"),
+            &format!(
+                r#"name = "{name}"
+tool = "gleam"
+target = "javascript"
+version = "{version}"
+description = "{description}"
+"#,
+                name = self.project_name,
+                version = PROJECT_VERSION,
+                description = self.options.description,
+            ),
+        )
+    }
+
     fn gleam_toml(&self) -> Result<()> {
         write(
             self.root.join("gleam.toml"),
@@ -546,9 +919,22 @@ pub fn create(options: NewOptions, version: &'static str) -> Result<()> {
         format!("\tcd {}\n", creator.options.project_root)
     };
 
+    if let Some(template_dir) = &creator.options.template_dir {
+        println!(
+            "
+Your Gleam project {} has been successfully created from the template
+{}
+",
+            creator.project_name,
+            template_dir.display(),
+        );
+        return Ok(());
+    }
+
     let test_command = match &creator.options.template {
         Template::Lib | Template::App | Template::Escript => "rebar3 eunit",
         Template::GleamLib => "gleam eunit",
+        Template::JavascriptLib | Template::JavascriptApp => "npm test",
     };
 
     println!(
@@ -564,6 +950,10 @@ The project can be compiled and tested by running these commands:
 }
 
 fn write(path: PathBuf, contents: &str) -> Result<()> {
+    write_bytes(path, contents.as_bytes())
+}
+
+fn write_bytes(path: PathBuf, contents: &[u8]) -> Result<()> {
     let mut f = File::create(&path).map_err(|err| Error::FileIo {
         kind: FileKind::File,
         path: path.clone(),
@@ -571,16 +961,30 @@ fn write(path: PathBuf, contents: &str) -> Result<()> {
         err: Some(err.to_string()),
     })?;
 
-    f.write_all(contents.as_bytes())
-        .map_err(|err| Error::FileIo {
-            kind: FileKind::File,
-            path,
-            action: FileIoAction::WriteTo,
-            err: Some(err.to_string()),
-        })?;
+    f.write_all(contents).map_err(|err| Error::FileIo {
+        kind: FileKind::File,
+        path,
+        action: FileIoAction::WriteTo,
+        err: Some(err.to_string()),
+    })?;
     Ok(())
 }
 
+/// The absolute form of a path that may not exist yet, with symbolic links in
+/// the part that does exist resolved.
+fn absolute_path(path: &Path) -> PathBuf {
+    let path = env::current_dir()
+        .map(|dir| dir.join(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+    path.ancestors()
+        .find_map(|ancestor| {
+            let existing = ancestor.canonicalize().ok()?;
+            let rest = path.strip_prefix(ancestor).ok()?;
+            Some(existing.join(rest))
+        })
+        .unwrap_or(path)
+}
+
 fn validate_root_folder(name: &str) -> Result<(), Error> {
     if Path::new(name).exists() {
         Err(Error::ProjectRootAlreadyExist {
@@ -644,3 +1048,141 @@ fn get_foldername(path: &str) -> Result<String, Error> {
             }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn creator(project_root: &Path, template_dir: &Path) -> Creator {
+        let options = NewOptions {
+            project_root: project_root.to_string_lossy().to_string(),
+            name: None,
+            description: "My project".to_string(),
+            template: Template::Lib,
+            template_dir: Some(template_dir.to_path_buf()),
+        };
+        Creator::new(options, "my_project".to_string(), "0.17.0")
+    }
+
+    #[test]
+    fn copy_template_dir() {
+        let dir = env::temp_dir().join(format!("gleam-template-dir-{}", process::id()));
+        crate::fs::delete_dir(&dir).expect("delete");
+        let template = dir.join("template");
+        crate::fs::mkdir(template.join("src")).expect("mkdir");
+        crate::fs::mkdir(template.join(".git")).expect("mkdir");
+        write(
+            template.join("gleam.toml"),
+            "name = \"{{name}}\"\ndescription = \"{{description}}\"\n",
+        )
+        .expect("write");
+        write(template.join("src/{{name}}.gleam"), "// {{version}}\n").expect("write");
+        write(template.join(".git/HEAD"), "ref: refs/heads/main\n").expect("write");
+        write_bytes(template.join("logo.png"), &[0xff, 0x00]).expect("write");
+
+        let root = dir.join("my_project");
+        creator(&root, &template).run().expect("run");
+
+        assert_eq!(
+            crate::fs::read(root.join("gleam.toml")),
+            Ok("name = \"my_project\"\ndescription = \"My project\"\n".to_string())
+        );
+        assert_eq!(
+            crate::fs::read(root.join("src/my_project.gleam")),
+            Ok(format!("// {}\n", PROJECT_VERSION))
+        );
+        assert_eq!(
+            crate::fs::read_bytes(root.join("logo.png")),
+            Ok(vec![0xff, 0x00])
+        );
+        assert!(!root.join(".git").exists());
+
+        crate::fs::delete_dir(&dir).expect("delete");
+    }
+
+    #[test]
+    fn template_dir_conflicts_with_template() {
+        use structopt::StructOpt;
+        let parse = |args: &[&str]| NewOptions::from_iter_safe(args).map(|_| ());
+        assert!(parse(&["new", "app", "--template-dir", "t"]).is_ok());
+        assert!(parse(&["new", "app", "--template", "lib", "--template-dir", "t"]).is_err());
+    }
+
+    #[test]
+    fn project_inside_template_dir_is_an_error() {
+        let dir = env::temp_dir().join(format!("gleam-nested-template-{}", process::id()));
+        crate::fs::delete_dir(&dir).expect("delete");
+        crate::fs::mkdir(&dir).expect("mkdir");
+        let root = dir.join("my_project");
+        assert_eq!(
+            creator(&root, &dir).run(),
+            Err(Error::FileIo {
+                kind: FileKind::Directory,
+                path: dir.clone(),
+                action: FileIoAction::Copy,
+                err: Some("The project cannot be created inside the template".to_string()),
+            })
+        );
+        assert!(!root.exists());
+
+        crate::fs::delete_dir(&dir).expect("delete");
+    }
+
+    #[test]
+    fn missing_template_dir_is_an_error() {
+        let dir = env::temp_dir().join(format!("gleam-missing-template-{}", process::id()));
+        let template = dir.join("template");
+        let root = dir.join("my_project");
+        assert_eq!(
+            creator(&root, &template).run(),
+            Err(Error::FileIo {
+                kind: FileKind::Directory,
+                path: template,
+                action: FileIoAction::Read,
+                err: Some("The template must be a directory".to_string()),
+            })
+        );
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn package_json_escapes_description() {
+        let dir = env::temp_dir().join(format!("gleam-package-json-{}", process::id()));
+        crate::fs::delete_dir(&dir).expect("delete");
+        crate::fs::mkdir(&dir).expect("mkdir");
+        let options = NewOptions {
+            project_root: dir.to_string_lossy().to_string(),
+            name: None,
+            description: r#"A "quoted" \ project"#.to_string(),
+            template: Template::JavascriptApp,
+            template_dir: None,
+        };
+        Creator::new(options, "my_project".to_string(), "0.17.0")
+            .package_json()
+            .expect("package.json");
+
+        assert_eq!(
+            crate::fs::read(dir.join("package.json")),
+            Ok(r#"{
+  "name": "my_project",
+  "version": "0.1.0",
+  "description": "A \"quoted\" \\ project",
+  "type": "module",
+  "main": "./_build/default/lib/my_project/src/my_project.js",
+  "exports": {
+    ".": "./_build/default/lib/my_project/src/my_project.js"
+  },
+  "scripts": {
+    "start": "gleam build && npx serve .",
+    "build": "gleam build",
+    "test": "gleam build && node test_runner.mjs"
+  }
+}
+"#
+            .to_string())
+        );
+
+        crate::fs::delete_dir(&dir).expect("delete");
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{compile_package, config, dependencies::HEXPM_PUBLIC_KEY, export, fs};
+use gleam_core::{
+    build::{package_compiler, project_root::ProjectRoot, Origin, Target},
+    error::{Error, FileIoAction, FileKind},
+    package_interface::{ModuleInterface, PackageInterface},
+    semver::{self, Version},
+    type_,
+};
+use hexpm::Client;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+pub fn command(project_root: String, against: String) -> Result<(), Error> {
+    let root = PathBuf::from(&project_root);
+    let target = config::read_project_config(&root)?.target;
+    let (current, dependencies) = export::analyse_package(project_root)?;
+
+    // A version that is not the path of a file is fetched from Hex
+    let previous = match Version::parse(&against) {
+        Some(version) if !Path::new(&against).exists() => download_release(
+            &ProjectRoot::new(root),
+            target,
+            &current.name,
+            &version,
+            dependencies,
+        )?,
+        _ => read_previous_release(Path::new(&against))?,
+    };
+
+    let changes = semver::changes(&previous, &current);
+    let bump = semver::required_bump(&changes);
+    if changes.is_empty() {
+        println!("No changes to the public interface.");
+    }
+    for change in &changes {
+        println!("  {}", change);
+    }
+
+    let current_version = parse_version(&current.version)?;
+    let previous_version = parse_version(&previous.version)?;
+
+    let minimum = previous_version.minimum_next(bump);
+    if current_version < minimum {
+        return Err(Error::InsufficientVersionBump {
+            previous: previous_version.to_string(),
+            current: current_version.to_string(),
+            minimum: minimum.to_string(),
+            bump: bump.name().to_string(),
+        });
+    }
+
+    println!(
+        "\nThe changes since {} require a {} version bump, {} is suitable.",
+        previous_version,
+        bump.name(),
+        current_version
+    );
+    Ok(())
+}
+
+fn parse_version(version: &str) -> Result<Version, Error> {
+    Version::parse(version).ok_or_else(|| Error::InvalidVersion {
+        version: version.to_string(),
+    })
+}
+
+/// Read the interface of a previous release, either from a JSON file written by
+/// `gleam export package-interface` or from a directory of `.gleam_module`
+/// metadata files.
+fn read_previous_release(path: &Path) -> Result<PackageInterface, Error> {
+    if path.is_file() {
+        let json = fs::read(path)?;
+        return PackageInterface::from_json(path, &json);
+    }
+
+    if !path.is_dir() {
+        return Err(Error::FileIo {
+            kind: FileKind::File,
+            action: FileIoAction::Read,
+            path: path.to_path_buf(),
+            err: Some(
+                "The previous release must be a package interface JSON file or a
+directory of .gleam_module files"
+                    .to_string(),
+            ),
+        });
+    }
+
+    // The metadata is written to the `src` directory of a package in `_build`,
+    // next to the package's gleam.toml, which gives the version of the release.
+    let config = config::read_project_config(path)
+        .or_else(|_| config::read_project_config(path.join("..")))?;
+
+    let mut interface = PackageInterface::new(config.name, config.version);
+    for path in fs::gleam_modules_metadata_paths(PathBuf::from(path))? {
+        let module = compile_package::read_module_metadata(path)?;
+        if module.origin == Origin::Src {
+            interface.insert_module(
+                ModuleInterface::from_type_info(&module),
+                module.name.join("/"),
+            );
+        }
+    }
+    Ok(interface)
+}
+
+/// Download a release of the package from Hex and type check it against the
+/// dependencies of the current version of the package.
+fn download_release(
+    root: &ProjectRoot,
+    target: Target,
+    name: &str,
+    version: &Version,
+    mut dependencies: HashMap<String, type_::Module>,
+) -> Result<PackageInterface, Error> {
+    let version = version.to_string();
+    println!("Downloading {} {} from Hex", name, version);
+    let tarball = get_release_tarball(name, &version)?;
+
+    let directory = root
+        .build_path()
+        .join("semver")
+        .join(format!("{}-{}", name, version));
+    fs::delete_dir(&directory)?;
+    unpack_release(&tarball, &directory)?;
+
+    let package = package_compiler::Options {
+        target,
+        name: name.to_string(),
+        src_paths: vec![directory.join("src")],
+        test_path: None,
+        out_path: directory.join("build"),
+    }
+    .into_compiler(fs::FileSystemAccessor::new())?
+    .codegen(false)
+    .compile(&mut vec![], &mut dependencies, &mut HashMap::new())?;
+
+    Ok(export::package_interface_of(
+        name.to_string(),
+        version,
+        &package.modules,
+    ))
+}
+
+fn get_release_tarball(name: &str, version: &str) -> Result<Vec<u8>, Error> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let client = hexpm::UnauthenticatedClient::new();
+    let package = runtime
+        .block_on(client.get_package(name, HEXPM_PUBLIC_KEY))
+        .map_err(|error| match error {
+            hexpm::GetPackageError::NotFound => Error::UnknownPackage {
+                name: name.to_string(),
+            },
+            error => Error::Hex(error.to_string()),
+        })?;
+    let release = package
+        .releases
+        .into_iter()
+        .find(|release| release.version == version)
+        .ok_or_else(|| Error::UnknownPackageVersion {
+            name: name.to_string(),
+            version: version.to_string(),
+        })?;
+    runtime
+        .block_on(client.get_package_tarball(name, version, &release.outer_checksum))
+        .map_err(|error| Error::Hex(error.to_string()))
+}
+
+/// Unpack the source files of a package from a Hex release tarball, which
+/// holds them in a compressed `contents.tar.gz` file.
+fn unpack_release(tarball: &[u8], directory: &Path) -> Result<(), Error> {
+    let tar_error = |error: std::io::Error| Error::Tar {
+        path: directory.to_path_buf(),
+        err: error.to_string(),
+    };
+    let mut archive = tar::Archive::new(tarball);
+    for entry in archive.entries().map_err(tar_error)? {
+        let entry = entry.map_err(tar_error)?;
+        if entry.path().map_err(tar_error)?.as_ref() == Path::new("contents.tar.gz") {
+            return tar::Archive::new(flate2::read::GzDecoder::new(entry))
+                .unpack(directory)
+                .map_err(tar_error);
+        }
+    }
+    Err(Error::Tar {
+        path: directory.to_path_buf(),
+        err: "The release does not contain contents.tar.gz".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::process;
+
+    fn tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(vec![]);
+        for (path, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_path(path).expect("path");
+            header.set_size(contents.len() as u64);
+            header.set_cksum();
+            builder.append(&header, *contents).expect("append");
+        }
+        builder.into_inner().expect("tar")
+    }
+
+    #[test]
+    fn unpack_release_contents() {
+        let dir = std::env::temp_dir().join(format!("gleam-unpack-release-{}", process::id()));
+        fs::delete_dir(&dir).expect("delete");
+
+        let mut contents = GzEncoder::new(vec![], Compression::default());
+        std::io::Write::write_all(
+            &mut contents,
+            &tar(&[("src/one.gleam", b"pub fn main() { 1 }\n")]),
+        )
+        .expect("gzip");
+        let contents = contents.finish().expect("gzip");
+        let tarball = tar(&[("VERSION", b"3"), ("contents.tar.gz", &contents)]);
+
+        unpack_release(&tarball, &dir).expect("unpack");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/one.gleam")).expect("read"),
+            "pub fn main() { 1 }\n"
+        );
+        assert!(unpack_release(&tar(&[("VERSION", b"3")]), &dir).is_err());
+        fs::delete_dir(&dir).expect("delete");
+    }
+
+    #[test]
+    fn metadata_without_config_is_an_error() {
+        let dir = std::env::temp_dir().join(format!("gleam-semver-metadata-{}", process::id()));
+        fs::delete_dir(&dir).expect("delete");
+        fs::mkdir(dir.join("src")).expect("mkdir");
+        assert!(read_previous_release(&dir.join("src")).is_err());
+        fs::delete_dir(&dir).expect("delete");
+    }
+}
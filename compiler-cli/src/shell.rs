@@ -8,7 +8,7 @@ pub fn command(root_string: String) -> Result<(), Error> {
     let config = crate::config::root_config(&root)?;
 
     // Build project
-    let _ = super::new_build_main(config, root_path)?;
+    let _ = super::new_build_main(config, root_path, &crate::BuildOptions::default())?;
 
     // Don't exit on ctrl+c as it is used by child erlang shell
     ctrlc::set_handler(move || {}).expect("Error setting Ctrl-C handler");
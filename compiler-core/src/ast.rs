@@ -8,8 +8,10 @@ pub use self::untyped::UntypedExpr;
 pub use self::constant::{Constant, TypedConstant, UntypedConstant};
 
 use crate::build::Target;
+use crate::parse::extra::{Comment, ModuleExtra};
 use crate::type_::{self, ModuleValueConstructor, PatternConstructor, Type, ValueConstructor};
 use std::sync::Arc;
+use vec1::Vec1;
 
 #[cfg(test)]
 use pretty_assertions::assert_eq;
@@ -117,6 +119,73 @@ impl UntypedModule {
     }
 }
 
+impl TypedModule {
+    /// Attach the module comments and the doc comments found when parsing the
+    /// module to the module and to the statements they precede.
+    pub fn attach_doc_and_module_comments(&mut self, extra: &ModuleExtra, src: &str) {
+        // Module Comments
+        self.documentation = extra
+            .module_comments
+            .iter()
+            .map(|span| Comment::from((span, src)).content.to_string())
+            .collect();
+
+        // Doc Comments
+        // Statements are not always stored in source order (constants are
+        // type checked first) so they are sorted to match the comments.
+        let mut doc_comments = extra.doc_comments.iter().peekable();
+        let mut statements: Vec<_> = self.statements.iter_mut().collect();
+        statements.sort_by_key(|statement| statement.location().start);
+        for statement in statements {
+            let docs: Vec<&str> =
+                comments_before(&mut doc_comments, statement.location().start, src);
+            if !docs.is_empty() {
+                let doc = docs.join("\n");
+                statement.put_doc(doc);
+            }
+
+            if let Statement::CustomType { constructors, .. } = statement {
+                for constructor in constructors {
+                    let docs: Vec<&str> =
+                        comments_before(&mut doc_comments, constructor.location.start, src);
+                    if !docs.is_empty() {
+                        let doc = docs.join("\n");
+                        constructor.put_doc(doc);
+                    }
+
+                    for argument in constructor.arguments.iter_mut() {
+                        let docs: Vec<&str> =
+                            comments_before(&mut doc_comments, argument.location.start, src);
+                        if !docs.is_empty() {
+                            let doc = docs.join("\n");
+                            argument.put_doc(doc);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn comments_before<'a>(
+    comment_spans: &mut std::iter::Peekable<impl Iterator<Item = &'a SrcSpan>>,
+    byte: usize,
+    src: &'a str,
+) -> Vec<&'a str> {
+    let mut comments = vec![];
+    while let Some(SrcSpan { start, .. }) = comment_spans.peek() {
+        if start <= &byte {
+            let comment = comment_spans
+                .next()
+                .expect("Comment before accessing next span");
+            comments.push(Comment::from((comment, src)).content)
+        } else {
+            break;
+        }
+    }
+    comments
+}
+
 #[test]
 fn module_dependencies_test() {
     let (module, _) = crate::parse::parse_module(
@@ -282,6 +351,7 @@ pub enum Statement<T, Expr, ConstantRecordTag, PackageName> {
         return_annotation: Option<TypeAst>,
         return_type: T,
         doc: Option<String>,
+        deprecation: Deprecation,
     },
 
     TypeAlias {
@@ -292,6 +362,7 @@ pub enum Statement<T, Expr, ConstantRecordTag, PackageName> {
         type_: T,
         public: bool,
         doc: Option<String>,
+        deprecation: Deprecation,
     },
 
     CustomType {
@@ -303,6 +374,7 @@ pub enum Statement<T, Expr, ConstantRecordTag, PackageName> {
         doc: Option<String>,
         opaque: bool,
         typed_parameters: Vec<T>,
+        deprecation: Deprecation,
     },
 
     ExternalFn {
@@ -312,9 +384,9 @@ pub enum Statement<T, Expr, ConstantRecordTag, PackageName> {
         name: String,
         return_: TypeAst,
         return_type: T,
-        module: String,
-        fun: String,
+        implementations: Vec1<ExternalFnImplementation>,
         doc: Option<String>,
+        deprecation: Deprecation,
     },
 
     ExternalType {
@@ -341,6 +413,7 @@ pub enum Statement<T, Expr, ConstantRecordTag, PackageName> {
         annotation: Option<TypeAst>,
         value: Box<Constant<T, ConstantRecordTag>>,
         type_: T,
+        deprecation: Deprecation,
     },
 }
 
@@ -370,6 +443,52 @@ impl<A, B, C, E> Statement<A, B, C, E> {
             }
         }
     }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        match self {
+            Statement::Import { .. } | Statement::ExternalType { .. } => None,
+            Statement::Fn { deprecation, .. }
+            | Statement::TypeAlias { deprecation, .. }
+            | Statement::CustomType { deprecation, .. }
+            | Statement::ExternalFn { deprecation, .. }
+            | Statement::ModuleConstant { deprecation, .. } => Some(deprecation),
+        }
+    }
+
+    pub fn deprecation_mut(&mut self) -> Option<&mut Deprecation> {
+        match self {
+            Statement::Import { .. } | Statement::ExternalType { .. } => None,
+            Statement::Fn { deprecation, .. }
+            | Statement::TypeAlias { deprecation, .. }
+            | Statement::CustomType { deprecation, .. }
+            | Statement::ExternalFn { deprecation, .. }
+            | Statement::ModuleConstant { deprecation, .. } => Some(deprecation),
+        }
+    }
+}
+
+/// Whether a definition has been marked with the `@deprecated("...")`
+/// attribute, and if so the message to show where it is used.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Deprecation {
+    #[default]
+    NotDeprecated,
+    Deprecated {
+        message: String,
+    },
+}
+
+impl Deprecation {
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self, Deprecation::Deprecated { .. })
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Deprecation::NotDeprecated => None,
+            Deprecation::Deprecated { message } => Some(message),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -379,6 +498,34 @@ pub struct UnqualifiedImport {
     pub as_name: Option<String>,
 }
 
+/// The module and function that implement an external function. An
+/// implementation without a target is used for every target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalFnImplementation {
+    pub target: Option<Target>,
+    pub module: String,
+    pub fun: String,
+}
+
+/// Find the implementation of an external function to use when compiling for
+/// the given target, if it has one.
+pub fn external_fn_implementation(
+    implementations: &[ExternalFnImplementation],
+    target: Target,
+) -> Option<&ExternalFnImplementation> {
+    implementations
+        .iter()
+        .find(|implementation| implementation.target.is_none_or(|t| t == target))
+}
+
+/// The targets that an external function has an implementation for.
+pub fn external_fn_targets(implementations: &[ExternalFnImplementation]) -> Vec<Target> {
+    Target::all()
+        .into_iter()
+        .filter(|target| external_fn_implementation(implementations, *target).is_some())
+        .collect()
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExternalFnArg<T> {
     pub location: SrcSpan,
@@ -417,6 +564,9 @@ pub enum BinOp {
     DivInt,
     DivFloat,
     ModuloInt,
+
+    // Strings
+    Concatenate,
 }
 
 impl BinOp {
@@ -438,7 +588,7 @@ impl BinOp {
             | Self::GtFloat => 4,
 
             // Pipe is 5
-            Self::AddInt | Self::AddFloat | Self::SubInt | Self::SubFloat => 6,
+            Self::AddInt | Self::AddFloat | Self::SubInt | Self::SubFloat | Self::Concatenate => 6,
 
             Self::MultInt | Self::MultFloat | Self::DivInt | Self::DivFloat | Self::ModuloInt => 7,
         }
@@ -467,6 +617,7 @@ impl BinOp {
             Self::DivInt => "/",
             Self::DivFloat => "/.",
             Self::ModuloInt => "%",
+            Self::Concatenate => "<>",
         }
     }
 }
@@ -476,6 +627,9 @@ pub struct CallArg<A> {
     pub label: Option<String>,
     pub location: SrcSpan,
     pub value: A,
+    /// Whether the argument was written as `label:`, a shorthand for
+    /// `label: label`.
+    pub shorthand: bool,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -489,6 +643,9 @@ pub struct UntypedRecordUpdateArg {
     pub label: String,
     pub location: SrcSpan,
     pub value: UntypedExpr,
+    /// Whether the argument was written as `label:`, a shorthand for
+    /// `label: label`.
+    pub shorthand: bool,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -595,6 +752,22 @@ pub enum ClauseGuard<Type, RecordTag> {
         right: Box<Self>,
     },
 
+    Concatenate {
+        location: SrcSpan,
+        left: Box<Self>,
+        right: Box<Self>,
+    },
+
+    NegateBool {
+        location: SrcSpan,
+        value: Box<Self>,
+    },
+
+    NegateInt {
+        location: SrcSpan,
+        value: Box<Self>,
+    },
+
     Or {
         location: SrcSpan,
         left: Box<Self>,
@@ -640,7 +813,10 @@ impl<A, B> ClauseGuard<A, B> {
             | ClauseGuard::GtFloat { location, .. }
             | ClauseGuard::GtEqFloat { location, .. }
             | ClauseGuard::LtFloat { location, .. }
-            | ClauseGuard::LtEqFloat { location, .. } => *location,
+            | ClauseGuard::LtEqFloat { location, .. }
+            | ClauseGuard::Concatenate { location, .. }
+            | ClauseGuard::NegateBool { location, .. }
+            | ClauseGuard::NegateInt { location, .. } => *location,
         }
     }
 }
@@ -651,6 +827,8 @@ impl TypedClauseGuard {
             ClauseGuard::Var { type_, .. } => type_.clone(),
             ClauseGuard::TupleIndex { type_, .. } => type_.clone(),
             ClauseGuard::Constant(constant) => constant.type_(),
+            ClauseGuard::Concatenate { .. } => type_::string(),
+            ClauseGuard::NegateInt { .. } => type_::int(),
 
             ClauseGuard::Or { .. }
             | ClauseGuard::And { .. }
@@ -663,7 +841,8 @@ impl TypedClauseGuard {
             | ClauseGuard::GtFloat { .. }
             | ClauseGuard::GtEqFloat { .. }
             | ClauseGuard::LtFloat { .. }
-            | ClauseGuard::LtEqFloat { .. } => type_::bool(),
+            | ClauseGuard::LtEqFloat { .. }
+            | ClauseGuard::NegateBool { .. } => type_::bool(),
         }
     }
 }
@@ -71,6 +71,8 @@ pub enum TypedExpr {
         value: Box<Self>,
         pattern: Pattern<PatternConstructor, Arc<Type>>,
         kind: AssignmentKind,
+        /// The message to crash with if an `assert` pattern does not match
+        message: Option<String>,
     },
 
     Try {
@@ -136,6 +138,16 @@ pub enum TypedExpr {
         spread: Box<Self>,
         args: Vec<TypedRecordUpdateArg>,
     },
+
+    NegateBool {
+        location: SrcSpan,
+        value: Box<Self>,
+    },
+
+    NegateInt {
+        location: SrcSpan,
+        value: Box<Self>,
+    },
 }
 
 impl TypedExpr {
@@ -171,7 +183,9 @@ impl TypedExpr {
             | Self::TupleIndex { location, .. }
             | Self::ModuleSelect { location, .. }
             | Self::RecordAccess { location, .. }
-            | Self::RecordUpdate { location, .. } => *location,
+            | Self::RecordUpdate { location, .. }
+            | Self::NegateBool { location, .. }
+            | Self::NegateInt { location, .. } => *location,
         }
     }
 
@@ -208,6 +222,8 @@ impl TypedExpr {
             Self::RecordAccess { typ, .. } => typ.clone(),
             Self::BitString { typ, .. } => typ.clone(),
             Self::RecordUpdate { typ, .. } => typ.clone(),
+            Self::NegateBool { .. } => type_::bool(),
+            Self::NegateInt { .. } => type_::int(),
             Self::Sequence { expressions, .. } => expressions
                 .last()
                 .map(TypedExpr::type_)
@@ -226,6 +242,25 @@ impl TypedExpr {
                 | Self::BitString { .. }
         )
     }
+
+    pub fn start_byte_index(&self) -> usize {
+        match self {
+            Self::Sequence {
+                expressions,
+                location,
+            } => expressions
+                .first()
+                .map(|e| e.start_byte_index())
+                .unwrap_or(location.start),
+            _ => self.location().start,
+        }
+    }
+
+    /// Returns `true` if evaluating the expression always crashes, so any
+    /// code following it can never be run.
+    pub fn never_returns(&self) -> bool {
+        matches!(self, Self::Todo { .. })
+    }
 }
 
 impl HasType for TypedExpr {
@@ -66,6 +66,8 @@ pub enum UntypedExpr {
         pattern: Pattern<(), ()>,
         kind: AssignmentKind,
         annotation: Option<TypeAst>,
+        /// The message to crash with if an `assert` pattern does not match
+        message: Option<String>,
     },
 
     Try {
@@ -115,6 +117,16 @@ pub enum UntypedExpr {
         spread: RecordUpdateSpread,
         arguments: Vec<UntypedRecordUpdateArg>,
     },
+
+    NegateBool {
+        location: SrcSpan,
+        value: Box<Self>,
+    },
+
+    NegateInt {
+        location: SrcSpan,
+        value: Box<Self>,
+    },
 }
 
 impl UntypedExpr {
@@ -137,7 +149,9 @@ impl UntypedExpr {
             | Self::Assignment { location, .. }
             | Self::TupleIndex { location, .. }
             | Self::FieldAccess { location, .. }
-            | Self::RecordUpdate { location, .. } => *location,
+            | Self::RecordUpdate { location, .. }
+            | Self::NegateBool { location, .. }
+            | Self::NegateInt { location, .. } => *location,
             Self::Sequence {
                 location,
                 expressions,
@@ -3,7 +3,6 @@
 // TODO: Avoid rebuilding clean modules
 // TODO: Download deps from Hex
 // TODO: Support compilation of rebar3 packages
-// TODO: Track removed files in src and test so they can be removed from _build
 // TODO: Test profile and default profile
 // TODO: Only compile test code in test profile
 // TODO: Full .app generation
@@ -14,7 +13,9 @@
 // - no Erlang generation
 
 mod dep_tree;
+pub mod manifest;
 pub mod package_compiler;
+mod parallel;
 mod project_compiler;
 pub mod project_root;
 mod telemetry;
@@ -24,7 +25,7 @@ mod package_compilation_tests;
 
 pub use self::package_compiler::PackageCompiler;
 pub use self::project_compiler::ProjectCompiler;
-pub use self::telemetry::Telemetry;
+pub use self::telemetry::{NullTelemetry, Phase, Telemetry, Timing, TimingsRecorder};
 
 use crate::{
     ast::TypedModule,
@@ -44,21 +45,35 @@ use strum::{Display, EnumString, EnumVariantNames, VariantNames};
     Debug, Serialize, Deserialize, Display, EnumString, EnumVariantNames, Clone, Copy, PartialEq,
 )]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum Target {
     Erlang,
     JavaScript,
 }
 
+impl Default for Target {
+    fn default() -> Self {
+        Self::Erlang
+    }
+}
+
 impl Target {
     pub fn variant_strings() -> Vec<String> {
         Self::VARIANTS.iter().map(|s| s.to_string()).collect()
     }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::Erlang, Self::JavaScript]
+    }
 }
 
 #[derive(Debug)]
 pub struct Package {
     pub name: String,
     pub modules: Vec<Module>,
+    /// Files left over from modules that no longer exist, removed from the
+    /// output directory when the package was compiled.
+    pub removed_files: Vec<PathBuf>,
 }
 
 #[derive(Debug)]
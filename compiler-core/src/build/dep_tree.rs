@@ -49,6 +49,47 @@ pub fn toposort_deps(inputs: Vec<(String, Vec<String>)>) -> Result<Vec<String>,
     }
 }
 
+/// Take a sequence of values and their deps, and return the values grouped
+/// into stages so that deps come in an earlier stage than their dependants.
+/// Values within a stage do not depend on each other so they can be processed
+/// concurrently.
+///
+/// Each stage is sorted so the result does not depend on the order of the
+/// inputs.
+///
+/// Errors if there are cycles.
+///
+pub fn toposort_deps_in_stages(
+    inputs: Vec<(String, Vec<String>)>,
+) -> Result<Vec<Vec<String>>, Error> {
+    let deps: HashMap<String, Vec<String>> = inputs.clone().into_iter().collect();
+    let mut stage_of: HashMap<String, usize> = HashMap::with_capacity(inputs.len());
+    let mut stages: Vec<Vec<String>> = vec![];
+
+    for value in toposort_deps(inputs)? {
+        let stage = deps
+            .get(&value)
+            .into_iter()
+            .flatten()
+            .filter_map(|dep| stage_of.get(dep))
+            .map(|stage| stage + 1)
+            .max()
+            .unwrap_or(0);
+        if stages.len() <= stage {
+            stages.push(vec![]);
+        }
+        if let Some(values) = stages.get_mut(stage) {
+            values.push(value.clone());
+        }
+        let _ = stage_of.insert(value, stage);
+    }
+
+    for stage in &mut stages {
+        stage.sort();
+    }
+    Ok(stages)
+}
+
 // TODO: test
 fn import_cycle(
     cycle: Cycle<NodeIndex>,
@@ -126,6 +167,56 @@ fn toposort_deps_test() {
     );
 }
 
+#[test]
+fn toposort_deps_in_stages_test() {
+    let stages = |inputs: Vec<(&str, Vec<&str>)>| {
+        toposort_deps_in_stages(
+            inputs
+                .into_iter()
+                .map(|(value, deps)| {
+                    (
+                        value.to_string(),
+                        deps.into_iter().map(String::from).collect(),
+                    )
+                })
+                .collect(),
+        )
+    };
+    let strings = |values: Vec<Vec<&str>>| -> Vec<Vec<String>> {
+        values
+            .into_iter()
+            .map(|stage| stage.into_iter().map(String::from).collect())
+            .collect()
+    };
+
+    // Independent values share a stage, sorted regardless of input order
+    assert_eq!(
+        stages(vec![("b", vec![]), ("c", vec![]), ("a", vec![])]),
+        Ok(strings(vec![vec!["a", "b", "c"]]))
+    );
+
+    // Values come in the stage after their latest dep
+    assert_eq!(
+        stages(vec![
+            ("a", vec!["b", "d"]),
+            ("b", vec!["c"]),
+            ("c", vec![]),
+            ("d", vec![]),
+            ("e", vec!["c"]),
+        ]),
+        Ok(strings(vec![vec!["c", "d"], vec!["b", "e"], vec!["a"]]))
+    );
+
+    // Some deps are not nodes (and thus are ignored)
+    assert_eq!(
+        stages(vec![("a", vec!["b", "z"]), ("b", vec!["x"])]),
+        Ok(strings(vec![vec!["b"], vec!["a"]]))
+    );
+
+    // Cycles are errors
+    assert!(stages(vec![("a", vec!["b"]), ("b", vec!["a"])]).is_err());
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     Cycle(Vec<String>),
@@ -0,0 +1,106 @@
+use crate::{
+    io::{FileSystemReader, FileSystemWriter, WrappedWriter},
+    Result,
+};
+use std::{
+    collections::BTreeSet,
+    path::{Component, Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+/// The name of the file in a package's output directory that lists the files
+/// written by the previous build.
+pub const FILE_NAME: &str = "gleam.manifest";
+
+/// The files written to an output directory when compiling a package, relative
+/// to that directory.
+///
+/// The manifest of the previous build is compared to the current one so that
+/// files written for modules that have since been deleted or renamed can be
+/// removed, rather than continuing to be loadable at runtime.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Manifest {
+    files: BTreeSet<PathBuf>,
+}
+
+impl Manifest {
+    /// Read the manifest from an output directory, returning an empty
+    /// manifest if there is none.
+    pub fn read<IO: FileSystemReader>(io: &IO, out_path: &Path) -> Result<Self> {
+        let path = out_path.join(FILE_NAME);
+        if !io.is_file(&path) {
+            return Ok(Self::default());
+        }
+        let files = io
+            .read(&path)?
+            .lines()
+            .map(PathBuf::from)
+            // Only paths within the output directory are ever removed
+            .filter(|path| path.components().all(|c| matches!(c, Component::Normal(_))))
+            .collect();
+        Ok(Self { files })
+    }
+
+    pub fn write<IO: FileSystemWriter>(&self, io: &IO, out_path: &Path) -> Result<()> {
+        let mut text = String::new();
+        for path in &self.files {
+            text.push_str(&path.to_string_lossy());
+            text.push('\n');
+        }
+        io.open(&out_path.join(FILE_NAME))?.write(text.as_bytes())
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.iter()
+    }
+
+    /// The files in this manifest that are not in the newer one.
+    pub fn orphans<'a>(&'a self, newer: &'a Self) -> impl Iterator<Item = &'a PathBuf> {
+        self.files.difference(&newer.files)
+    }
+}
+
+/// A `FileSystemWriter` that records the paths of the files written within an
+/// output directory so that they can be saved to a `Manifest`.
+#[derive(Debug)]
+pub struct Recorder<'a, IO> {
+    io: &'a IO,
+    out_path: &'a Path,
+    files: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl<'a, IO> Recorder<'a, IO> {
+    pub fn new(io: &'a IO, out_path: &'a Path) -> Self {
+        Self {
+            io,
+            out_path,
+            files: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    pub fn into_manifest(self) -> Manifest {
+        Manifest {
+            files: self
+                .files
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
+
+impl<'a, IO: FileSystemWriter> FileSystemWriter for &Recorder<'a, IO> {
+    fn open(&self, path: &Path) -> Result<WrappedWriter> {
+        if let Ok(relative) = path.strip_prefix(self.out_path) {
+            let _ = self
+                .files
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(relative.to_path_buf());
+        }
+        self.io.open(path)
+    }
+
+    fn delete(&self, path: &Path) -> Result<()> {
+        self.io.delete(path)
+    }
+}
@@ -7,12 +7,15 @@ use crate::{
         Origin, Target,
     },
     codegen,
-    config::{BuildTool, Docs, PackageConfig, Repository},
+    config::{BuildTool, Dependency, Docs, PackageConfig, Repository},
     erl,
-    io::test::FilesChannel,
+    io::{memory::InMemoryFileSystem, test::FilesChannel, FileSystemReader, FileSystemWriter},
     type_,
 };
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use pretty_assertions::assert_eq;
 
@@ -22,7 +25,7 @@ macro_rules! assert_erlang_compile {
         let options = Options {
             target: Target::Erlang,
             name: "the_package".to_string(),
-            src_path: PathBuf::from("_build/default/lib/the_package/src"),
+            src_paths: vec![PathBuf::from("_build/default/lib/the_package/src")],
             out_path: PathBuf::from("_build/default/lib/the_package/src"),
             test_path: None,
         };
@@ -47,7 +50,7 @@ macro_rules! assert_no_warnings {
         let options = Options {
             target: Target::Erlang,
             name: "the_package".to_string(),
-            src_path: PathBuf::from("_build/default/lib/the_package/src"),
+            src_paths: vec![PathBuf::from("_build/default/lib/the_package/src")],
             out_path: PathBuf::from("_build/default/lib/the_package/src"),
             test_path: None,
         };
@@ -1676,6 +1679,23 @@ fn x() { test }"
     );
 }
 
+#[test]
+fn imported_constant_used_by_modules_checked_together() {
+    // `two` and `three` are type checked at the same time, each with its own
+    // instance of the type variable in the type of `one.empty`
+    let source = |name: &str, code: &str| Source {
+        origin: Origin::Src,
+        path: PathBuf::from(format!("/src/{}.gleam", name)),
+        name: name.to_string(),
+        code: code.to_string(),
+    };
+    assert_no_warnings!(vec![
+        source("one", "pub const empty = []"),
+        source("two", "import one pub fn go() { [1, ..one.empty] }"),
+        source("three", "import one pub fn go() { [\"a\", ..one.empty] }"),
+    ]);
+}
+
 #[test]
 fn imported_type_constructor_used_as_function() {
     assert_erlang_compile!(
@@ -1781,7 +1801,7 @@ fn config_compilation_test() {
             let options = package_compiler::Options {
                 target: Target::Erlang,
                 name: config.name.clone(),
-                src_path: PathBuf::from("src"),
+                src_paths: vec![PathBuf::from("src")],
                 out_path: PathBuf::from("out"),
                 test_path: None,
             };
@@ -1802,6 +1822,7 @@ fn config_compilation_test() {
     fn make_config() -> PackageConfig {
         PackageConfig {
             dependencies: HashMap::new(),
+            dev_dependencies: HashMap::new(),
             description: "".to_string(),
             version: "1.0.0".to_string(),
             name: "the_package".to_string(),
@@ -1809,6 +1830,8 @@ fn config_compilation_test() {
             docs: Default::default(),
             otp_start_module: None,
             tool: BuildTool::Gleam,
+            target: Target::Erlang,
+            warnings: HashMap::new(),
         }
     }
 
@@ -1878,7 +1901,7 @@ fn config_compilation_test() {
         ("simple_json", "1.0.0"),
     ]
     .into_iter()
-    .map(|(a, b)| (a.to_string(), b.to_string()))
+    .map(|(a, b)| (a.to_string(), Dependency::Hex(b.to_string())))
     .collect();
     assert_config_compile!(
         config,
@@ -1957,3 +1980,214 @@ const x = two.A"#
         ]),
     );
 }
+
+fn in_memory_package(files: Vec<(&str, &str)>) -> InMemoryFileSystem {
+    let io = InMemoryFileSystem::new();
+    for (path, code) in files {
+        io.open(&PathBuf::from(path))
+            .unwrap()
+            .write(code.as_bytes())
+            .unwrap();
+    }
+    io
+}
+
+fn multiple_src_options() -> Options {
+    Options {
+        target: Target::Erlang,
+        name: "the_package".to_string(),
+        src_paths: vec![PathBuf::from("/one"), PathBuf::from("/two")],
+        out_path: PathBuf::from("/out"),
+        test_path: None,
+    }
+}
+
+#[test]
+fn multiple_src_paths() {
+    let io = in_memory_package(vec![
+        ("/one/a.gleam", "pub fn a() { 1 }"),
+        ("/two/b/c.gleam", "import a\npub fn c() { a.a() }"),
+    ]);
+    let package = multiple_src_options()
+        .into_compiler(io)
+        .unwrap()
+        .compile(&mut vec![], &mut HashMap::new(), &mut HashMap::new())
+        .unwrap();
+    let mut names: Vec<_> = package.modules.into_iter().map(|m| m.name).collect();
+    names.sort();
+    assert_eq!(names, vec!["a".to_string(), "b/c".to_string()]);
+}
+
+#[test]
+fn duplicate_module_in_multiple_src_paths() {
+    let io = in_memory_package(vec![
+        ("/one/a.gleam", "pub fn a() { 1 }"),
+        ("/two/a.gleam", "pub fn a() { 2 }"),
+    ]);
+    let error = multiple_src_options()
+        .into_compiler(io)
+        .unwrap()
+        .compile(&mut vec![], &mut HashMap::new(), &mut HashMap::new())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        Error::DuplicateModule {
+            module: "a".to_string(),
+            first: PathBuf::from("/one/a.gleam"),
+            second: PathBuf::from("/two/a.gleam"),
+        }
+    );
+}
+
+#[derive(Debug, Default)]
+struct RecordingTelemetry(crate::build::TimingsRecorder);
+
+impl crate::build::Telemetry for RecordingTelemetry {
+    fn compiling_package(&self, _name: &str) {}
+
+    fn timed(&self, timing: &crate::build::Timing) {
+        self.0.record(timing)
+    }
+}
+
+#[test]
+fn module_timings_are_reported() {
+    let io = in_memory_package(vec![
+        ("/one/a.gleam", "pub fn a() { 1 }"),
+        ("/two/b.gleam", "import a\npub fn b() { a.a() }"),
+    ]);
+    let telemetry = Arc::new(RecordingTelemetry::default());
+    let _ = multiple_src_options()
+        .into_compiler(io)
+        .unwrap()
+        .telemetry(telemetry.clone())
+        .compile(&mut vec![], &mut HashMap::new(), &mut HashMap::new())
+        .unwrap();
+
+    let timings: Vec<serde_json::Value> = serde_json::from_str(&telemetry.0.to_json()).unwrap();
+    let mut events: Vec<_> = timings
+        .iter()
+        .map(|t| (t["module"].to_string(), t["phase"].to_string()))
+        .collect();
+    events.sort();
+    let expected: Vec<_> = vec![
+        ("a", "codegen"),
+        ("a", "parse"),
+        ("a", "type_check"),
+        ("b", "codegen"),
+        ("b", "parse"),
+        ("b", "type_check"),
+    ]
+    .into_iter()
+    .map(|(m, p)| (format!("\"{}\"", m), format!("\"{}\"", p)))
+    .collect();
+    assert_eq!(events, expected);
+
+    let trace: Vec<serde_json::Value> =
+        serde_json::from_str(&telemetry.0.to_chrome_trace()).unwrap();
+    assert_eq!(trace.len(), 6);
+}
+
+#[test]
+fn concurrently_checked_modules_are_reported_in_a_deterministic_order() {
+    let io = in_memory_package(vec![
+        ("/one/d.gleam", "pub fn d() { todo }"),
+        ("/one/b.gleam", "pub fn b() { todo }"),
+        ("/one/a.gleam", "import b\nimport d\npub fn a() { todo }"),
+        ("/two/c.gleam", "pub fn c() { todo }"),
+    ]);
+    let mut warnings = vec![];
+    let package = multiple_src_options()
+        .into_compiler(io)
+        .unwrap()
+        .compile(&mut warnings, &mut HashMap::new(), &mut HashMap::new())
+        .unwrap();
+
+    let names: Vec<_> = package.modules.into_iter().map(|m| m.name).collect();
+    assert_eq!(names, vec!["b", "c", "d", "a"]);
+
+    let paths: Vec<_> = warnings
+        .iter()
+        .map(|warning| warning.to_diagnostic().0.file)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/one/b.gleam",
+            "/two/c.gleam",
+            "/one/d.gleam",
+            "/one/a.gleam"
+        ]
+    );
+}
+
+#[test]
+fn outputs_of_deleted_modules_are_removed() {
+    let io = in_memory_package(vec![
+        ("/one/a.gleam", "pub type A { A(x: Int) }"),
+        ("/two/b.gleam", "pub fn b() { 1 }"),
+    ]);
+    let compile = |io: &InMemoryFileSystem| {
+        multiple_src_options()
+            .into_compiler(io.clone())
+            .unwrap()
+            .write_metadata(true)
+            .compile(&mut vec![], &mut HashMap::new(), &mut HashMap::new())
+            .unwrap()
+    };
+
+    let package = compile(&io);
+    assert_eq!(package.removed_files, Vec::<PathBuf>::new());
+    assert_eq!(
+        io.read("/out/gleam.manifest").unwrap(),
+        "a.erl\na.gleam_module\na_A.hrl\nb.erl\nb.gleam_module\n"
+    );
+
+    // The module is deleted and another is added
+    io.delete(Path::new("/two/b.gleam")).unwrap();
+    io.open(Path::new("/two/c.gleam"))
+        .unwrap()
+        .write(b"pub fn c() { 1 }")
+        .unwrap();
+    let package = compile(&io);
+    assert_eq!(
+        package.removed_files,
+        vec![
+            PathBuf::from("/out/b.erl"),
+            PathBuf::from("/out/b.gleam_module")
+        ]
+    );
+    assert!(!io.is_file(Path::new("/out/b.erl")));
+    assert!(!io.is_file(Path::new("/out/b.gleam_module")));
+    assert!(io.is_file(Path::new("/out/a.erl")));
+    assert!(io.is_file(Path::new("/out/c.erl")));
+    assert_eq!(
+        io.read("/out/gleam.manifest").unwrap(),
+        "a.erl\na.gleam_module\na_A.hrl\nc.erl\nc.gleam_module\n"
+    );
+}
+
+#[test]
+fn type_checking_without_codegen_writes_no_files() {
+    let io = in_memory_package(vec![
+        ("/one/a.gleam", "pub type A { A(x: Int) }"),
+        ("/two/b.gleam", "import a\npub fn b() { a.A(1) }"),
+    ]);
+    let mut warnings = vec![];
+    let package = multiple_src_options()
+        .into_compiler(io.clone())
+        .unwrap()
+        .write_metadata(true)
+        .codegen(false)
+        .compile(&mut warnings, &mut HashMap::new(), &mut HashMap::new())
+        .unwrap();
+
+    let names: Vec<_> = package.modules.into_iter().map(|m| m.name).collect();
+    assert_eq!(names, vec!["a", "b"]);
+    let outputs: Vec<_> = io
+        .paths()
+        .into_iter()
+        .filter(|path| path.starts_with("/out"))
+        .collect();
+    assert_eq!(outputs, Vec::<PathBuf>::new());
+}
@@ -1,21 +1,29 @@
 use crate::{
     ast::{SrcSpan, TypedModule, UntypedModule},
-    build::{dep_tree, project_root::ProjectRoot, Module, Origin, Package, Target},
+    build::{
+        dep_tree,
+        manifest::{Manifest, Recorder},
+        parallel,
+        project_root::ProjectRoot,
+        telemetry::{NullTelemetry, Phase, Telemetry, Timing},
+        Module, Origin, Package, Target,
+    },
     codegen::{Erlang, JavaScript},
     config::PackageConfig,
     error,
     io::{FileSystemIO, FileSystemReader, FileSystemWriter},
     metadata::ModuleEncoder,
+    parse::extra::ModuleExtra,
     type_, Error, Result, Warning,
 };
 use std::path::{Path, PathBuf};
-use std::{collections::HashMap, fmt::write};
+use std::{collections::HashMap, fmt::write, sync::Arc};
 
 #[derive(Debug)]
 pub struct Options {
     pub target: Target,
     pub name: String,
-    pub src_path: PathBuf,
+    pub src_paths: Vec<PathBuf>,
     pub test_path: Option<PathBuf>,
     pub out_path: PathBuf,
 }
@@ -23,12 +31,14 @@ pub struct Options {
 impl Options {
     pub fn into_compiler<IO>(self, io: IO) -> Result<PackageCompiler<IO>>
     where
-        IO: FileSystemIO + Clone,
+        IO: FileSystemIO + Clone + Send + Sync,
     {
         let mut compiler = PackageCompiler {
             options: self,
             sources: vec![],
             write_metadata: false,
+            codegen: true,
+            telemetry: Arc::new(NullTelemetry),
             io,
         };
         compiler.read_source_files()?;
@@ -42,6 +52,8 @@ pub struct PackageCompiler<IO> {
     pub sources: Vec<Source>,
     pub io: IO,
     pub write_metadata: bool,
+    pub codegen: bool,
+    pub telemetry: Arc<dyn Telemetry>,
 }
 
 // TODO: ensure this is not a duplicate module
@@ -50,7 +62,7 @@ pub struct PackageCompiler<IO> {
 // - modules that don't import anything
 impl<IO> PackageCompiler<IO>
 where
-    IO: FileSystemIO + Clone,
+    IO: FileSystemIO + Clone + Send + Sync,
 {
     pub fn new(options: Options, io: IO) -> Self {
         Self {
@@ -58,6 +70,8 @@ where
             options,
             sources: vec![],
             write_metadata: false,
+            codegen: true,
+            telemetry: Arc::new(NullTelemetry),
         }
     }
 
@@ -75,10 +89,13 @@ where
             &self.options.name,
             std::mem::take(&mut self.sources),
             already_defined_modules,
+            self.telemetry.as_ref(),
         )?;
 
-        // Determine order in which modules are to be processed
-        let sequence = dep_tree::toposort_deps(
+        // Determine order in which modules are to be processed. Modules in the
+        // same stage do not import each other so they can be processed
+        // concurrently.
+        let stages = dep_tree::toposort_deps_in_stages(
             parsed_modules
                 .values()
                 .map(|m| module_deps_for_graph(self.options.target, m))
@@ -90,25 +107,46 @@ where
         let modules = type_check(
             &self.options.name,
             self.options.target,
-            sequence,
+            stages,
             parsed_modules,
             existing_modules,
             warnings,
+            self.telemetry.as_ref(),
         )?;
 
+        if !self.codegen {
+            return Ok(Package {
+                name: self.options.name,
+                modules,
+                removed_files: vec![],
+            });
+        }
+
+        // Record the files written so any left over from modules that have
+        // since been deleted or renamed can be removed.
+        let recorder = Recorder::new(&self.io, &self.options.out_path);
+
         tracing::info!("Performing code generation");
-        self.perform_codegen(&modules)?;
+        self.perform_codegen(&recorder, &modules)?;
 
         tracing::info!("Writing package metadata to disc");
-        self.encode_and_write_metadata(&modules)?;
+        self.encode_and_write_metadata(&recorder, &modules)?;
+
+        tracing::info!("Removing outputs of deleted modules");
+        let removed_files = self.remove_orphans(recorder.into_manifest())?;
 
         Ok(Package {
             name: self.options.name,
             modules,
+            removed_files,
         })
     }
 
-    fn encode_and_write_metadata(&mut self, modules: &[Module]) -> Result<()> {
+    fn encode_and_write_metadata(
+        &self,
+        writer: &Recorder<'_, IO>,
+        modules: &[Module],
+    ) -> Result<()> {
         if !self.write_metadata {
             return Ok(());
         }
@@ -116,26 +154,44 @@ where
             let name = format!("{}.gleam_module", &module.name.replace('/', "@"));
             tracing::trace!(name = %name, "Writing module metadata");
             let path = self.options.out_path.join(name);
-            ModuleEncoder::new(&module.ast.type_info).write(self.io.open(&path)?)?;
+            ModuleEncoder::new(&module.ast.type_info).write(writer.open(&path)?)?;
         }
         Ok(())
     }
 
+    /// Delete the files written by the previous build that were not written
+    /// by this one, and save the manifest for the next build.
+    fn remove_orphans(&self, manifest: Manifest) -> Result<Vec<PathBuf>> {
+        let out_path = &self.options.out_path;
+        let previous = Manifest::read(&self.io, out_path)?;
+        let mut removed = vec![];
+        for orphan in previous.orphans(&manifest) {
+            let path = out_path.join(orphan);
+            tracing::trace!(path = ?path, "Removing orphaned output");
+            self.io.delete(&path)?;
+            removed.push(path);
+        }
+        manifest.write(&self.io, out_path)?;
+        Ok(removed)
+    }
+
     pub fn read_source_files(&mut self) -> Result<()> {
         let span = tracing::info_span!("load", package = self.options.name.as_str());
         let _enter = span.enter();
         tracing::info!("Reading source files");
 
         // Src
-        for path in self.io.gleam_files(&self.options.src_path) {
-            let name = module_name(&self.options.src_path, &path);
-            let code = self.io.read(&path)?;
-            self.sources.push(Source {
-                name,
-                path,
-                code,
-                origin: Origin::Src,
-            });
+        for src_path in &self.options.src_paths {
+            for path in self.io.gleam_files(src_path) {
+                let name = module_name(src_path, &path);
+                let code = self.io.read(&path)?;
+                self.sources.push(Source {
+                    name,
+                    path,
+                    code,
+                    origin: Origin::Src,
+                });
+            }
         }
 
         // Test
@@ -154,11 +210,23 @@ where
         Ok(())
     }
 
-    fn perform_codegen(&self, modules: &[Module]) -> Result<()> {
-        match self.options.target {
-            Target::JavaScript => JavaScript::new(&self.options.out_path).render(&self.io, modules),
-            Target::Erlang => Erlang::new(&self.options.out_path).render(self.io.clone(), modules),
-        }
+    fn perform_codegen(&self, writer: &Recorder<'_, IO>, modules: &[Module]) -> Result<()> {
+        let out_path = &self.options.out_path;
+        let results = parallel::map(modules.iter().collect(), |module| {
+            let name = &module.name;
+            let module = std::slice::from_ref(module);
+            Timing::time(
+                self.telemetry.as_ref(),
+                &self.options.name,
+                name,
+                Phase::Codegen,
+                || match self.options.target {
+                    Target::JavaScript => JavaScript::new(out_path).render(&writer, module),
+                    Target::Erlang => Erlang::new(out_path).render(writer, module),
+                },
+            )
+        });
+        results.into_iter().collect()
     }
 
     /// Set whether to write metadata files
@@ -166,18 +234,32 @@ where
         self.write_metadata = write_metadata;
         self
     }
+
+    /// Set whether to generate code and write metadata, rather than only
+    /// type checking the package
+    pub fn codegen(mut self, codegen: bool) -> Self {
+        self.codegen = codegen;
+        self
+    }
+
+    /// Set the telemetry to report module timings to
+    pub fn telemetry(mut self, telemetry: Arc<dyn Telemetry>) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
 }
 
 fn type_check(
     package_name: &str,
     target: Target,
-    sequence: Vec<String>,
+    stages: Vec<Vec<String>>,
     mut parsed_modules: HashMap<String, Parsed>,
     module_types: &mut HashMap<String, type_::Module>,
     warnings: &mut Vec<Warning>,
+    telemetry: &dyn Telemetry,
 ) -> Result<Vec<Module>, Error> {
     let mut modules = Vec::with_capacity(parsed_modules.len() + 1);
-    let mut uid = 0;
+    let mut uid = type_::UidGenerator::default();
 
     // Insert the prelude
     // DUPE: preludeinsertion
@@ -186,21 +268,86 @@ fn type_check(
     // place.
     let _ = module_types.insert("gleam".to_string(), type_::build_prelude(&mut uid));
 
-    for name in sequence {
-        let Parsed {
-            name,
-            code,
-            ast,
-            path,
-            origin,
-            package,
-        } = parsed_modules
-            .remove(&name)
-            .expect("Getting parsed module for name");
-
-        tracing::trace!(module = ?name, "Type checking");
-        let mut type_warnings = Vec::new();
-        let ast = type_::infer_module(
+    for stage in stages {
+        let stage: Vec<_> = stage
+            .iter()
+            .map(|name| {
+                parsed_modules
+                    .remove(name)
+                    .expect("Getting parsed module for name")
+            })
+            .collect();
+
+        // The modules in the stage are given interleaved type variable ids so
+        // that they are distinct, while keeping each module's ids the same no
+        // matter how the modules are scheduled.
+        let uids = uid.interleave(stage.len());
+        let importable_modules = &*module_types;
+        let checked = parallel::map(stage.into_iter().zip(uids).collect(), |(parsed, uid)| {
+            type_check_module(
+                package_name,
+                target,
+                uid,
+                parsed,
+                importable_modules,
+                telemetry,
+            )
+        });
+
+        // Results are registered in the order of the stage so that warnings
+        // and errors are reported deterministically.
+        let mut module_uids = Vec::with_capacity(checked.len());
+        for result in checked {
+            let CheckedModule {
+                module,
+                warnings: module_warnings,
+                uid: module_uid,
+            } = result?;
+            module_uids.push(module_uid);
+            warnings.extend(module_warnings);
+
+            // Register the types from this module so they can be imported into
+            // other modules.
+            let _ = module_types.insert(module.name.clone(), module.ast.type_info.clone());
+
+            // Register the successfully type checked module data so that it can
+            // be used for code generation
+            modules.push(module);
+        }
+        uid.resume(module_uids);
+    }
+
+    Ok(modules)
+}
+
+struct CheckedModule {
+    module: Module,
+    warnings: Vec<Warning>,
+    uid: type_::UidGenerator,
+}
+
+fn type_check_module(
+    package_name: &str,
+    target: Target,
+    mut uid: type_::UidGenerator,
+    parsed: Parsed,
+    module_types: &HashMap<String, type_::Module>,
+    telemetry: &dyn Telemetry,
+) -> Result<CheckedModule, Error> {
+    let Parsed {
+        name,
+        code,
+        ast,
+        path,
+        origin,
+        package,
+        extra,
+    } = parsed;
+
+    tracing::trace!(module = ?name, "Type checking");
+    let mut type_warnings = Vec::new();
+    let mut ast = Timing::time(telemetry, package_name, &name, Phase::TypeCheck, || {
+        type_::infer_module(
             target,
             &mut uid,
             ast,
@@ -209,34 +356,33 @@ fn type_check(
             module_types,
             &mut type_warnings,
         )
-        .map_err(|error| Error::Type {
-            path: path.clone(),
-            src: code.clone(),
-            error,
-        })?;
-
-        // Register any warnings emitted as type warnings
-        let type_warnings = type_warnings
-            .into_iter()
-            .map(|w| w.into_warning(path.clone(), code.clone()));
-        warnings.extend(type_warnings);
-
-        // Register the types from this module so they can be imported into
-        // other modules.
-        let _ = module_types.insert(name.clone(), ast.type_info.clone());
-
-        // Register the successfully type checked module data so that it can be
-        // used for code generation
-        modules.push(Module {
+    })
+    .map_err(|error| Error::Type {
+        path: path.clone(),
+        src: code.clone(),
+        error,
+    })?;
+
+    // Attach documentation comments so they can be used by docs tooling
+    ast.attach_doc_and_module_comments(&extra, &code);
+
+    // Register any warnings emitted as type warnings
+    let warnings = type_warnings
+        .into_iter()
+        .map(|w| w.into_warning(path.clone(), code.clone()))
+        .collect();
+
+    Ok(CheckedModule {
+        module: Module {
             origin,
             name,
             code,
             ast,
             path,
-        });
-    }
-
-    Ok(modules)
+        },
+        warnings,
+        uid,
+    })
 }
 
 fn convert_deps_tree_error(e: dep_tree::Error) -> Error {
@@ -260,32 +406,14 @@ fn parse_sources(
     package_name: &str,
     sources: Vec<Source>,
     already_defined_modules: &mut HashMap<String, PathBuf>,
+    telemetry: &dyn Telemetry,
 ) -> Result<HashMap<String, Parsed>, Error> {
     let mut parsed_modules = HashMap::with_capacity(sources.len());
-    for Source {
-        name,
-        code,
-        path,
-        origin,
-    } in sources
-    {
-        let (mut ast, _) = crate::parse::parse_module(&code).map_err(|error| Error::Parse {
-            path: path.clone(),
-            src: code.clone(),
-            error,
-        })?;
-
-        // Store the name
-        ast.name = name.split("/").map(String::from).collect(); // TODO: store the module name as a string
-
-        let module = Parsed {
-            package: package_name.to_string(),
-            origin,
-            path,
-            name,
-            code,
-            ast,
-        };
+    let parsed = parallel::map(sources, |source| {
+        parse_source(package_name, source, telemetry)
+    });
+    for module in parsed {
+        let module = module?;
 
         // Ensure there are no modules defined that already have this name
         if let Some(first) =
@@ -304,6 +432,36 @@ fn parse_sources(
     Ok(parsed_modules)
 }
 
+fn parse_source(package_name: &str, source: Source, telemetry: &dyn Telemetry) -> Result<Parsed> {
+    let Source {
+        name,
+        code,
+        path,
+        origin,
+    } = source;
+    let (mut ast, extra) = Timing::time(telemetry, package_name, &name, Phase::Parse, || {
+        crate::parse::parse_module(&code)
+    })
+    .map_err(|error| Error::Parse {
+        path: path.clone(),
+        src: code.clone(),
+        error,
+    })?;
+
+    // Store the name
+    ast.name = name.split("/").map(String::from).collect(); // TODO: store the module name as a string
+
+    Ok(Parsed {
+        package: package_name.to_string(),
+        origin,
+        path,
+        name,
+        code,
+        ast,
+        extra,
+    })
+}
+
 fn module_name(package_path: &Path, full_module_path: &Path) -> String {
     // /path/to/project/_build/default/lib/the_package/src/my/module.gleam
 
@@ -342,4 +500,5 @@ struct Parsed {
     origin: Origin,
     package: String,
     ast: UntypedModule,
+    extra: ModuleExtra,
 }
@@ -0,0 +1,114 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+    thread,
+};
+
+/// The number of threads started by `map` that are still running. `map` is
+/// called again from within those threads, such as to compile the modules of
+/// packages that are being compiled concurrently, so the threads are counted
+/// across every call to keep to one thread per available core overall.
+static RUNNING_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Apply a function to each item using up to one thread per available core,
+/// returning the results in the same order as the items so that the output
+/// does not depend on how the work was scheduled.
+///
+/// The current thread also takes work, so if threads cannot be spawned, such
+/// as when running in WebAssembly, or every core is already in use by another
+/// call, all the items are processed on the current thread.
+pub fn map<T, R, F>(items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let count = items.len();
+    if count <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(count));
+    let work = || loop {
+        let next = queue.lock().unwrap_or_else(PoisonError::into_inner).next();
+        let (index, item) = match next {
+            Some(next) => next,
+            None => return,
+        };
+        let result = f(item);
+        results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((index, result));
+    };
+
+    let cores = thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1);
+    thread::scope(|scope| {
+        for _ in 1..cores.min(count) {
+            // The thread that first called `map` is not counted so one core
+            // is left for it.
+            let reserved =
+                RUNNING_THREADS.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                    (running + 1 < cores).then(|| running + 1)
+                });
+            if reserved.is_err() {
+                break;
+            }
+            let spawned = thread::Builder::new().spawn_scoped(scope, || {
+                work();
+                let _ = RUNNING_THREADS.fetch_sub(1, Ordering::SeqCst);
+            });
+            if spawned.is_err() {
+                let _ = RUNNING_THREADS.fetch_sub(1, Ordering::SeqCst);
+                break;
+            }
+        }
+        work();
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(PoisonError::into_inner);
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[test]
+fn map_preserves_order() {
+    let items: Vec<usize> = (0..100).collect();
+    let results = map(items, |i| {
+        if i % 7 == 0 {
+            thread::sleep(std::time::Duration::from_millis(1));
+        }
+        i * 2
+    });
+    assert_eq!(results, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn map_empty() {
+    assert_eq!(map(Vec::<usize>::new(), |i| i), Vec::<usize>::new());
+}
+
+#[test]
+fn nested_map_shares_threads() {
+    let cores = thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1);
+    let active = AtomicUsize::new(0);
+    let most_active = AtomicUsize::new(0);
+    let results = map((0..8).collect(), |i: usize| {
+        map((0..8).collect(), |j: usize| {
+            let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+            let _ = most_active.fetch_max(now_active, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(1));
+            let _ = active.fetch_sub(1, Ordering::SeqCst);
+            i * 8 + j
+        })
+    });
+    assert_eq!(results.concat(), (0..64).collect::<Vec<_>>());
+    assert!(most_active.into_inner() <= cores);
+}
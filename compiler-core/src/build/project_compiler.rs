@@ -2,15 +2,22 @@ use codegen::ErlangApp;
 
 use crate::{
     build::{
-        dep_tree, package_compiler, package_compiler::PackageCompiler, project_root::ProjectRoot,
-        telemetry::Telemetry, Origin, Package, Target,
+        dep_tree, package_compiler, package_compiler::PackageCompiler, parallel,
+        project_root::ProjectRoot, telemetry::Telemetry, Origin, Package, Target,
     },
     codegen,
     config::PackageConfig,
     io::{FileSystemIO, FileSystemWriter},
-    type_, warning, Error, Warning,
+    type_,
+    warning::{self, Warnings},
+    Error, Warning,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
 };
-use std::{collections::HashMap, path::PathBuf};
 
 #[derive(Debug)]
 pub struct ProjectCompiler<'a, IO> {
@@ -20,8 +27,12 @@ pub struct ProjectCompiler<'a, IO> {
     packages: HashMap<String, Package>,
     importable_modules: HashMap<String, type_::Module>,
     defined_modules: HashMap<String, PathBuf>,
-    warnings: Vec<Warning>,
-    telemetry: Box<dyn Telemetry>,
+    warnings: Warnings,
+    /// The target of the root package, which all packages are compiled for.
+    target: Target,
+    /// Whether to generate code, or only type check the packages.
+    codegen: bool,
+    telemetry: Arc<dyn Telemetry>,
     io: IO,
 }
 
@@ -31,13 +42,13 @@ pub struct ProjectCompiler<'a, IO> {
 
 impl<'a, IO> ProjectCompiler<'a, IO>
 where
-    IO: FileSystemIO + Clone,
+    IO: FileSystemIO + Clone + Send + Sync,
 {
     pub fn new(
         root: &'a ProjectRoot,
         root_config: PackageConfig,
         configs: HashMap<String, PackageConfig>,
-        telemetry: Box<dyn Telemetry>,
+        telemetry: Arc<dyn Telemetry>,
         io: IO,
     ) -> Self {
         let estimated_number_of_modules = configs.len() * 5;
@@ -45,7 +56,9 @@ where
             packages: HashMap::with_capacity(configs.len()),
             importable_modules: HashMap::with_capacity(estimated_number_of_modules),
             defined_modules: HashMap::with_capacity(estimated_number_of_modules),
-            warnings: Vec::new(),
+            warnings: Warnings::new(root_config.warnings.clone()),
+            target: root_config.target,
+            codegen: true,
             root_config,
             telemetry,
             configs,
@@ -54,30 +67,65 @@ where
         }
     }
 
-    pub fn compile(mut self) -> Result<HashMap<String, Package>, Error> {
-        // Determine package processing order
-        let sequence = order_packages(&self.configs)?;
+    /// Set whether to generate code and `.app` files, rather than only type
+    /// checking the project
+    pub fn codegen(mut self, codegen: bool) -> Self {
+        self.codegen = codegen;
+        self
+    }
+
+    pub fn compile(mut self) -> Result<(HashMap<String, Package>, Warnings), Error> {
+        // Determine package processing order. Packages in the same stage do
+        // not depend on each other so they can be compiled concurrently.
+        let stages = order_packages(&self.configs)?;
+        let dependencies = transitive_dependencies(&self.configs, &stages);
 
         // Read and type check deps packages
-        for name in sequence {
-            let config = self.configs.remove(&name).expect("Missing package config");
-            self.compile_package(name, config, SourceLocations::Src)?;
+        for stage in stages {
+            let packages: Vec<_> = stage
+                .into_iter()
+                .map(|name| {
+                    let config = self.configs.remove(&name).expect("Missing package config");
+                    (name, config)
+                })
+                .collect();
+            let compiled = {
+                let compiler = &self;
+                parallel::map(packages, |(name, config)| {
+                    let dependencies = dependencies.get(&name).cloned().unwrap_or_default();
+                    compiler.compile_package(name, config, &dependencies, SourceLocations::Src)
+                })
+            };
+
+            // Register the packages in the order of the stage so that errors
+            // are reported deterministically.
+            for package in compiled {
+                self.register_package(package?)?;
+            }
         }
 
         // Read and type check top level package
         let root_config = std::mem::replace(&mut self.root_config, Default::default());
         let name = root_config.name.clone();
-        self.compile_package(name, root_config, SourceLocations::SrcAndTest)?;
-
-        Ok(self.packages)
+        let dependencies = self.packages.keys().cloned().collect();
+        let package = self.compile_package(
+            name,
+            root_config,
+            &dependencies,
+            SourceLocations::SrcAndTest,
+        )?;
+        self.register_package(package)?;
+        Ok((self.packages, self.warnings))
     }
 
     fn compile_package(
-        &mut self,
+        &self,
         name: String,
         config: PackageConfig,
+        dependencies: &HashSet<String>,
         locations: SourceLocations,
-    ) -> Result<(), Error> {
+    ) -> Result<CompiledPackage, Error> {
+        let start = Instant::now();
         self.telemetry.compiling_package(&name);
         let test_path = match locations {
             SourceLocations::SrcAndTest => {
@@ -89,39 +137,135 @@ where
         // TODO: this isn't the right location. We may want multiple output locations.
         let out_path = self.root.default_build_lib_package_src_path(&name);
         let options = package_compiler::Options {
-            target: Target::Erlang,
-            src_path: self.root.default_build_lib_package_src_path(&name),
+            target: self.target,
+            src_paths: vec![self.root.default_build_lib_package_src_path(&name)],
             out_path: out_path.clone(),
             test_path,
             name: name.clone(),
         };
 
-        let mut compiler = options.into_compiler(self.io.clone())?;
+        let mut compiler = options
+            .into_compiler(self.io.clone())?
+            .codegen(self.codegen)
+            .telemetry(self.telemetry.clone());
+
+        // Only the modules of the package's dependencies can be imported
+        let mut importable_modules = self
+            .importable_modules
+            .iter()
+            .filter(|(_, module)| dependencies.contains(&module.package))
+            .map(|(name, module)| (name.clone(), module.clone()))
+            .collect();
+        let mut defined_modules = self.defined_modules.clone();
+        let mut warnings = vec![];
 
         // Compile project
-        let compiled = compiler.compile(
-            &mut self.warnings,
-            &mut self.importable_modules,
-            &mut self.defined_modules,
-        )?;
-        ErlangApp::new(&out_path).render(self.io.clone(), &config, &compiled.modules)?;
+        let compiled =
+            compiler.compile(&mut warnings, &mut importable_modules, &mut defined_modules)?;
+        if self.codegen && self.target == Target::Erlang {
+            ErlangApp::new(&out_path).render(self.io.clone(), &config, &compiled.modules)?;
+            self.remove_orphaned_beams(&name, &compiled.removed_files)?;
+        }
+
+        // Warnings in dependencies cannot be fixed by the user so they are not
+        // reported, and the root package's levels do not apply to them.
+        if locations == SourceLocations::Src {
+            warnings.clear();
+        }
 
-        let _ = self.packages.insert(name, compiled);
+        self.telemetry.compiled_package(&name, start.elapsed());
+        Ok(CompiledPackage {
+            package: compiled,
+            warnings,
+            defined_modules,
+        })
+    }
+
+    /// Remove the bytecode compiled from Erlang modules that no longer exist
+    /// so that they cannot be loaded at runtime.
+    fn remove_orphaned_beams(&self, name: &str, removed_files: &[PathBuf]) -> Result<(), Error> {
+        let ebin = self.root.default_build_lib_package_ebin_path(name);
+        for path in removed_files {
+            if let (Some("erl"), Some(module)) = (
+                path.extension().and_then(|e| e.to_str()),
+                path.file_stem().and_then(|s| s.to_str()),
+            ) {
+                self.io.delete(&ebin.join(format!("{}.beam", module)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn register_package(&mut self, compiled: CompiledPackage) -> Result<(), Error> {
+        let CompiledPackage {
+            package,
+            warnings,
+            defined_modules,
+        } = compiled;
+
+        // Packages compiled concurrently cannot see each other's modules, so
+        // check they did not define the same module.
+        for (module, path) in defined_modules {
+            if let Some(first) = self.defined_modules.insert(module.clone(), path.clone()) {
+                if first != path {
+                    return Err(Error::DuplicateModule {
+                        module,
+                        first,
+                        second: path,
+                    });
+                }
+            }
+        }
+
+        for module in &package.modules {
+            let _ = self
+                .importable_modules
+                .insert(module.name.clone(), module.ast.type_info.clone());
+        }
+        self.warnings.extend(warnings);
+        let _ = self.packages.insert(package.name.clone(), package);
         Ok(())
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
+struct CompiledPackage {
+    package: Package,
+    warnings: Vec<Warning>,
+    defined_modules: HashMap<String, PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum SourceLocations {
     Src,
     SrcAndTest,
 }
 
-fn order_packages(configs: &HashMap<String, PackageConfig>) -> Result<Vec<String>, Error> {
-    dep_tree::toposort_deps(configs.values().map(package_deps_for_graph).collect())
+fn order_packages(configs: &HashMap<String, PackageConfig>) -> Result<Vec<Vec<String>>, Error> {
+    dep_tree::toposort_deps_in_stages(configs.values().map(package_deps_for_graph).collect())
         .map_err(convert_deps_tree_error)
 }
 
+/// The names of the packages each package depends on, directly or indirectly.
+fn transitive_dependencies(
+    configs: &HashMap<String, PackageConfig>,
+    stages: &[Vec<String>],
+) -> HashMap<String, HashSet<String>> {
+    let mut transitive: HashMap<String, HashSet<String>> = HashMap::with_capacity(configs.len());
+    // Each stage only depends on earlier stages, so their dependencies have
+    // already been found.
+    for name in stages.iter().flatten() {
+        let mut dependencies = HashSet::new();
+        for (dep, _) in configs.get(name).into_iter().flat_map(|c| &c.dependencies) {
+            let dep = dep.to_string();
+            dependencies.extend(transitive.get(&dep).into_iter().flatten().cloned());
+            let _ = dependencies.insert(dep);
+        }
+        let _ = transitive.insert(name.clone(), dependencies);
+    }
+    transitive
+}
+
 fn convert_deps_tree_error(e: dep_tree::Error) -> Error {
     match e {
         dep_tree::Error::Cycle(packages) => Error::PackageCycle { packages },
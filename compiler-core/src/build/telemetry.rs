@@ -1,5 +1,209 @@
-use std::fmt::Debug;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::Mutex,
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
+};
 
-pub trait Telemetry: Debug {
+/// Hooks called as a project is compiled, used to report progress to the
+/// user and to record how long each part of the build took.
+pub trait Telemetry: Debug + Send + Sync {
     fn compiling_package(&self, name: &str);
+
+    fn compiled_package(&self, _name: &str, _duration: Duration) {}
+
+    /// Called after each phase of compiling a module has finished.
+    fn timed(&self, _timing: &Timing) {}
+}
+
+/// A `Telemetry` that ignores all events.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullTelemetry;
+
+impl Telemetry for NullTelemetry {
+    fn compiling_package(&self, _name: &str) {}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Parse,
+    TypeCheck,
+    Codegen,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub package: String,
+    pub module: String,
+    pub phase: Phase,
+    pub start: Instant,
+    pub duration: Duration,
+    /// The thread the phase ran on, as modules are compiled concurrently
+    pub thread: ThreadId,
+}
+
+impl Timing {
+    /// Run a function and record how long it took.
+    pub fn time<T>(
+        telemetry: &dyn Telemetry,
+        package: &str,
+        module: &str,
+        phase: Phase,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        let start = Instant::now();
+        let result = f();
+        telemetry.timed(&Timing {
+            package: package.to_string(),
+            module: module.to_string(),
+            phase,
+            start,
+            duration: start.elapsed(),
+            thread: thread::current().id(),
+        });
+        result
+    }
+}
+
+/// Records the timings of a build so that they can be written to a file.
+#[derive(Debug)]
+pub struct TimingsRecorder {
+    start: Instant,
+    timings: Mutex<Vec<Timing>>,
+}
+
+#[derive(Debug, Serialize)]
+struct TimingRecord<'a> {
+    package: &'a str,
+    module: &'a str,
+    phase: Phase,
+    start_us: u128,
+    duration_us: u128,
+}
+
+#[derive(Debug, Serialize)]
+struct TraceEvent<'a> {
+    name: &'a str,
+    cat: Phase,
+    ph: &'static str,
+    ts: u128,
+    dur: u128,
+    pid: usize,
+    tid: usize,
+    args: TraceArgs<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct TraceArgs<'a> {
+    package: &'a str,
+}
+
+impl TimingsRecorder {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            timings: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn record(&self, timing: &Timing) {
+        self.timings
+            .lock()
+            .expect("Timings lock poisoned")
+            .push(timing.clone());
+    }
+
+    /// A JSON array of timings, with times in microseconds since the recorder
+    /// was created.
+    pub fn to_json(&self) -> String {
+        let timings = self.timings.lock().expect("Timings lock poisoned");
+        let records: Vec<_> = timings
+            .iter()
+            .map(|timing| TimingRecord {
+                package: &timing.package,
+                module: &timing.module,
+                phase: timing.phase,
+                start_us: self.offset(timing),
+                duration_us: timing.duration.as_micros(),
+            })
+            .collect();
+        serde_json::to_string_pretty(&records).expect("Timings JSON serialisation")
+    }
+
+    /// Timings in the Chrome trace event format, which can be viewed with
+    /// `chrome://tracing` or <https://ui.perfetto.dev>. Each thread is shown
+    /// as its own track, numbered in the order it first recorded a timing.
+    pub fn to_chrome_trace(&self) -> String {
+        let timings = self.timings.lock().expect("Timings lock poisoned");
+        let mut threads = HashMap::new();
+        let events: Vec<_> = timings
+            .iter()
+            .map(|timing| TraceEvent {
+                name: &timing.module,
+                cat: timing.phase,
+                ph: "X",
+                ts: self.offset(timing),
+                dur: timing.duration.as_micros(),
+                pid: 1,
+                tid: {
+                    let next = threads.len() + 1;
+                    *threads.entry(timing.thread).or_insert(next)
+                },
+                args: TraceArgs {
+                    package: &timing.package,
+                },
+            })
+            .collect();
+        serde_json::to_string(&events).expect("Timings JSON serialisation")
+    }
+
+    fn offset(&self, timing: &Timing) -> u128 {
+        timing
+            .start
+            .saturating_duration_since(self.start)
+            .as_micros()
+    }
+}
+
+impl Default for TimingsRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn chrome_trace_threads() {
+    let timing = |module: &str, thread| Timing {
+        package: "package".to_string(),
+        module: module.to_string(),
+        phase: Phase::TypeCheck,
+        start: Instant::now(),
+        duration: Duration::from_micros(1),
+        thread,
+    };
+    let other = thread::spawn(|| thread::current().id())
+        .join()
+        .expect("thread panicked");
+    let recorder = TimingsRecorder::new();
+    recorder.record(&timing("a", thread::current().id()));
+    recorder.record(&timing("b", other));
+    recorder.record(&timing("c", thread::current().id()));
+
+    let events: Vec<serde_json::Value> =
+        serde_json::from_str(&recorder.to_chrome_trace()).expect("trace JSON");
+    let threads: Vec<_> = events
+        .iter()
+        .map(|event| (event["name"].to_string(), event["tid"].to_string()))
+        .collect();
+    assert_eq!(
+        threads,
+        vec![
+            ("\"a\"".to_string(), "1".to_string()),
+            ("\"b\"".to_string(), "2".to_string()),
+            ("\"c\"".to_string(), "1".to_string()),
+        ]
+    );
 }
@@ -1,3 +1,5 @@
+use crate::build::Target;
+use crate::warning::{WarningKind, WarningLevel};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -18,11 +20,38 @@ pub struct PackageConfig {
     #[serde(default)]
     pub docs: Docs,
     #[serde(default)]
-    pub dependencies: HashMap<String, String>,
+    pub dependencies: HashMap<String, Dependency>,
+    #[serde(default, rename = "dev-dependencies")]
+    pub dev_dependencies: HashMap<String, Dependency>,
     #[serde(default)]
     pub otp_start_module: Option<String>,
     #[serde(default)]
     pub repository: Repository,
+    #[serde(default)]
+    pub target: Target,
+    #[serde(default)]
+    pub warnings: HashMap<WarningKind, WarningLevel>,
+}
+
+/// Where the source code of a dependency comes from.
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum Dependency {
+    /// A version requirement for a package published to Hex, such as
+    /// `"~> 1.0"`.
+    Hex(String),
+
+    /// A package in a local directory, relative to the directory of the
+    /// `gleam.toml` that depends on it.
+    Path { path: PathBuf },
+
+    /// A package in a git repository, at a branch, tag or commit. Without a
+    /// ref the default branch of the repository is used.
+    Git {
+        git: String,
+        #[serde(default, rename = "ref")]
+        reference: Option<String>,
+    },
 }
 
 #[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
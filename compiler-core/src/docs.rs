@@ -170,12 +170,14 @@ fn function<'a>(
             return_: retrn,
             arguments: args,
             location,
+            deprecation,
             ..
         } => Some(Function {
             name,
             documentation: markdown_documentation(doc),
             signature: print(formatter.external_fn_signature(true, name, args, retrn)),
             source_url: source_links.url(location),
+            deprecation_message: deprecation.message().unwrap_or_default(),
         }),
 
         Statement::Fn {
@@ -185,12 +187,14 @@ fn function<'a>(
             arguments: args,
             return_type: ret,
             location,
+            deprecation,
             ..
         } => Some(Function {
             name,
             documentation: markdown_documentation(doc),
             signature: print(formatter.docs_fn_signature(true, name, args, ret.clone())),
             source_url: source_links.url(location),
+            deprecation_message: deprecation.message().unwrap_or_default(),
         }),
 
         _ => None,
@@ -225,6 +229,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
             documentation: markdown_documentation(doc),
             constructors: vec![],
             source_url: source_links.url(location),
+            deprecation_message: "",
         }),
 
         Statement::CustomType {
@@ -235,6 +240,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
             doc,
             constructors: cs,
             location,
+            deprecation,
             ..
         } => Some(Type {
             name,
@@ -259,6 +265,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
                 })
                 .collect(),
             source_url: source_links.url(location),
+            deprecation_message: deprecation.message().unwrap_or_default(),
         }),
 
         Statement::CustomType {
@@ -268,6 +275,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
             parameters,
             doc,
             location,
+            deprecation,
             ..
         } => Some(Type {
             name,
@@ -275,6 +283,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
             documentation: markdown_documentation(doc),
             constructors: vec![],
             source_url: source_links.url(location),
+            deprecation_message: deprecation.message().unwrap_or_default(),
         }),
 
         Statement::TypeAlias {
@@ -284,6 +293,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
             doc,
             parameters: args,
             location,
+            deprecation,
             ..
         } => Some(Type {
             name,
@@ -291,6 +301,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedStatement) -> Opti
             documentation: markdown_documentation(doc),
             constructors: vec![],
             source_url: source_links.url(location),
+            deprecation_message: deprecation.message().unwrap_or_default(),
         }),
 
         _ => None,
@@ -309,12 +320,14 @@ fn constant<'a>(
             name,
             value,
             location,
+            deprecation,
             ..
         } => Some(Constant {
             name,
             definition: print(formatter.docs_const_expr(true, name, value)),
             documentation: markdown_documentation(doc),
             source_url: source_links.url(location),
+            deprecation_message: deprecation.message().unwrap_or_default(),
         }),
 
         _ => None,
@@ -337,6 +350,7 @@ struct Function<'a> {
     signature: String,
     documentation: String,
     source_url: String,
+    deprecation_message: &'a str,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -359,6 +373,7 @@ struct Type<'a> {
     documentation: String,
     constructors: Vec<TypeConstructor>,
     source_url: String,
+    deprecation_message: &'a str,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -367,6 +382,7 @@ struct Constant<'a> {
     definition: String,
     documentation: String,
     source_url: String,
+    deprecation_message: &'a str,
 }
 
 #[derive(Template)]
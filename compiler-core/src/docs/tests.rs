@@ -28,6 +28,12 @@ fn private_fun() {
   1
 }
 
+/// An old function
+@deprecated("use public_fun instead")
+pub fn old_fun() {
+  1
+}
+
 pub fn complicated_fun(
   over thing: a,
   from initial: b,
@@ -52,7 +58,10 @@ pub fn complicated_fun(
         repository: Default::default(),
         description: Default::default(),
         dependencies: Default::default(),
+        dev_dependencies: Default::default(),
         otp_start_module: None,
+        target: Default::default(),
+        warnings: Default::default(),
     };
 
     let mut analysed = crate::project::analysed(vec![input]).expect("Compilation failed");
@@ -86,6 +95,10 @@ pub fn complicated_fun(
     module_page.should_contain("pub fn implicit_return() -&gt; String");
     module_page.should_not_contain("private_fun()");
 
+    // Deprecations
+    module_page.should_contain("An old function");
+    module_page.should_contain("<strong>Deprecated:</strong> use public_fun instead");
+
     module_page.should_contain(
         "pub fn complicated_fun(
   over thing: a,
@@ -0,0 +1,171 @@
+//! Extraction of fenced Gleam code blocks from `///` and `////` documentation
+//! comments so that they can be compiled and run as tests.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{ast::SrcSpan, line_numbers::LineNumbers, Error, Result};
+use itertools::Itertools;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+use std::path::Path;
+
+/// A code example found in the documentation of a module.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Example {
+    /// The line of the opening fence of the code block in the original source.
+    pub line: usize,
+    pub code: String,
+}
+
+/// Find all the fenced Gleam code blocks in the doc and module comments of a
+/// module.
+pub fn examples(path: &Path, src: &str) -> Result<Vec<Example>> {
+    let (_, extra) = crate::parse::parse_module(src).map_err(|error| Error::Parse {
+        path: path.to_path_buf(),
+        src: src.to_string(),
+        error,
+    })?;
+    let line_numbers = LineNumbers::new(src);
+    let mut examples = vec![];
+    for comments in [&extra.module_comments, &extra.doc_comments].iter() {
+        for (first_line, text) in comment_blocks(comments, &line_numbers, src) {
+            examples.extend(
+                code_blocks(&text)
+                    .into_iter()
+                    .map(|(offset, code)| Example {
+                        line: first_line + offset,
+                        code,
+                    }),
+            );
+        }
+    }
+    examples.sort_by_key(|example| example.line);
+    Ok(examples)
+}
+
+/// Group the comment spans into blocks of consecutive lines, returning the
+/// line each block starts on and the comment text of the block.
+fn comment_blocks(
+    comments: &[SrcSpan],
+    line_numbers: &LineNumbers,
+    src: &str,
+) -> Vec<(usize, String)> {
+    let mut blocks: Vec<(usize, usize, Vec<&str>)> = vec![];
+    for span in comments {
+        let line = line_numbers.line_number(span.start);
+        let content = src.get(span.start..span.end).unwrap_or_default();
+        match blocks.last_mut() {
+            Some((_, last_line, lines)) if *last_line + 1 == line => {
+                *last_line = line;
+                lines.push(content);
+            }
+            _ => blocks.push((line, line, vec![content])),
+        }
+    }
+    blocks
+        .into_iter()
+        .map(|(first_line, _, lines)| (first_line, lines.join("\n")))
+        .collect()
+}
+
+/// Find the Gleam code blocks in a markdown text, returning the line offset of
+/// the opening fence within the text and the code of each block.
+fn code_blocks(markdown: &str) -> Vec<(usize, String)> {
+    let mut blocks = vec![];
+    let mut current: Option<(usize, String)> = None;
+    for (event, range) in Parser::new(markdown).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(language)))
+                if language.split_whitespace().next() == Some("gleam") =>
+            {
+                let offset = markdown
+                    .get(..range.start)
+                    .unwrap_or_default()
+                    .matches('\n')
+                    .count();
+                current = Some((offset, String::new()));
+            }
+
+            Event::Text(text) => {
+                if let Some((_, code)) = current.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(block) = current.take() {
+                    blocks.push(block);
+                }
+            }
+
+            _ => (),
+        }
+    }
+    blocks
+}
+
+/// The name of the test module generated for the examples of a module.
+pub fn test_module_name(module: &str) -> String {
+    format!("{}_doctest", module)
+}
+
+/// Generate the source of a test module that runs each example as an eunit
+/// test described with the file and line the example came from.
+///
+/// Import statements in examples are hoisted to the top of the test module.
+/// Returns `None` if there are no examples to run.
+///
+pub fn test_module(module: &str, display_path: &str, examples: &[Example]) -> Option<String> {
+    let mut imports = vec![format!("import {}", module)];
+    let mut tests = vec![];
+
+    for example in examples {
+        let (example_imports, body): (Vec<&str>, Vec<&str>) = example
+            .code
+            .lines()
+            .partition(|line| line.trim_start().starts_with("import "));
+        imports.extend(example_imports.into_iter().map(|i| i.trim().to_string()));
+
+        if body.iter().all(|line| line.trim().is_empty()) {
+            continue;
+        }
+        let body = body
+            .into_iter()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    String::new()
+                } else {
+                    format!("    {}", line)
+                }
+            })
+            .join("\n");
+        tests.push(format!(
+            "pub fn line_{line}_test_() {{
+  #(\"{path}:{line}\", fn() {{
+{body}
+  }})
+}}
+",
+            line = example.line,
+            path = display_path.replace('\\', "/").replace('"', "\\\""),
+            body = body.trim_end(),
+        ));
+    }
+
+    if tests.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "//// Examples from the documentation of the `{module}` module,
+//// generated from {path}
+
+{imports}
+
+{tests}",
+        module = module,
+        path = display_path,
+        imports = imports.into_iter().unique().join("\n"),
+        tests = tests.join("\n"),
+    ))
+}
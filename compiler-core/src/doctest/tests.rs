@@ -0,0 +1,179 @@
+use super::*;
+use std::path::PathBuf;
+
+fn examples_of(src: &str) -> Vec<Example> {
+    examples(&PathBuf::from("src/my/mod.gleam"), src).unwrap()
+}
+
+#[test]
+fn no_examples() {
+    assert_eq!(
+        examples_of(
+            r#"
+/// Just some text
+pub fn main() { 1 }
+"#
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn doc_comment_example() {
+    assert_eq!(
+        examples_of(
+            r#"
+/// Add one to a number
+///
+/// ```gleam
+/// assert 2 = mod.inc(1)
+/// ```
+pub fn inc(x) { x + 1 }
+"#
+        ),
+        vec![Example {
+            line: 4,
+            code: "assert 2 = mod.inc(1)\n".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn module_comment_example() {
+    assert_eq!(
+        examples_of(
+            r#"//// A module
+////
+//// ```gleam
+//// assert 1 = mod.one()
+//// assert 2 = mod.two()
+//// ```
+
+pub fn one() { 1 }
+pub fn two() { 2 }
+"#
+        ),
+        vec![Example {
+            line: 3,
+            code: "assert 1 = mod.one()\nassert 2 = mod.two()\n".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn non_gleam_code_blocks_are_ignored() {
+    assert_eq!(
+        examples_of(
+            r#"
+/// ```erlang
+/// one() -> 1.
+/// ```
+///
+/// ```
+/// not gleam either
+/// ```
+///
+///     indented code
+///
+pub fn one() { 1 }
+"#
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn multiple_examples_are_in_source_order() {
+    assert_eq!(
+        examples_of(
+            r#"
+/// ```gleam
+/// assert 1 = mod.one()
+/// ```
+pub fn one() { 1 }
+
+//// ```gleam
+//// assert 2 = mod.two()
+//// ```
+
+/// ```gleam
+/// assert 2 = mod.two()
+/// ```
+pub fn two() { 2 }
+"#
+        ),
+        vec![
+            Example {
+                line: 2,
+                code: "assert 1 = mod.one()\n".to_string(),
+            },
+            Example {
+                line: 7,
+                code: "assert 2 = mod.two()\n".to_string(),
+            },
+            Example {
+                line: 11,
+                code: "assert 2 = mod.two()\n".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_module_source() {
+    let examples = vec![
+        Example {
+            line: 4,
+            code: "import gleam/list\nassert [2] = list.map([1], mod.inc)\n".to_string(),
+        },
+        Example {
+            line: 9,
+            code: "import gleam/list\n".to_string(),
+        },
+        Example {
+            line: 12,
+            code: "let x = 1\n\nassert 2 = mod.inc(x)\n".to_string(),
+        },
+    ];
+    assert_eq!(
+        test_module("my/mod", "src/my/mod.gleam", &examples),
+        Some(
+            r#"//// Examples from the documentation of the `my/mod` module,
+//// generated from src/my/mod.gleam
+
+import my/mod
+import gleam/list
+
+pub fn line_4_test_() {
+  #("src/my/mod.gleam:4", fn() {
+    assert [2] = list.map([1], mod.inc)
+  })
+}
+
+pub fn line_12_test_() {
+  #("src/my/mod.gleam:12", fn() {
+    let x = 1
+
+    assert 2 = mod.inc(x)
+  })
+}
+"#
+            .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_module_source_parses() {
+    let examples = vec![Example {
+        line: 1,
+        code: "assert 2 = mod.inc(1)\n".to_string(),
+    }];
+    let src = test_module("mod", "src/mod.gleam", &examples).unwrap();
+    assert!(crate::parse::parse_module(&src).is_ok());
+}
+
+#[test]
+fn test_module_without_examples() {
+    assert_eq!(test_module("my/mod", "src/my/mod.gleam", &[]), None);
+}
@@ -7,13 +7,14 @@ mod tests;
 
 use crate::{
     ast::*,
+    build::Target,
     docvec,
     io::{OutputFile, Utf8Writer},
     line_numbers::LineNumbers,
     pretty::*,
     project::Analysed,
     type_::{
-        ModuleValueConstructor, PatternConstructor, Type, TypeVar, ValueConstructor,
+        ModuleValueConstructor, PatternConstructor, Type, TypeVar, TypeVarCell, ValueConstructor,
         ValueConstructorVariant,
     },
     Result,
@@ -292,8 +293,14 @@ fn register_imports(
             public: true,
             name,
             arguments: args,
+            implementations,
             ..
-        } => exports.push(atom(name.to_string()).append("/").append(args.len())),
+        } => {
+            // Functions without an Erlang implementation are not generated
+            if external_fn_implementation(implementations, Target::Erlang).is_some() {
+                exports.push(atom(name.to_string()).append("/").append(args.len()))
+            }
+        }
 
         Statement::ExternalType {
             name,
@@ -355,7 +362,7 @@ fn register_imports(
                 .keys()
                 .filter(|&id| !constructor_var_usages.contains_key(id))
                 .map(|&id| Type::Var {
-                    type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id })),
                 })
                 .collect();
             let phantom_vars_constructor = if !phantom_vars.is_empty() {
@@ -439,20 +446,22 @@ fn statement<'a>(
         } => vec![mod_fun(name, args, body, module, return_type, line_numbers)],
 
         Statement::ExternalFn {
-            fun,
-            module,
+            implementations,
             arguments: args,
             name,
             return_type,
             ..
-        } => vec![external_fun(
-            current_module,
-            name,
-            module,
-            fun,
-            args,
-            return_type,
-        )],
+        } => match external_fn_implementation(implementations, Target::Erlang) {
+            Some(implementation) => vec![external_fun(
+                current_module,
+                name,
+                &implementation.module,
+                &implementation.fun,
+                args,
+                return_type,
+            )],
+            None => vec![],
+        },
     }
 }
 
@@ -729,6 +738,44 @@ fn seq<'a>(expressions: &'a [TypedExpr], env: &mut Env<'a>) -> Document<'a> {
     documents.to_doc()
 }
 
+fn string_concatenate<'a>(
+    left: &'a TypedExpr,
+    right: &'a TypedExpr,
+    env: &mut Env<'a>,
+) -> Document<'a> {
+    let mut segments = vec![];
+    string_concatenate_segments(left, &mut segments, env);
+    string_concatenate_segments(right, &mut segments, env);
+    bit_string(segments.into_iter())
+}
+
+// Nested concatenations are flattened into a single binary construction
+fn string_concatenate_segments<'a>(
+    value: &'a TypedExpr,
+    segments: &mut Vec<Document<'a>>,
+    env: &mut Env<'a>,
+) {
+    match value {
+        TypedExpr::BinOp {
+            name: BinOp::Concatenate,
+            left,
+            right,
+            ..
+        } => {
+            string_concatenate_segments(left, segments, env);
+            string_concatenate_segments(right, segments, env);
+        }
+
+        TypedExpr::String { value, .. } => {
+            segments.push(value.to_doc().surround("\"", "\"/utf8"));
+        }
+
+        TypedExpr::Var { .. } => segments.push(expr(value, env).append("/binary")),
+
+        _ => segments.push(expr(value, env).surround("(", ")/binary")),
+    }
+}
+
 fn bin_op<'a>(
     name: &'a BinOp,
     left: &'a TypedExpr,
@@ -758,6 +805,7 @@ fn bin_op<'a>(
         BinOp::DivInt => "div",
         BinOp::DivFloat => "/",
         BinOp::ModuloInt => "rem",
+        BinOp::Concatenate => return string_concatenate(left, right, env),
     };
 
     let left_expr = match left {
@@ -837,7 +885,12 @@ fn try_<'a>(
         .group()
 }
 
-fn assert<'a>(value: &'a TypedExpr, pat: &'a TypedPattern, env: &mut Env<'a>) -> Document<'a> {
+fn assert<'a>(
+    value: &'a TypedExpr,
+    pat: &'a TypedPattern,
+    message: &'a Option<String>,
+    env: &mut Env<'a>,
+) -> Document<'a> {
     let mut vars: Vec<&str> = vec![];
     let body = maybe_block_expr(value, env);
     let pattern1 = pattern::to_doc(pat, &mut vars, env);
@@ -854,7 +907,9 @@ fn assert<'a>(value: &'a TypedExpr, pat: &'a TypedPattern, env: &mut Env<'a>) ->
             line(),
             erlang_error(
                 "assert",
-                "Assertion pattern match failed",
+                message
+                    .as_deref()
+                    .unwrap_or("Assertion pattern match failed"),
                 pat.location(),
                 vec![("value", env.local_var_name(ASSERT_VARIABLE))],
                 env,
@@ -1100,6 +1155,22 @@ fn bare_clause_guard<'a>(guard: &'a TypedClauseGuard, env: &mut Env<'a>) -> Docu
             .append(" =< ")
             .append(clause_guard(right, env)),
 
+        ClauseGuard::NegateBool { value, .. } => "not ".to_doc().append(clause_guard(value, env)),
+
+        ClauseGuard::NegateInt { value, .. } => match value.as_ref() {
+            ClauseGuard::Constant(Constant::Int { value: int, .. }) if int.starts_with('-') => "-"
+                .to_doc()
+                .append(clause_guard(value, env).surround("(", ")")),
+            _ => "-".to_doc().append(clause_guard(value, env)),
+        },
+
+        ClauseGuard::Concatenate { left, right, .. } => {
+            let mut segments = vec![];
+            clause_guard_concatenate_segments(left, &mut segments, env);
+            clause_guard_concatenate_segments(right, &mut segments, env);
+            bit_string(segments.into_iter())
+        }
+
         // Only local variables are supported and the typer ensures that all
         // ClauseGuard::Vars are local variables
         ClauseGuard::Var { name, .. } => env.local_var_name(name),
@@ -1110,6 +1181,31 @@ fn bare_clause_guard<'a>(guard: &'a TypedClauseGuard, env: &mut Env<'a>) -> Docu
     }
 }
 
+fn clause_guard_concatenate_segments<'a>(
+    guard: &'a TypedClauseGuard,
+    segments: &mut Vec<Document<'a>>,
+    env: &mut Env<'a>,
+) {
+    match guard {
+        ClauseGuard::Concatenate { left, right, .. } => {
+            clause_guard_concatenate_segments(left, segments, env);
+            clause_guard_concatenate_segments(right, segments, env);
+        }
+
+        ClauseGuard::Constant(Constant::String { value, .. }) => {
+            segments.push(value.to_doc().surround("\"", "\"/utf8"));
+        }
+
+        ClauseGuard::Var { .. } => segments.push(bare_clause_guard(guard, env).append("/binary")),
+
+        _ => segments.push(
+            bare_clause_guard(guard, env)
+                .surround("(", ")")
+                .append("/binary"),
+        ),
+    }
+}
+
 fn tuple_index_inline<'a>(
     tuple: &'a TypedClauseGuard,
     index: u64,
@@ -1136,15 +1232,18 @@ fn clause_guard<'a>(guard: &'a TypedClauseGuard, env: &mut Env<'a>) -> Document<
         | ClauseGuard::GtFloat { .. }
         | ClauseGuard::GtEqFloat { .. }
         | ClauseGuard::LtFloat { .. }
-        | ClauseGuard::LtEqFloat { .. } => "("
+        | ClauseGuard::LtEqFloat { .. }
+        | ClauseGuard::NegateBool { .. }
+        | ClauseGuard::NegateInt { .. } => "("
             .to_doc()
             .append(bare_clause_guard(guard, env))
             .append(")"),
 
         // Values are not wrapped
-        ClauseGuard::Constant(_) | ClauseGuard::Var { .. } | ClauseGuard::TupleIndex { .. } => {
-            bare_clause_guard(guard, env)
-        }
+        ClauseGuard::Constant(_)
+        | ClauseGuard::Var { .. }
+        | ClauseGuard::TupleIndex { .. }
+        | ClauseGuard::Concatenate { .. } => bare_clause_guard(guard, env),
     }
 }
 
@@ -1323,6 +1422,20 @@ fn maybe_block_expr<'a>(expression: &'a TypedExpr, env: &mut Env<'a>) -> Documen
     }
 }
 
+fn negate_with<'a>(op: &'static str, value: &'a TypedExpr, env: &mut Env<'a>) -> Document<'a> {
+    let value = match value {
+        // Parentheses are required to avoid the operator being taken as `--`
+        TypedExpr::BinOp { .. } | TypedExpr::NegateInt { .. } => {
+            expr(value, env).surround("(", ")")
+        }
+        TypedExpr::Int { value: int, .. } if int.starts_with('-') => {
+            expr(value, env).surround("(", ")")
+        }
+        _ => maybe_block_expr(value, env),
+    };
+    op.to_doc().append(value)
+}
+
 fn todo<'a>(message: &'a Option<String>, location: SrcSpan, env: &mut Env<'a>) -> Document<'a> {
     let message = message
         .as_deref()
@@ -1444,8 +1557,9 @@ fn expr<'a>(expression: &'a TypedExpr, env: &mut Env<'a>) -> Document<'a> {
             value,
             pattern,
             kind: AssignmentKind::Assert,
+            message,
             ..
-        } => assert(value, pattern, env),
+        } => assert(value, pattern, message, env),
 
         TypedExpr::Assignment {
             value,
@@ -1462,6 +1576,10 @@ fn expr<'a>(expression: &'a TypedExpr, env: &mut Env<'a>) -> Document<'a> {
             name, left, right, ..
         } => bin_op(name, left, right, env),
 
+        TypedExpr::NegateBool { value, .. } => negate_with("not ", value, env),
+
+        TypedExpr::NegateInt { value, .. } => negate_with("-", value, env),
+
         TypedExpr::Tuple { elems, .. } => tuple(elems.iter().map(|e| maybe_block_expr(e, env))),
 
         TypedExpr::BitString { segments, .. } => bit_string(
@@ -1704,7 +1822,7 @@ fn collect_type_var_usages<'a>(
 
 fn type_var_ids(type_: &Type, ids: &mut HashMap<usize, usize>) {
     match type_ {
-        Type::Var { type_: typ } => match typ.borrow().deref() {
+        Type::Var { type_: typ } => match typ.read().deref() {
             TypeVar::Generic { id, .. } | TypeVar::Unbound { id, .. } => {
                 let count = ids.entry(*id).or_insert(0);
                 *count += 1;
@@ -1801,7 +1919,7 @@ impl<'a> TypePrinter<'a> {
 
     pub fn print(&self, type_: &Type) -> Document<'static> {
         match type_ {
-            Type::Var { type_: typ } => self.print_var(&typ.borrow()),
+            Type::Var { type_: typ } => self.print_var(&typ.read()),
 
             Type::App {
                 name, module, args, ..
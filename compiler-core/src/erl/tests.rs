@@ -104,13 +104,13 @@ macro_rules! assert_erl {
             build::Origin,
             erl::module,
             line_numbers::LineNumbers,
-            type_::{build_prelude, infer_module},
+            type_::{build_prelude, infer_module, UidGenerator},
         };
         use std::collections::HashMap;
         let (mut ast, _) = crate::parse::parse_module($src).expect("syntax error");
         ast.name = vec!["the_app".to_string()];
         let mut modules = HashMap::new();
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
         // to have one place where we create all this required state for use in each
@@ -118,7 +118,7 @@ macro_rules! assert_erl {
         let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
         let ast = infer_module(
             crate::build::Target::Erlang,
-            &mut 0,
+            &mut UidGenerator::default(),
             ast,
             Origin::Src,
             "thepackage",
@@ -905,7 +905,7 @@ bitstring_discard(X) ->
 fn bit_string_declare_and_use_var() {
     assert_erl!(
         r#"pub fn go(x) {
-  let <<name_size:8, name:binary-size(name_size)>> = x
+  assert <<name_size:8, name:binary-size(name_size)>> = x
   name
 }"#,
         r#"-module(the_app).
@@ -915,8 +915,18 @@ fn bit_string_declare_and_use_var() {
 
 -spec go(bitstring()) -> bitstring().
 go(X) ->
-    <<Name_size:8, Name:Name_size/binary>> = X,
-    Name.
+    <<Name_size@1:8, Name@1:Name_size@1/binary>> = case X of
+        <<Name_size:8, Name:Name_size/binary>> -> <<Name_size:8,
+                                                    Name:Name_size/binary>>;
+        _try ->
+            erlang:error(#{gleam_error => assert,
+                           message => <<"Assertion pattern match failed"/utf8>>,
+                           value => _try,
+                           module => <<"the_app"/utf8>>,
+                           function => <<"go"/utf8>>,
+                           line => 2})
+    end,
+    Name@1.
 "#,
     );
 }
@@ -1615,6 +1625,26 @@ get_age(Person) ->
 -spec get_name(person()) -> binary().
 get_name(Person) ->
     erlang:element(2, Person).
+"#,
+    );
+
+    // We can use record accessors for fields shared by every constructor
+    assert_erl!(
+        r#"
+pub type Shape { Circle(name: String, radius: Float) Square(name: String) }
+pub fn get_name(shape: Shape) { shape.name }
+"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-export([get_name/1]).
+-export_type([shape/0]).
+
+-type shape() :: {circle, binary(), float()} | {square, binary()}.
+
+-spec get_name(shape()) -> binary().
+get_name(Shape) ->
+    erlang:element(2, Shape).
 "#,
     );
 }
@@ -1879,8 +1909,8 @@ fn bit_strings() {
   let a = 1
   let simple = <<1, a>>
   let complex = <<4:int-big, 5.0:little-float, 6:native-int>>
-  let <<7:2, 8:size(3), b:binary-size(4)>> = <<1>>
-  let <<c:8-unit(1), d:binary-size(2)-unit(2)>> = <<1>>
+  assert <<7:2, 8:size(3), b:binary-size(4)>> = <<1>>
+  assert <<c:8-unit(1), d:binary-size(2)-unit(2)>> = <<1>>
 
   simple
 }
@@ -1893,8 +1923,26 @@ main() ->
     A = 1,
     Simple = <<1, A>>,
     Complex = <<4/integer-big, 5.0/little-float, 6/native-integer>>,
-    <<7:2, 8:3, B:4/binary>> = <<1>>,
-    <<C:8/unit:1, D:2/binary-unit:2>> = <<1>>,
+    <<7:2, 8:3, B@1:4/binary>> = case <<1>> of
+        <<7:2, 8:3, B:4/binary>> -> <<7:2, 8:3, B:4/binary>>;
+        _try ->
+            erlang:error(#{gleam_error => assert,
+                           message => <<"Assertion pattern match failed"/utf8>>,
+                           value => _try,
+                           module => <<"the_app"/utf8>>,
+                           function => <<"main"/utf8>>,
+                           line => 5})
+    end,
+    <<C@1:8/unit:1, D@1:2/binary-unit:2>> = case <<1>> of
+        <<C:8/unit:1, D:2/binary-unit:2>> -> <<C:8/unit:1, D:2/binary-unit:2>>;
+        _try@1 ->
+            erlang:error(#{gleam_error => assert,
+                           message => <<"Assertion pattern match failed"/utf8>>,
+                           value => _try@1,
+                           module => <<"the_app"/utf8>>,
+                           function => <<"main"/utf8>>,
+                           line => 6})
+    end,
     Simple.
 "#,
     );
@@ -1927,7 +1975,7 @@ main() ->
     assert_erl!(
         r#"fn main() {
   let a = 1
-  let <<b, 1>> = <<1, a>>
+  assert <<b, 1>> = <<1, a>>
   b
 }
 "#,
@@ -1937,15 +1985,24 @@ main() ->
 -spec main() -> integer().
 main() ->
     A = 1,
-    <<B, 1>> = <<1, A>>,
-    B.
+    <<B@1, 1>> = case <<1, A>> of
+        <<B, 1>> -> <<B, 1>>;
+        _try ->
+            erlang:error(#{gleam_error => assert,
+                           message => <<"Assertion pattern match failed"/utf8>>,
+                           value => _try,
+                           module => <<"the_app"/utf8>>,
+                           function => <<"main"/utf8>>,
+                           line => 3})
+    end,
+    B@1.
 "#,
     );
 
     assert_erl!(
         r#"fn main() {
   let a = <<"test":utf8>>
-  let <<b:utf8_codepoint, "st":utf8>> = a
+  assert <<b:utf8_codepoint, "st":utf8>> = a
   b
 }
 "#,
@@ -1955,8 +2012,17 @@ main() ->
 -spec main() -> integer().
 main() ->
     A = <<"test"/utf8>>,
-    <<B/utf8, "st"/utf8>> = A,
-    B.
+    <<B@1/utf8, "st"/utf8>> = case A of
+        <<B/utf8, "st"/utf8>> -> <<B/utf8, "st"/utf8>>;
+        _try ->
+            erlang:error(#{gleam_error => assert,
+                           message => <<"Assertion pattern match failed"/utf8>>,
+                           value => _try,
+                           module => <<"the_app"/utf8>>,
+                           function => <<"main"/utf8>>,
+                           line => 3})
+    end,
+    B@1.
 "#,
     );
 
@@ -2399,20 +2465,25 @@ main() ->
 
     assert_erl!(
         r#"
-fn main() {
-  let 100_000 = 1
-  let 100_000.00101 = 1.
-  1
+fn main(x) {
+  case x {
+    #(100_000, 100_000.00101) -> 1
+    _ -> 2
+  }
 }
 "#,
         r#"-module(the_app).
 -compile(no_auto_import).
 
--spec main() -> integer().
-main() ->
-    100000 = 1,
-    100000.00101 = 1.0,
-    1.
+-spec main({integer(), float()}) -> integer().
+main(X) ->
+    case X of
+        {100000, 100000.00101} ->
+            1;
+
+        _ ->
+            2
+    end.
 "#,
     );
 }
@@ -2859,6 +2930,33 @@ go() ->
     // TODO: patterns that are just vars don't render a case expression
 }
 
+#[test]
+fn assert_with_message() {
+    assert_erl!(
+        r#"fn go() {
+  assert Ok(y) = Ok(1) as "should be ok"
+  y
+}"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-spec go() -> integer().
+go() ->
+    {ok, Y@1} = case {ok, 1} of
+        {ok, Y} -> {ok, Y};
+        _try ->
+            erlang:error(#{gleam_error => assert,
+                           message => <<"should be ok"/utf8>>,
+                           value => _try,
+                           module => <<"the_app"/utf8>>,
+                           function => <<"go"/utf8>>,
+                           line => 2})
+    end,
+    Y@1.
+"#,
+    );
+}
+
 // https://github.com/gleam-lang/gleam/issues/1006
 #[test]
 fn keyword_constructors() {
@@ -3019,3 +3117,132 @@ x(F) ->
 "
     );
 }
+
+#[test]
+fn string_concatenation() {
+    assert_erl!(
+        r#"pub fn go(name, f) {
+  "Hello, " <> name <> f(name) <> "!"
+}
+
+pub fn guard(x) {
+  case x {
+    _ if x == "a" <> "b" -> 1
+    _ -> 2
+  }
+}"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-export([go/2, guard/1]).
+
+-spec go(binary(), fun((binary()) -> binary())) -> binary().
+go(Name, F) ->
+    <<"Hello, "/utf8, Name/binary, (F(Name))/binary, "!"/utf8>>.
+
+-spec guard(binary()) -> integer().
+guard(X) ->
+    case X of
+        _ when X =:= <<"a"/utf8, "b"/utf8>> ->
+            1;
+
+        _ ->
+            2
+    end.
+"#
+    );
+}
+
+#[test]
+fn negation() {
+    assert_erl!(
+        r#"pub fn go(a, b) {
+  let x = !a
+  let y = -b * 2
+  let z = -{ b - 1 }
+  let w = - -1
+  case a {
+    _ if !a && -b > 0 -> x
+    _ -> y + z + w > 0
+  }
+}"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-export([go/2]).
+
+-spec go(boolean(), integer()) -> boolean().
+go(A, B) ->
+    X = not A,
+    Y = -B * 2,
+    Z = -(B - 1),
+    W = -(-1),
+    case A of
+        _ when (not A) andalso ((-B) > 0) ->
+            X;
+
+        _ ->
+            ((Y + Z) + W) > 0
+    end.
+"#
+    );
+}
+
+#[test]
+fn external_fn_targets() {
+    assert_erl!(
+        r#"pub external fn now() -> Int =
+  erlang "os" "system_time"
+  javascript "./time.mjs" "now"
+pub fn main() { now() }"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-export([now/0, main/0]).
+
+-spec now() -> integer().
+now() ->
+    os:system_time().
+
+-spec main() -> integer().
+main() ->
+    os:system_time().
+"#,
+    );
+
+    // Functions with no Erlang implementation are not generated
+    assert_erl!(
+        r#"pub external fn now() -> Int = javascript "./time.mjs" "now"
+pub fn main() { 1 }"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-export([main/0]).
+
+-spec main() -> integer().
+main() ->
+    1.
+"#,
+    );
+}
+
+#[test]
+fn label_shorthand() {
+    assert_erl!(
+        r#"pub fn add(x x, y y) { x - y }
+pub fn main(y, x) { add(y:, x:) }"#,
+        r#"-module(the_app).
+-compile(no_auto_import).
+
+-export([add/2, main/2]).
+
+-spec add(integer(), integer()) -> integer().
+add(X, Y) ->
+    X - Y.
+
+-spec main(integer(), integer()) -> integer().
+main(Y, X) ->
+    add(X, Y).
+"#,
+    );
+}
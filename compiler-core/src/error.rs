@@ -48,6 +48,13 @@ pub enum Error {
         second: PathBuf,
     },
 
+    LibraryPackageMismatch {
+        path: PathBuf,
+        module: Name,
+        expected: Name,
+        found: Name,
+    },
+
     SrcImportingTest {
         path: PathBuf,
         src: Src,
@@ -118,15 +125,62 @@ pub enum Error {
         error: Option<String>,
     },
 
+    IncompatibleMetadata {
+        path: Option<PathBuf>,
+        problem: MetadataProblem,
+    },
+
     ForbiddenWarnings {
         count: usize,
     },
 
+    InvalidPackageInterface {
+        path: PathBuf,
+        error: String,
+    },
+
+    InvalidVersion {
+        version: String,
+    },
+
+    InsufficientVersionBump {
+        previous: String,
+        current: String,
+        minimum: String,
+        bump: String,
+    },
+
     JavaScript {
         path: PathBuf,
         src: Src,
         error: crate::javascript::Error,
     },
+
+    UnknownPackage {
+        name: String,
+    },
+
+    UnknownPackageVersion {
+        name: String,
+        version: String,
+    },
+
+    InvalidPackageName {
+        name: String,
+    },
+
+    InvalidVersionRequirement {
+        requirement: String,
+    },
+
+    DependencyNotFound {
+        name: String,
+    },
+
+    UnableToEditConfig {
+        path: PathBuf,
+        reason: String,
+    },
 }
 
 impl From<capnp::Error> for Error {
@@ -145,6 +199,14 @@ impl From<capnp::NotInSchema> for Error {
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum MetadataProblem {
+    MissingHeader,
+    SchemaVersion { found: u32, expected: u32 },
+    CompilerVersion { found: String, expected: String },
+    ContentHash,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum InvalidProjectNameReason {
     Format,
@@ -248,6 +310,39 @@ of the Gleam dependency modules."
                 }
             }
 
+            Error::IncompatibleMetadata { path, problem } => {
+                let file = match path {
+                    Some(path) => format!("The module metadata file\n\n    {}\n\n", path.display()),
+                    None => "Module metadata ".to_string(),
+                };
+                let problem = match problem {
+                    MetadataProblem::MissingHeader => {
+                        "was not written by a compatible version of Gleam.".to_string()
+                    }
+                    MetadataProblem::SchemaVersion { found, expected } => format!(
+                        "uses metadata schema version {} but this version of Gleam
+uses schema version {}.",
+                        found, expected
+                    ),
+                    MetadataProblem::CompilerVersion { found, expected } => format!(
+                        "was written by Gleam v{} but this is Gleam v{}.",
+                        found, expected
+                    ),
+                    MetadataProblem::ContentHash => "is corrupt or incomplete.".to_string(),
+                };
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Incompatible module metadata".to_string(),
+                    label: format!(
+                        "{}{}
+
+Rebuild the library that contains it with this version of Gleam and
+try again.",
+                        file, problem
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
             Error::InvalidProjectName { name, reason } => {
                 let diagnostic = ProjectErrorDiagnostic {
                     title: "Invalid project name".to_string(),
@@ -435,6 +530,30 @@ Second: {}",
                 write_project(buf, diagnostic);
             }
 
+            Error::LibraryPackageMismatch {
+                path,
+                module,
+                expected,
+                found,
+            } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Library package mismatch".to_string(),
+                    label: format!(
+                        "The module `{}` was expected to belong to the `{}` package
+but its metadata says it belongs to the `{}` package.
+
+Metadata: {}
+
+Check the name given for the library with the `--lib` flag.",
+                        module,
+                        expected,
+                        found,
+                        path.to_str().expect("pretty error print PathBuf to_str"),
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
             Error::FileIo {
                 kind,
                 action,
@@ -1422,12 +1541,104 @@ Try a different name for this module.",
                     };
                     write_project(buf, diagnostic);
                 }
+
+                TypeError::UnsupportedTarget {
+                    location,
+                    name,
+                    via,
+                    target,
+                } => {
+                    let diagnostic = Diagnostic {
+                        title: "Unsupported target".to_string(),
+                        label: format!("has no {} implementation", target),
+                        file: path.to_str().unwrap().to_string(),
+                        src: src.to_string(),
+                        location: *location,
+                    };
+                    write(buf, diagnostic, Severity::Error);
+                    if via.is_empty() {
+                        writeln!(
+                            buf,
+                            "The external function `{}` cannot be used when compiling for {}.",
+                            name, target
+                        )
+                        .unwrap();
+                    } else {
+                        let chain = std::iter::once(name).chain(via).join(" -> ");
+                        writeln!(
+                            buf,
+                            "`{}` cannot be used when compiling for {} as it depends on an
+external function without an implementation for this target:
+
+    {}
+",
+                            name, target, chain
+                        )
+                        .unwrap();
+                    }
+                    writeln!(
+                        buf,
+                        "Hint: Add an implementation for this target to the external function."
+                    )
+                    .unwrap();
+                }
+
+                TypeError::InexhaustiveLetAssignment { location } => {
+                    let diagnostic = Diagnostic {
+                        title: "Inexhaustive pattern".to_string(),
+                        label: "this pattern does not match every value".to_string(),
+                        file: path.to_str().unwrap().to_string(),
+                        src: src.to_string(),
+                        location: *location,
+                    };
+                    write(buf, diagnostic, Severity::Error);
+                    writeln!(
+                        buf,
+                        "A `let` must have a pattern that matches any value of its type.
+
+Hint: Use `assert` if you want to crash when the pattern does not match,
+or `case` to handle every possible value."
+                    )
+                    .unwrap();
+                }
+
+                TypeError::PatternNeverMatches { location } => {
+                    let diagnostic = Diagnostic {
+                        title: "Pattern never matches".to_string(),
+                        label: "this pattern cannot match the value".to_string(),
+                        file: path.to_str().unwrap().to_string(),
+                        src: src.to_string(),
+                        location: *location,
+                    };
+                    write(buf, diagnostic, Severity::Error);
+                    writeln!(
+                        buf,
+                        "This assertion will always fail, crashing the program when it is run."
+                    )
+                    .unwrap();
+                }
             },
 
             Error::Parse { path, src, error } => {
                 let crate::parse::error::ParseError { location, error } = error;
 
                 let (label, extra) = match error {
+                    ParseErrorType::AttributeWithoutDefinition => (
+                        "This attribute is not followed by a definition.",
+                        vec!["Hint: Put a function, type or constant after it.".to_string()]
+                    ),
+                    ParseErrorType::DuplicateAttribute => (
+                        "This attribute has already been given.",
+                        vec!["Hint: Remove it.".to_string()]
+                    ),
+                    ParseErrorType::DuplicateExternalTarget => (
+                        "This target already has an implementation.",
+                        vec!["Hint: Give each target only one implementation.".to_string()]
+                    ),
+                    ParseErrorType::EmptyDeprecationMessage => (
+                        "This deprecation message is empty.",
+                        vec!["Hint: Say what to use instead.".to_string()]
+                    ),
                     ParseErrorType::ExpectedExpr => (
                         "I was expecting an expression after this.",
                         vec![]
@@ -1472,6 +1683,10 @@ Try a different name for this module.",
                         "I'm expecting a type name here.",
                         vec![ "Hint: Type names start with a uppercase letter, and can contain a-z, A-Z, or 0-9.".to_string()]
                     ),
+                    ParseErrorType::InvalidAttributeTarget => (
+                        "This definition cannot be deprecated.",
+                        vec!["Hint: Only functions, types and constants can be deprecated.".to_string()]
+                    ),
                     ParseErrorType::InvalidBitStringSegment => (
                         "This is not a valid BitString segment option.",
                         vec![ "Hint: Valid BitString segment options are:".to_string(),
@@ -1539,6 +1754,10 @@ Try a different name for this module.",
                         "This is a reserved word.",
                         vec!["Hint: I was expecting to see a name here.".to_string(), "See: https://gleam.run/book/tour/reserved-words".to_string()]
                     ),
+                    ParseErrorType::UnknownAttribute => (
+                        "I don't recognise this attribute.",
+                        vec!["Hint: The only supported attribute is @deprecated(\"...\").".to_string()]
+                    ),
                     ParseErrorType::UnexpectedToken { expected } => {
                         let mut messages = expected.clone();
                         if let Some(s) = messages.first_mut() {
@@ -1671,13 +1890,132 @@ but it cannot be found.",
                 };
                 let diagnostic = ProjectErrorDiagnostic {
                     title: format!("{} {} generated.", count, word_warning),
-                    label: "Your project was compiled with the `--warnings-as-errors` flag.
+                    label: "Warnings are treated as errors by the `--warnings-as-errors` flag
+or by the `[warnings]` table of gleam.toml.
 Fix the warnings and try again!"
                         .to_string(),
                 };
                 write_project(buf, diagnostic);
             }
 
+            Error::InvalidPackageInterface { path, error } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Invalid package interface".to_string(),
+                    label: format!(
+                        "The package interface file could not be read:
+
+    {}
+
+The error from the JSON parser was:
+
+    {}",
+                        path.to_str().unwrap(),
+                        error
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::InvalidVersion { version } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Invalid version".to_string(),
+                    label: format!(
+                        "`{}` is not a valid version. Versions must be in the
+format `major.minor.patch`, for example `1.0.0`.",
+                        version
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::InsufficientVersionBump {
+                previous,
+                current,
+                minimum,
+                bump,
+            } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Version bump required".to_string(),
+                    label: format!(
+                        "The changes since version {} require a {} version bump,
+but the version in gleam.toml is {}.
+
+Update the version to {} or later and try again.",
+                        previous, bump, current, minimum
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::UnknownPackage { name } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Unknown package".to_string(),
+                    label: format!("The package `{}` could not be found on Hex.", name),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::UnknownPackageVersion { name, version } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Unknown package version".to_string(),
+                    label: format!(
+                        "Version {} of the package `{}` could not be found on Hex.",
+                        version, name
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::InvalidPackageName { name } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Invalid package name".to_string(),
+                    label: format!(
+                        "`{}` is not a valid package name.
+Package names start with a lowercase letter and may only contain
+lowercase letters, numbers and underscores.",
+                        name
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::InvalidVersionRequirement { requirement } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Invalid version requirement".to_string(),
+                    label: format!(
+                        "`{}` is not a valid version requirement.
+Try a requirement such as `~> 1.0` or `>= 0.3.0 and < 0.4.0`.",
+                        requirement
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::DependencyNotFound { name } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Dependency not found".to_string(),
+                    label: format!("`{}` is not a dependency of this project.", name),
+                };
+                write_project(buf, diagnostic);
+            }
+
+            Error::UnableToEditConfig { path, reason } => {
+                let diagnostic = ProjectErrorDiagnostic {
+                    title: "Unable to edit config".to_string(),
+                    label: format!(
+                        "The dependencies in this file could not be updated:
+
+    {}
+
+{}
+Edit the file by hand and try again.",
+                        path.to_string_lossy(),
+                        reason
+                    ),
+                };
+                write_project(buf, diagnostic);
+            }
+
             Error::JavaScript { src, path, error } => match error {
                 javascript::Error::Unsupported { feature, location } => {
                     let diagnostic = Diagnostic {
@@ -223,18 +223,25 @@ impl<'comments> Formatter<'comments> {
                 arguments: args,
                 name,
                 return_: retrn,
-                module,
-                fun,
+                implementations,
                 ..
             } => self
                 .external_fn_signature(*public, name, args, retrn)
                 .append(" =")
-                .append(line())
-                .append("  \"")
-                .append(module.as_str())
-                .append("\" \"")
-                .append(fun.as_str())
-                .append("\""),
+                .append(concat(implementations.iter().map(|implementation| {
+                    let target = match implementation.target {
+                        Some(target) => Document::String(format!("{} ", target)),
+                        None => nil(),
+                    };
+                    line()
+                        .append("  ")
+                        .append(target)
+                        .append("\"")
+                        .append(implementation.module.as_str())
+                        .append("\" \"")
+                        .append(implementation.fun.as_str())
+                        .append("\"")
+                }))),
 
             Statement::ExternalType {
                 public,
@@ -377,7 +384,20 @@ impl<'comments> Formatter<'comments> {
 
     fn documented_statement<'a>(&mut self, s: &'a UntypedStatement) -> Document<'a> {
         let comments = self.doc_comments(s.location().start);
-        comments.append(self.statement(s)).group()
+        let attributes = match s.deprecation().and_then(Deprecation::message) {
+            Some(message) => docvec![
+                "@deprecated(",
+                message.to_doc().surround("\"", "\""),
+                ")",
+                force_break(),
+                line()
+            ],
+            None => nil(),
+        };
+        comments
+            .append(attributes)
+            .append(self.statement(s))
+            .group()
     }
 
     fn doc_comments<'a>(&mut self, limit: usize) -> Document<'a> {
@@ -580,6 +600,7 @@ impl<'comments> Formatter<'comments> {
         then: Option<&'a UntypedExpr>,
         kind: Option<AssignmentKind>,
         annotation: &'a Option<TypeAst>,
+        message: Option<&'a str>,
     ) -> Document<'a> {
         let _ = self.pop_empty_lines(pattern.location().end);
 
@@ -602,7 +623,11 @@ impl<'comments> Formatter<'comments> {
         }
         .append(pattern.append(annotation).group())
         .append(" =")
-        .append(self.assigned_value(value));
+        .append(self.assigned_value(value))
+        .append(match message {
+            Some(message) => docvec![" as \"", message, "\""],
+            None => nil(),
+        });
 
         if let Some(then) = then {
             doc.append(if self.pop_empty_lines(then.start_byte_index()) {
@@ -634,6 +659,10 @@ impl<'comments> Formatter<'comments> {
 
             UntypedExpr::Sequence { expressions, .. } => self.sequence(expressions),
 
+            UntypedExpr::NegateBool { value, .. } => self.negate_with("!", value),
+
+            UntypedExpr::NegateInt { value, .. } => self.negate_with("-", value),
+
             UntypedExpr::Var { name, .. } if name == CAPTURE_VARIABLE => "_".to_doc(),
 
             UntypedExpr::Var { name, .. } => name.to_doc(),
@@ -670,8 +699,16 @@ impl<'comments> Formatter<'comments> {
                 pattern,
                 annotation,
                 kind,
+                message,
                 ..
-            } => self.assignment(pattern, value, None, Some(*kind), annotation),
+            } => self.assignment(
+                pattern,
+                value,
+                None,
+                Some(*kind),
+                annotation,
+                message.as_deref(),
+            ),
 
             UntypedExpr::Try {
                 value,
@@ -679,7 +716,7 @@ impl<'comments> Formatter<'comments> {
                 annotation,
                 then,
                 ..
-            } => self.assignment(pattern, value, Some(then), None, annotation),
+            } => self.assignment(pattern, value, Some(then), None, annotation, None),
 
             UntypedExpr::Case {
                 subjects, clauses, ..
@@ -1060,6 +1097,14 @@ impl<'comments> Formatter<'comments> {
         wrap_args(args.iter().map(|e| self.external_fn_arg(e)))
     }
 
+    fn negate_with<'a>(&mut self, op: &'a str, value: &'a UntypedExpr) -> Document<'a> {
+        // Unary operators bind tighter than any binary operator
+        let precedence = value.binop_precedence();
+        let value = self.wrap_expr(value);
+        op.to_doc()
+            .append(self.operator_side(value, u8::MAX, precedence))
+    }
+
     fn wrap_expr<'a>(&mut self, expr: &'a UntypedExpr) -> Document<'a> {
         match expr {
             UntypedExpr::Sequence { .. }
@@ -1077,16 +1122,23 @@ impl<'comments> Formatter<'comments> {
 
     fn call_arg<'a>(&mut self, arg: &'a CallArg<UntypedExpr>) -> Document<'a> {
         match &arg.label {
+            Some(s) if arg.shorthand => commented(
+                s.to_doc().append(":"),
+                self.pop_comments(arg.location.start),
+            ),
             Some(s) => commented(
                 s.to_doc().append(": "),
                 self.pop_comments(arg.location.start),
-            ),
-            None => nil(),
+            )
+            .append(self.wrap_expr(&arg.value)),
+            None => self.wrap_expr(&arg.value),
         }
-        .append(self.wrap_expr(&arg.value))
     }
 
     fn record_update_arg<'a>(&mut self, arg: &'a UntypedRecordUpdateArg) -> Document<'a> {
+        if arg.shorthand {
+            return arg.label.to_doc().append(":");
+        }
         arg.label
             .to_doc()
             .append(": ")
@@ -1258,6 +1310,17 @@ impl<'comments> Formatter<'comments> {
             .append(self.pattern(&arg.value))
     }
 
+    fn negated_guard<'a>(&mut self, value: &'a UntypedClauseGuard) -> Document<'a> {
+        match value {
+            ClauseGuard::Var { .. }
+            | ClauseGuard::TupleIndex { .. }
+            | ClauseGuard::Constant(_)
+            | ClauseGuard::NegateBool { .. }
+            | ClauseGuard::NegateInt { .. } => self.clause_guard(value),
+            _ => "{ ".to_doc().append(self.clause_guard(value)).append(" }"),
+        }
+    }
+
     fn clause_guard<'a>(&mut self, clause_guard: &'a UntypedClauseGuard) -> Document<'a> {
         match clause_guard {
             ClauseGuard::And { left, right, .. } => self
@@ -1320,6 +1383,15 @@ impl<'comments> Formatter<'comments> {
                 .append(" <=. ")
                 .append(self.clause_guard(right)),
 
+            ClauseGuard::NegateBool { value, .. } => "!".to_doc().append(self.negated_guard(value)),
+
+            ClauseGuard::NegateInt { value, .. } => "-".to_doc().append(self.negated_guard(value)),
+
+            ClauseGuard::Concatenate { left, right, .. } => self
+                .clause_guard(left)
+                .append(" <> ")
+                .append(self.clause_guard(right)),
+
             ClauseGuard::Var { name, .. } => name.to_doc(),
 
             ClauseGuard::TupleIndex { tuple, index, .. } => {
@@ -1397,6 +1469,7 @@ impl<'a> Documentable<'a> for &'a BinOp {
             BinOp::DivInt => " / ",
             BinOp::DivFloat => " /. ",
             BinOp::ModuloInt => " % ",
+            BinOp::Concatenate => " <> ",
         }
         .to_doc()
     }
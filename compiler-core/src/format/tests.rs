@@ -141,6 +141,30 @@ external fn x(a, b, c) -> #(a, b, c) =
     );
 }
 
+#[test]
+fn external_fn_targets() {
+    assert_format!(
+        r#"external fn now() -> Int =
+  erlang "os" "system_time"
+"#
+    );
+
+    assert_format!(
+        r#"pub external fn now() -> Int =
+  erlang "os" "system_time"
+  javascript "./time.mjs" "now"
+"#
+    );
+
+    assert_format_rewrite!(
+        r#"external fn now() -> Int = erlang "os" "system_time" javascript "./time.mjs" "now""#,
+        r#"external fn now() -> Int =
+  erlang "os" "system_time"
+  javascript "./time.mjs" "now"
+"#
+    );
+}
+
 #[test]
 fn type_alias() {
     assert_format!(
@@ -1029,6 +1053,93 @@ fn binary_operators() {
         r#"fn main() {
   1 % 1
 }
+"#
+    );
+
+    assert_format!(
+        r#"fn main() {
+  "a" <> "b"
+}
+"#
+    );
+}
+
+#[test]
+fn unary_operators() {
+    assert_format!(
+        r#"fn main() {
+  !True
+}
+"#
+    );
+
+    assert_format!(
+        r#"fn main() {
+  !x && !{ y || z }
+}
+"#
+    );
+
+    assert_format!(
+        r#"fn main() {
+  -x * -{ y + 1 }
+}
+"#
+    );
+
+    assert_format!(
+        r#"fn main() {
+  --1
+}
+"#
+    );
+
+    assert_format!(
+        r#"fn main() {
+  case x {
+    _ if !x || !{ y && z } -> -y
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn deprecated_attribute() {
+    assert_format!(
+        r#"@deprecated("use new instead")
+pub fn old() {
+  1
+}
+"#
+    );
+
+    assert_format!(
+        r#"/// The old way
+@deprecated("use New instead")
+pub type Old {
+  Old
+}
+
+@deprecated("use New instead")
+pub type OldAlias =
+  Old
+
+@deprecated("use new instead")
+pub external fn old_external() -> Int =
+  "a" "b"
+
+@deprecated("use new instead")
+pub const old = 1
+"#
+    );
+
+    assert_format_rewrite!(
+        r#"@deprecated("use new instead")   pub fn old() { 1 }"#,
+        r#"@deprecated("use new instead")
+pub fn old() {
+  1
+}
 "#
     );
 }
@@ -1992,6 +2103,15 @@ pub fn main() {
     _ if x != y && x == z -> Nil
   }
 }
+"#
+    );
+
+    assert_format!(
+        r#"fn main() {
+  case 1 {
+    _ if x == y <> "!" -> Nil
+  }
+}
 "#
     );
 }
@@ -2870,6 +2990,22 @@ fn main() {
     );
 }
 
+#[test]
+fn label_shorthand() {
+    assert_format!(
+        "pub type Person {
+  Person(name: String, age: Int)
+}
+
+fn main(name, age) {
+  let person = Person(name:, age:)
+  let person = Person(..person, name:)
+  Person(age: age, name:)
+}
+"
+    );
+}
+
 #[test]
 fn concise_wrapping_of_simple_lists() {
     assert_format!(
@@ -3142,6 +3278,17 @@ fn tuple_constant() {
     );
 }
 
+#[test]
+fn assert_with_message() {
+    assert_format!(
+        r#"pub fn main() {
+  assert Ok(x) = run() as "run should succeed"
+  x
+}
+"#
+    );
+}
+
 #[test]
 fn let_as_expression() {
     assert_format!(
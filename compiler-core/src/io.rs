@@ -54,6 +54,8 @@ pub trait FileSystemReader {
     fn read<P>(&self, path: P) -> Result<String, Error>
     where
         P: AsRef<Path> + Debug;
+
+    fn is_file(&self, path: &Path) -> bool;
 }
 
 pub trait FileSystemIO: FileSystemWriter + FileSystemReader {}
@@ -63,6 +65,9 @@ pub trait FileSystemIO: FileSystemWriter + FileSystemReader {}
 /// but in tests and in other places other implementations may be used.
 pub trait FileSystemWriter {
     fn open(&self, path: &Path) -> Result<WrappedWriter, Error>;
+
+    /// Delete a file, doing nothing if it does not exist.
+    fn delete(&self, path: &Path) -> Result<(), Error>;
 }
 
 // TODO: Remove this when the Rust compiler stops incorrectly suggesting this
@@ -124,10 +129,11 @@ impl<'a> std::fmt::Write for WrappedWriter {
 pub mod test {
     use super::*;
     use std::{
-        cell::RefCell,
         io::Write,
-        rc::Rc,
-        sync::mpsc::{self, Receiver, Sender},
+        sync::{
+            mpsc::{self, Receiver, Sender},
+            Arc, Mutex,
+        },
     };
 
     #[derive(Debug, Clone)]
@@ -144,6 +150,8 @@ pub mod test {
         ) -> Result<Vec<OutputFile>, ()> {
             receiver
                 .try_iter()
+                // The build manifest is tested separately
+                .filter(|(path, _)| !path.ends_with(crate::build::manifest::FILE_NAME))
                 .map(|(path, file)| {
                     Ok(OutputFile {
                         path,
@@ -160,6 +168,10 @@ pub mod test {
             let _ = self.0.send((path.to_path_buf(), file.clone()));
             Ok(WrappedWriter::new(path, Box::new(file)))
         }
+
+        fn delete(&self, _path: &Path) -> Result<(), Error> {
+            Ok(())
+        }
     }
 
     impl FileSystemReader for FilesChannel {
@@ -173,13 +185,17 @@ pub mod test {
         {
             unimplemented!()
         }
+
+        fn is_file(&self, _path: &Path) -> bool {
+            false
+        }
     }
 
     impl FileSystemIO for FilesChannel {}
 
     #[derive(Debug, Default, Clone)]
     pub struct InMemoryFile {
-        contents: Rc<RefCell<Vec<u8>>>,
+        contents: Arc<Mutex<Vec<u8>>>,
     }
 
     impl InMemoryFile {
@@ -188,26 +204,33 @@ pub mod test {
         }
 
         pub fn into_contents(self) -> Result<Vec<u8>, ()> {
-            Rc::try_unwrap(self.contents)
+            Arc::try_unwrap(self.contents)
                 .map_err(|_| ())
-                .map(RefCell::into_inner)
+                .and_then(|contents| contents.into_inner().map_err(|_| ()))
         }
     }
 
     impl Write for InMemoryFile {
         fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-            self.contents.borrow_mut().write(buf)
+            self.contents
+                .lock()
+                .expect("InMemoryFile lock poisoned")
+                .write(buf)
         }
 
         fn flush(&mut self) -> std::io::Result<()> {
-            self.contents.borrow_mut().flush()
+            self.contents
+                .lock()
+                .expect("InMemoryFile lock poisoned")
+                .flush()
         }
     }
 
     impl std::fmt::Write for InMemoryFile {
         fn write_str(&mut self, s: &str) -> std::fmt::Result {
             self.contents
-                .borrow_mut()
+                .lock()
+                .expect("InMemoryFile lock poisoned")
                 .write(s.as_bytes())
                 .map(|_| ())
                 .map_err(|_| std::fmt::Error)
@@ -1,5 +1,8 @@
 use super::*;
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
 
 // An in memory sharable collection of pretend files that can be used in place
 // of a real file system. It is a shared reference to a set of buffer than can
@@ -9,37 +12,54 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 // Useful in tests and in environments like the browser where there is no file
 // system.
 //
-// Not thread safe. The compiler is single threaded, so that's OK.
-//
 // Only supports absolute paths. For now. In future we could have a explicit
 // current directory, or say that the current directory is always the root.
 //
-#[derive(Clone, Default, Debug, PartialEq)]
+#[derive(Clone, Default, Debug)]
 pub struct InMemoryFileSystem {
-    files: Rc<RefCell<HashMap<PathBuf, InMemoryFile>>>,
+    files: Arc<Mutex<HashMap<PathBuf, InMemoryFile>>>,
 }
 
 impl InMemoryFileSystem {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// The paths of all the files, in sorted order.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<_> = self.files().keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    fn files(&self) -> MutexGuard<'_, HashMap<PathBuf, InMemoryFile>> {
+        lock(&self.files)
+    }
 }
 
 impl FileSystemWriter for InMemoryFileSystem {
     fn open(&self, path: &Path) -> Result<WrappedWriter, Error> {
-        let mut files = (*self.files).borrow_mut();
-        let writer = files.entry(path.to_path_buf()).or_default();
+        // Opening a file replaces any existing contents, as with a real file
+        let writer = InMemoryFile::default();
+        let _ = self.files().insert(path.to_path_buf(), writer.clone());
         Ok(WrappedWriter {
             path: path.to_path_buf(),
-            inner: Box::new(writer.clone()),
+            inner: Box::new(writer),
         })
     }
+
+    fn delete(&self, path: &Path) -> Result<(), Error> {
+        let _ = self.files().remove(path);
+        Ok(())
+    }
 }
 
+impl FileSystemIO for InMemoryFileSystem {}
+
 impl FileSystemReader for InMemoryFileSystem {
     fn gleam_files(&self, dir: &Path) -> Box<dyn Iterator<Item = PathBuf>> {
-        let files: Vec<PathBuf> = (*self.files)
-            .borrow()
+        let files: Vec<PathBuf> = self
+            .files()
             .iter()
             .map(|(file_path, _)| file_path.to_path_buf())
             .filter(|file_path| file_path.starts_with(dir))
@@ -52,14 +72,14 @@ impl FileSystemReader for InMemoryFileSystem {
         P: AsRef<Path> + Debug,
     {
         let path = path.as_ref().to_path_buf();
-        let files = (*self.files).borrow();
+        let files = self.files();
         let file = files.get(&path).ok_or_else(|| Error::FileIo {
             kind: FileKind::File,
             action: FileIoAction::Open,
             path: path.clone(),
             err: None,
         })?;
-        let bytes = file.buffer.borrow();
+        let bytes = lock(&file.buffer);
         let unicode = String::from_utf8(bytes.clone()).map_err(|err| Error::FileIo {
             kind: FileKind::File,
             action: FileIoAction::Read,
@@ -68,6 +88,10 @@ impl FileSystemReader for InMemoryFileSystem {
         })?;
         Ok(unicode)
     }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files().contains_key(path)
+    }
 }
 
 // An in memory sharable that can be used in place of a real file. It is a
@@ -77,21 +101,23 @@ impl FileSystemReader for InMemoryFileSystem {
 // Useful in tests and in environments like the browser where there is no file
 // system.
 //
-// Not thread safe. The compiler is single threaded, so that's OK.
-//
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct InMemoryFile {
-    buffer: Rc<RefCell<Vec<u8>>>,
+    buffer: Arc<Mutex<Vec<u8>>>,
 }
 
 impl std::io::Write for InMemoryFile {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let mut reference = (*self.buffer).borrow_mut();
+        let mut reference = lock(&self.buffer);
         reference.write(buf)
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        let mut reference = (*self.buffer).borrow_mut();
+        let mut reference = lock(&self.buffer);
         reference.flush()
     }
 }
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -5,7 +5,7 @@ mod tests;
 
 use std::path::Path;
 
-use crate::{ast::*, docvec, io::Utf8Writer, line_numbers::LineNumbers, pretty::*};
+use crate::{ast::*, build::Target, docvec, io::Utf8Writer, line_numbers::LineNumbers, pretty::*};
 use heck::CamelCase;
 use itertools::Itertools;
 
@@ -121,12 +121,18 @@ impl<'a> Generator<'a> {
                 public,
                 name,
                 arguments,
-                module,
-                fun,
+                implementations,
                 ..
-            } => vec![Ok(
-                self.external_function(*public, name, arguments, module, fun)
-            )],
+            } => match external_fn_implementation(implementations, Target::JavaScript) {
+                Some(implementation) => vec![Ok(self.external_function(
+                    *public,
+                    name,
+                    arguments,
+                    &implementation.module,
+                    &implementation.fun,
+                ))],
+                None => vec![],
+            },
         }
     }
 
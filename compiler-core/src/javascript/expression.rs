@@ -123,7 +123,13 @@ impl<'module> Generator<'module> {
 
             TypedExpr::Sequence { expressions, .. } => self.sequence(expressions),
 
-            TypedExpr::Assignment { value, pattern, .. } => self.assignment(value, pattern),
+            TypedExpr::Assignment {
+                value,
+                pattern,
+                kind,
+                message,
+                ..
+            } => self.assignment(value, pattern, *kind, message),
 
             TypedExpr::Try {
                 value,
@@ -136,6 +142,10 @@ impl<'module> Generator<'module> {
                 name, left, right, ..
             } => self.bin_op(name, left, right),
 
+            TypedExpr::NegateBool { value, .. } => self.negate_with("!", value),
+
+            TypedExpr::NegateInt { value, .. } => self.negate_with("-", value),
+
             TypedExpr::Todo {
                 label, location, ..
             } => Ok(self.todo(label, location)),
@@ -354,7 +364,7 @@ impl<'module> Generator<'module> {
                 pattern_generator.traverse_pattern(&subject, pattern)?;
                 let compiled = pattern_generator.take_compiled();
                 docs.push(line());
-                docs.push(compiled.into_assignment_doc());
+                docs.push(compiled.into_assignment_doc(None));
                 docs.push(lines(2));
             }
         }
@@ -365,7 +375,13 @@ impl<'module> Generator<'module> {
         Ok(docs.to_doc())
     }
 
-    fn assignment<'a>(&mut self, value: &'a TypedExpr, pattern: &'a TypedPattern) -> Output<'a> {
+    fn assignment<'a>(
+        &mut self,
+        value: &'a TypedExpr,
+        pattern: &'a TypedPattern,
+        kind: AssignmentKind,
+        message: &'a Option<String>,
+    ) -> Output<'a> {
         // If it is a simple assignment to a variable we can generate a normal
         // JS assignment
         if let TypedPattern::Var { name, .. } = pattern {
@@ -383,6 +399,22 @@ impl<'module> Generator<'module> {
 
         let value = self.not_in_tail_position(|gen| gen.wrap_expression(value))?;
 
+        // A failed assert throws an error with details of where and why it
+        // failed, the same as the Erlang backend.
+        let failure = match kind {
+            AssignmentKind::Let => None,
+            AssignmentKind::Assert => Some(
+                self.throw_error(
+                    "assert",
+                    message
+                        .as_deref()
+                        .unwrap_or("Assertion pattern match failed"),
+                    pattern.location(),
+                    vec![("value", subject.clone())],
+                ),
+            ),
+        };
+
         // If we are in tail position we can return value being assigned
         let afterwards = if self.tail_position {
             line()
@@ -397,10 +429,10 @@ impl<'module> Generator<'module> {
         // use in patterns
         let doc = match subject_assignment {
             Some(name) => {
-                let compiled = compiled.into_assignment_doc();
+                let compiled = compiled.into_assignment_doc(failure);
                 docvec!("let ", name, " = ", value, ";", line(), compiled)
             }
-            None => compiled.into_assignment_doc(),
+            None => compiled.into_assignment_doc(failure),
         };
 
         Ok(docvec!(force_break(), doc.append(afterwards)))
@@ -694,7 +726,9 @@ impl<'module> Generator<'module> {
             BinOp::NotEq => self.equal(left, right, false),
             BinOp::GtInt | BinOp::GtFloat => self.print_bin_op(left, right, ">"),
             BinOp::GtEqInt | BinOp::GtEqFloat => self.print_bin_op(left, right, ">="),
-            BinOp::AddInt | BinOp::AddFloat => self.print_bin_op(left, right, "+"),
+            BinOp::AddInt | BinOp::AddFloat | BinOp::Concatenate => {
+                self.print_bin_op(left, right, "+")
+            }
             BinOp::SubInt | BinOp::SubFloat => self.print_bin_op(left, right, "-"),
             BinOp::MultInt => self.mult_int(left, right),
             BinOp::MultFloat => self.print_bin_op(left, right, "*"),
@@ -768,53 +802,80 @@ impl<'module> Generator<'module> {
         Ok(docvec!(left, " ", op, " ", right))
     }
 
+    fn negate_with<'a>(&mut self, op: &'static str, value: &'a TypedExpr) -> Output<'a> {
+        self.not_in_tail_position(|gen| {
+            let value = match value {
+                // Parentheses are required to avoid the operator being taken as `--`
+                TypedExpr::NegateInt { .. } => docvec!("(", gen.expression(value)?, ")"),
+                TypedExpr::Int { value: int, .. } if int.starts_with('-') => {
+                    docvec!("(", gen.expression(value)?, ")")
+                }
+                _ => gen.binop_child_expression(value)?,
+            };
+            Ok(docvec!(op, value))
+        })
+    }
+
     fn todo<'a>(&mut self, message: &'a Option<String>, location: &'a SrcSpan) -> Document<'a> {
         let tail_position = self.tail_position;
         self.tail_position = false;
-        let gleam_error = "todo";
         let message = message
             .as_ref()
             .map(|s| s.as_str())
             .unwrap_or_else(|| "This has not yet been implemented");
+        let doc = self.throw_error("todo", message, *location, vec![]);
+
+        // Reset tail position so later values are returned as needed. i.e.
+        // following clauses in a case expression.
+        self.tail_position = tail_position;
+
+        doc
+    }
+
+    /// Throws an error with the same details as the error raised by the
+    /// Erlang backend attached.
+    fn throw_error<'a>(
+        &self,
+        gleam_error: &'a str,
+        message: &'a str,
+        location: SrcSpan,
+        fields: Vec<(&'a str, Document<'a>)>,
+    ) -> Document<'a> {
         let module_name = Document::String(self.module_name.join("/"));
         let line = self.line_numbers.line_number(location.start);
+        let fields = vec![
+            ("gleam_error".to_doc(), Some(string(gleam_error))),
+            ("module".to_doc(), Some(module_name.surround("\"", "\""))),
+            (
+                "function".to_doc(),
+                Some(
+                    // TODO switch to use `string(self.function_name)`
+                    // This will require resolving the
+                    // difference in lifetimes 'module and 'a.
+                    Document::String(self.function_name.unwrap_or_default().to_string())
+                        .surround("\"", "\""),
+                ),
+            ),
+            ("line".to_doc(), Some(line.to_doc())),
+        ]
+        .into_iter()
+        .chain(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_doc(), Some(value))),
+        );
 
-        let doc = docvec![
+        docvec![
             "throw Object.assign",
             wrap_args(
                 vec![
                     docvec!["new Error", wrap_args(std::iter::once(string(message)))],
-                    wrap_object(
-                        vec![
-                            ("gleam_error".to_doc(), Some(string(gleam_error))),
-                            ("module".to_doc(), Some(module_name.surround("\"", "\""))),
-                            (
-                                "function".to_doc(),
-                                Some(
-                                    // TODO switch to use `string(self.function_name)`
-                                    // This will require resolving the
-                                    // difference in lifetimes 'module and 'a.
-                                    Document::String(
-                                        self.function_name.unwrap_or_default().to_string()
-                                    )
-                                    .surround("\"", "\"")
-                                )
-                            ),
-                            ("line".to_doc(), Some(line.to_doc())),
-                        ]
-                        .into_iter()
-                    )
+                    wrap_object(fields)
                 ]
                 .into_iter()
             ),
             ";"
-        ];
-
-        // Reset tail position so later values are returned as needed. i.e.
-        // following clauses in a case expression.
-        self.tail_position = tail_position;
-
-        doc
+        ]
     }
 
     fn module_select<'a>(
@@ -1012,7 +1073,8 @@ impl BinOp {
             | BinOp::MultFloat
             | BinOp::DivInt
             | BinOp::DivFloat
-            | BinOp::ModuloInt => true,
+            | BinOp::ModuloInt
+            | BinOp::Concatenate => true,
             BinOp::MultInt => false,
         }
     }
@@ -121,6 +121,9 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
             | ClauseGuard::GtEqFloat { .. }
             | ClauseGuard::LtFloat { .. }
             | ClauseGuard::LtEqFloat { .. }
+            | ClauseGuard::Concatenate { .. }
+            | ClauseGuard::NegateBool { .. }
+            | ClauseGuard::NegateInt { .. }
             | ClauseGuard::Or { .. }
             | ClauseGuard::And { .. } => Ok(docvec!("(", self.guard(guard)?, ")")),
         }
@@ -180,6 +183,21 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                 docvec!(left, " <= ", right)
             }
 
+            ClauseGuard::NegateBool { value, .. } => docvec!("!", self.wrapped_guard(value)?),
+
+            ClauseGuard::NegateInt { value, .. } => match value.as_ref() {
+                ClauseGuard::Constant(Constant::Int { value: int, .. }) if int.starts_with('-') => {
+                    docvec!("-(", self.guard(value)?, ")")
+                }
+                _ => docvec!("-", self.wrapped_guard(value)?),
+            },
+
+            ClauseGuard::Concatenate { left, right, .. } => {
+                let left = self.wrapped_guard(left)?;
+                let right = self.wrapped_guard(right)?;
+                docvec!(left, " + ", right)
+            }
+
             ClauseGuard::Or { left, right, .. } => {
                 let left = self.wrapped_guard(left)?;
                 let right = self.wrapped_guard(right)?;
@@ -388,16 +406,19 @@ pub struct CompiledPattern<'a> {
 }
 
 impl<'a> CompiledPattern<'a> {
-    pub fn into_assignment_doc(self) -> Document<'a> {
+    /// Renders the checks and assignments of the pattern, running the
+    /// `failure` statement if the checks do not pass. A generic error is thrown
+    /// if no statement is given.
+    pub fn into_assignment_doc(self, failure: Option<Document<'a>>) -> Document<'a> {
         if self.checks.is_empty() {
             return Self::assignments_doc(self.assignments);
         }
         if self.assignments.is_empty() {
-            return Self::checks_or_throw_doc(self.checks);
+            return Self::checks_or_throw_doc(self.checks, failure);
         }
 
         docvec![
-            Self::checks_or_throw_doc(self.checks),
+            Self::checks_or_throw_doc(self.checks, failure),
             line(),
             Self::assignments_doc(self.assignments)
         ]
@@ -448,15 +469,33 @@ impl<'a> CompiledPattern<'a> {
         ))
     }
 
-    pub fn checks_or_throw_doc(checks: Vec<Check<'a>>) -> Document<'a> {
+    pub fn checks_or_throw_doc(
+        checks: Vec<Check<'a>>,
+        failure: Option<Document<'a>>,
+    ) -> Document<'a> {
         let checks = Self::checks_doc(checks, false);
-        docvec![
-            "if (",
-            docvec![break_("", ""), checks].nest(INDENT),
-            break_("", ""),
-            ") throw new Error(\"Bad match\");",
-        ]
-        .group()
+        match failure {
+            None => docvec![
+                "if (",
+                docvec![break_("", ""), checks].nest(INDENT),
+                break_("", ""),
+                ") throw new Error(\"Bad match\");",
+            ]
+            .group(),
+
+            Some(failure) => docvec![
+                docvec![
+                    "if (",
+                    docvec![break_("", ""), checks].nest(INDENT),
+                    break_("", ""),
+                    ") {"
+                ]
+                .group(),
+                docvec![line(), failure].nest(INDENT),
+                line(),
+                "}"
+            ],
+        }
     }
 }
 
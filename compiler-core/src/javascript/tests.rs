@@ -23,7 +23,7 @@ macro_rules! assert_js {
         use crate::javascript::*;
         use std::path::Path;
         let mut modules = std::collections::HashMap::new();
-        let mut uid = 0;
+        let mut uid = crate::type_::UidGenerator::default();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
         // to have one place where we create all this required state for use in each
@@ -34,7 +34,7 @@ macro_rules! assert_js {
         ast.name = vec!["my".to_string(), "mod".to_string()];
         let ast = crate::type_::infer_module(
             crate::build::Target::JavaScript,
-            &mut 0,
+            &mut crate::type_::UidGenerator::default(),
             ast,
             crate::build::Origin::Src,
             "thepackage",
@@ -52,7 +52,7 @@ macro_rules! assert_js {
         use crate::javascript::*;
         use std::path::Path;
         let mut modules = std::collections::HashMap::new();
-        let mut uid = 0;
+        let mut uid = crate::type_::UidGenerator::default();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
         // to have one place where we create all this required state for use in each
@@ -62,7 +62,7 @@ macro_rules! assert_js {
         ast.name = $dep_name;
         let dep = crate::type_::infer_module(
             crate::build::Target::JavaScript,
-            &mut 0,
+            &mut crate::type_::UidGenerator::default(),
             ast,
             crate::build::Origin::Src,
             $dep_package,
@@ -75,7 +75,7 @@ macro_rules! assert_js {
         ast.name = vec!["my".to_string(), "mod".to_string()];
         let ast = crate::type_::infer_module(
             crate::build::Target::JavaScript,
-            &mut 0,
+            &mut crate::type_::UidGenerator::default(),
             ast,
             crate::build::Origin::Src,
             CURRENT_PACKAGE,
@@ -5,13 +5,24 @@ fn tuple_matching() {
     assert_js!(
         r#"
 fn go(x) {
-  let #(1, 2) = x
+  assert #(1, 2) = x
 }
 "#,
         r#""use strict";
 
 function go(x) {
-  if (x[0] !== 1 || x[1] !== 2) throw new Error("Bad match");
+  if (x[0] !== 1 || x[1] !== 2) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 3,
+        value: x
+      }
+    );
+  }
   return x;
 }
 "#
@@ -25,7 +36,18 @@ fn assert() {
         r#""use strict";
 
 function go(x) {
-  if (x !== 1) throw new Error("Bad match");
+  if (x !== 1) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 1,
+        value: x
+      }
+    );
+  }
   return x;
 }
 "#
@@ -36,25 +58,73 @@ function go(x) {
         r#""use strict";
 
 function go(x) {
-  if (x[0] !== 1 || x[1] !== 2) throw new Error("Bad match");
+  if (x[0] !== 1 || x[1] !== 2) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 1,
+        value: x
+      }
+    );
+  }
   return x;
 }
 "#
     );
 }
 
+#[test]
+fn assert_with_message() {
+    assert_js!(
+        r#"fn go(x) { assert Ok(y) = x as "x should be ok" y }"#,
+        r#""use strict";
+
+function go(x) {
+  if (x.type !== "Ok") {
+    throw Object.assign(
+      new Error("x should be ok"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 1,
+        value: x
+      }
+    );
+  }
+  let y = x[0];
+  return y;
+}
+"#
+    );
+}
+
 #[test]
 fn nested_binding() {
     assert_js!(
         r#"
 fn go(x) {
-  let #(a, #(b, c, 2) as t, _, 1) = x
+  assert #(a, #(b, c, 2) as t, _, 1) = x
 }
 "#,
         r#""use strict";
 
 function go(x) {
-  if (x[1][2] !== 2 || x[3] !== 1) throw new Error("Bad match");
+  if (x[1][2] !== 2 || x[3] !== 1) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 3,
+        value: x
+      }
+    );
+  }
   let a = x[0];
   let t = x[1];
   let b = x[1][0];
@@ -75,7 +145,7 @@ fn go(x, foo) {
   foo(a)
   let a = 2
   foo(a)
-  let #(a, 3) = x
+  assert #(a, 3) = x
   let b = a
   foo(b)
   let c = {
@@ -95,7 +165,18 @@ function go(x, foo) {
   foo(a);
   let a$1 = 2;
   foo(a$1);
-  if (x[1] !== 3) throw new Error("Bad match");
+  if (x[1] !== 3) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 8,
+        value: x
+      }
+    );
+  }
   let a$2 = x[0];
   let b = a$2;
   foo(b);
@@ -154,7 +235,18 @@ fn returning_literal_subject() {
 
 function go(x) {
   let $ = x + 1;
-  if ($ !== 1) throw new Error("Bad match");
+  if ($ !== 1) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 1,
+        value: $
+      }
+    );
+  }
   return $;
 }
 "#
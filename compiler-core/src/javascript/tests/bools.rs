@@ -72,9 +72,42 @@ fn go(x, y) {
         r#""use strict";
 
 function go(x, y) {
-  if (!x) throw new Error("Bad match");
-  if (x) throw new Error("Bad match");
-  if (y) throw new Error("Bad match");
+  if (!x) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 3,
+        value: x
+      }
+    );
+  }
+  if (x) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 4,
+        value: x
+      }
+    );
+  }
+  if (y) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 5,
+        value: y
+      }
+    );
+  }
   return y;
 }
 "#
@@ -89,17 +122,50 @@ fn shadowed_bools_and_nil() {
         r#"
 pub type True { True False Nil }
 fn go(x, y) {
-  let True = x
-  let False = x
-  let Nil = y
+  assert True = x
+  assert False = x
+  assert Nil = y
 }
 "#,
         r#""use strict";
 
 function go(x, y) {
-  if (x.type !== "True") throw new Error("Bad match");
-  if (x.type !== "False") throw new Error("Bad match");
-  if (y.type !== "Nil") throw new Error("Bad match");
+  if (x.type !== "True") {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 4,
+        value: x
+      }
+    );
+  }
+  if (x.type !== "False") {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 5,
+        value: x
+      }
+    );
+  }
+  if (y.type !== "Nil") {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 6,
+        value: y
+      }
+    );
+  }
   return y;
 }
 "#
@@ -187,3 +253,19 @@ function go(a) {
 "#
     );
 }
+
+#[test]
+fn negation() {
+    assert_js!(
+        r#"fn go(a) {
+  !a && !{ a || False }
+}
+"#,
+        r#""use strict";
+
+function go(a) {
+  return !a && !(a || false);
+}
+"#
+    );
+}
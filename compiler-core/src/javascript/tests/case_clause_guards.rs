@@ -408,3 +408,49 @@ export function main(xs) {
 "#
     );
 }
+
+#[test]
+fn string_concatenation_guard() {
+    assert_js!(
+        r#"pub fn main(x, y) {
+  case x {
+    _ if x == y <> "!" -> 1
+    _ -> 0
+  }
+}
+"#,
+        r#""use strict";
+
+export function main(x, y) {
+  if (x === (y + "!")) {
+    return 1;
+  } else {
+    return 0;
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn negation_guard() {
+    assert_js!(
+        r#"pub fn main(x, y) {
+  case x {
+    _ if !x && -y > 0 -> 1
+    _ -> 0
+  }
+}
+"#,
+        r#""use strict";
+
+export function main(x, y) {
+  if ((!x) && ((-y) > 0)) {
+    return 1;
+  } else {
+    return 0;
+  }
+}
+"#
+    );
+}
@@ -334,7 +334,7 @@ type Cat {
 fn go(cat) {
   let Cat(x, y) = cat
   let Cat(name: x, ..) = cat
-  let Cat(cuteness: 4, name: x) = cat
+  assert Cat(cuteness: 4, name: x) = cat
   x
 }
 
@@ -347,7 +347,18 @@ function go(cat) {
   let y = cat.cuteness;
   if (cat.type !== "Cat") throw new Error("Bad match");
   let x$1 = cat.name;
-  if (cat.type !== "Cat" || cat.cuteness !== 4) throw new Error("Bad match");
+  if (cat.type !== "Cat" || cat.cuteness !== 4) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 9,
+        value: cat
+      }
+    );
+  }
   let x$2 = cat.name;
   return x$2;
 }
@@ -810,3 +821,18 @@ export function main(x) {
 "#
     );
 }
+
+#[test]
+fn shared_field_access() {
+    assert_js!(
+        r#"pub type Shape { Circle(name: String, radius: Float) Square(name: String) }
+pub fn get_name(shape: Shape) { shape.name }
+"#,
+        r#""use strict";
+
+export function get_name(shape) {
+  return shape.name;
+}
+"#
+    );
+}
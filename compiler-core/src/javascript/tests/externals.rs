@@ -70,3 +70,56 @@ export function fetch(arg0) {
 "#
     );
 }
+
+#[test]
+fn target_implementations() {
+    assert_js!(
+        r#"pub external fn now() -> Int =
+  erlang "os" "system_time"
+  javascript "./time.mjs" "now""#,
+        r#""use strict";
+
+import { now } from "./time.mjs";
+export { now };
+"#
+    );
+}
+
+#[test]
+fn no_javascript_implementation() {
+    assert_js!(
+        r#"pub external fn now() -> Int = erlang "os" "system_time""#,
+        r#""use strict";
+"#
+    );
+}
+
+#[test]
+fn private_fn_without_javascript_implementation() {
+    assert_js!(
+        r#"external fn now() -> Int = erlang "os" "system_time"
+fn later() { now() + 1 }
+pub fn main() { 1 }"#,
+        r#""use strict";
+
+export function main() {
+  return 1;
+}
+"#
+    );
+}
+
+#[test]
+fn public_fn_without_javascript_implementation() {
+    assert_js!(
+        r#"external fn now() -> Int = erlang "os" "system_time"
+pub fn later() { now() + 1 }
+pub fn main() { 1 }"#,
+        r#""use strict";
+
+export function main() {
+  return 1;
+}
+"#
+    );
+}
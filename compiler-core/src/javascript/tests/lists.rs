@@ -90,30 +90,78 @@ fn list_destructuring() {
     assert_js!(
         r#"
 fn go(x, y) {
-  let [] = x
-  let [a] = x
-  let [1, 2] = x
-  let [_, #(3, b)] = y
-  let [head, ..tail] = y
+  assert [] = x
+  assert [a] = x
+  assert [1, 2] = x
+  assert [_, #(3, b)] = y
+  assert [head, ..tail] = y
 }
 "#,
         r#""use strict";
 
 function go(x, y) {
-  if (x?.length !== 0) throw new Error("Bad match");
-  if (x?.[1]?.length !== 0) throw new Error("Bad match");
+  if (x?.length !== 0) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 3,
+        value: x
+      }
+    );
+  }
+  if (x?.[1]?.length !== 0) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 4,
+        value: x
+      }
+    );
+  }
   let a = x[0];
-  if (
-    x?.[1]?.[1]?.length !== 0 ||
-    x[0] !== 1 ||
-    x[1][0] !== 2
-  ) throw new Error("Bad match");
-  if (
-    y?.[1]?.[1]?.length !== 0 ||
-    y[1][0][0] !== 3
-  ) throw new Error("Bad match");
+  if (x?.[1]?.[1]?.length !== 0 || x[0] !== 1 || x[1][0] !== 2) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 5,
+        value: x
+      }
+    );
+  }
+  if (y?.[1]?.[1]?.length !== 0 || y[1][0][0] !== 3) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 6,
+        value: y
+      }
+    );
+  }
   let b = y[1][0][1];
-  if (y?.[1]?.length === undefined) throw new Error("Bad match");
+  if (y?.[1]?.length === undefined) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 7,
+        value: y
+      }
+    );
+  }
   let head = y[0];
   let tail = y[1];
   return y;
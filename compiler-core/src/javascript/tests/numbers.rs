@@ -149,13 +149,24 @@ fn int_patterns() {
     assert_js!(
         r#"
 fn go(x) {
-  let 4 = x
+  assert 4 = x
 }
 "#,
         r#""use strict";
 
 function go(x) {
-  if (x !== 4) throw new Error("Bad match");
+  if (x !== 4) {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 3,
+        value: x
+      }
+    );
+  }
   return x;
 }
 "#
@@ -248,3 +259,23 @@ function go() {
 "#
     )
 }
+
+#[test]
+fn negation() {
+    assert_js!(
+        r#"fn go(a) {
+  let b = -a * 2
+  let c = - -1
+  b + -{ c - 1 }
+}
+"#,
+        r#""use strict";
+
+function go(a) {
+  let b = Math.imul(-a, 2);
+  let c = -(-1);
+  return b + -(c - 1);
+}
+"#
+    );
+}
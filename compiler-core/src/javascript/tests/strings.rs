@@ -22,13 +22,24 @@ fn string_patterns() {
     assert_js!(
         r#"
 fn go(x) {
-  let "Hello" = x
+  assert "Hello" = x
 }
 "#,
         r#""use strict";
 
 function go(x) {
-  if (x !== "Hello") throw new Error("Bad match");
+  if (x !== "Hello") {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "go",
+        line: 3,
+        value: x
+      }
+    );
+  }
   return x;
 }
 "#
@@ -85,3 +96,20 @@ function go(a) {
 "#
     );
 }
+
+#[test]
+fn concatenation() {
+    assert_js!(
+        r#"
+fn go(x) {
+  "Hello, " <> x <> "!"
+}
+"#,
+        r#""use strict";
+
+function go(x) {
+  return ("Hello, " + x) + "!";
+}
+"#
+    );
+}
@@ -139,7 +139,18 @@ export function main(x) {
 
     return { type: "Ok", 0: z + 1 };
   })();
-  if ($.type !== "Ok") throw new Error("Bad match");
+  if ($.type !== "Ok") {
+    throw Object.assign(
+      new Error("Assertion pattern match failed"),
+      {
+        gleam_error: "assert",
+        module: "my/mod",
+        function: "main",
+        line: 2,
+        value: $
+      }
+    );
+  }
   let y = $[0];
   return y;
 }
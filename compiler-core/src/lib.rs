@@ -57,6 +57,7 @@ pub mod codegen;
 pub mod config;
 pub mod diagnostic;
 pub mod docs;
+pub mod doctest;
 pub mod erl;
 pub mod error;
 pub mod format;
@@ -64,9 +65,11 @@ pub mod io;
 pub mod javascript;
 pub mod line_numbers;
 pub mod metadata;
+pub mod package_interface;
 pub mod parse;
 pub mod pretty;
 pub mod project;
+pub mod semver;
 pub mod type_;
 pub mod warning;
 
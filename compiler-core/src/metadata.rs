@@ -1,10 +1,15 @@
 //! Seriaisation and deserialisation of Gleam compiler metadata into binary files
 //! using the Cap'n Proto schema.
 
+mod header;
 mod module_decoder;
 mod module_encoder;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::{module_decoder::ModuleDecoder, module_encoder::ModuleEncoder};
+pub use self::{
+    header::{COMPILER_VERSION, SCHEMA_VERSION},
+    module_decoder::ModuleDecoder,
+    module_encoder::ModuleEncoder,
+};
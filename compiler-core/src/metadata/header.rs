@@ -0,0 +1,105 @@
+//! The header written before the Cap'n Proto message in a `.gleam_module`
+//! file, used to reject metadata written by a different version of the
+//! compiler rather than decoding it into garbage.
+//!
+//! The layout is:
+//!
+//! - The magic bytes `GLEAMMOD`.
+//! - The schema version as a little endian `u32`.
+//! - The length of the compiler version as a `u8`, followed by the compiler
+//!   version in UTF-8.
+//! - The FNV-1a hash of the message as a little endian `u64`.
+
+use crate::{error::MetadataProblem, Error, Result};
+use std::convert::TryInto;
+
+const MAGIC: &[u8] = b"GLEAMMOD";
+
+/// Increment this whenever the Cap'n Proto schema changes in a way that
+/// changes the meaning of previously written metadata.
+pub const SCHEMA_VERSION: u32 = 2;
+
+pub const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub fn with_header(message: &[u8]) -> Vec<u8> {
+    let version = COMPILER_VERSION.as_bytes();
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 13 + version.len() + message.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+    bytes.push(version.len() as u8);
+    bytes.extend_from_slice(version);
+    bytes.extend_from_slice(&hash(message).to_le_bytes());
+    bytes.extend_from_slice(message);
+    bytes
+}
+
+/// Check the header and return the message that follows it.
+pub fn without_header(bytes: &[u8]) -> Result<&[u8]> {
+    let mut bytes = Bytes(bytes);
+
+    if bytes.take(MAGIC.len()) != Some(MAGIC) {
+        return Err(problem(MetadataProblem::MissingHeader));
+    }
+
+    let schema_version = bytes
+        .take(4)
+        .and_then(|b| b.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or_else(|| problem(MetadataProblem::MissingHeader))?;
+    if schema_version != SCHEMA_VERSION {
+        return Err(problem(MetadataProblem::SchemaVersion {
+            found: schema_version,
+            expected: SCHEMA_VERSION,
+        }));
+    }
+
+    let compiler_version = bytes
+        .take(1)
+        .and_then(|length| bytes.take(*length.first()? as usize))
+        .map(|version| String::from_utf8_lossy(version).to_string())
+        .ok_or_else(|| problem(MetadataProblem::MissingHeader))?;
+    if compiler_version != COMPILER_VERSION {
+        return Err(problem(MetadataProblem::CompilerVersion {
+            found: compiler_version,
+            expected: COMPILER_VERSION.to_string(),
+        }));
+    }
+
+    let content_hash = bytes
+        .take(8)
+        .and_then(|b| b.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or_else(|| problem(MetadataProblem::MissingHeader))?;
+    if content_hash != hash(bytes.0) {
+        return Err(problem(MetadataProblem::ContentHash));
+    }
+
+    Ok(bytes.0)
+}
+
+fn problem(problem: MetadataProblem) -> Error {
+    Error::IncompatibleMetadata {
+        path: None,
+        problem,
+    }
+}
+
+/// 64 bit FNV-1a, which is plenty to detect truncated or corrupted files.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Some(taken)
+    }
+}
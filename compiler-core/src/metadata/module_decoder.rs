@@ -4,16 +4,16 @@ use itertools::Itertools;
 
 use crate::{
     ast::{
-        BitStringSegment, BitStringSegmentOption, CallArg, Constant, TypedConstant,
+        BitStringSegment, BitStringSegmentOption, CallArg, Constant, Deprecation, TypedConstant,
         TypedConstantBitStringSegment, TypedConstantBitStringSegmentOption,
     },
-    build::Origin,
+    build::{Origin, Target},
     schema_capnp::{self as schema, *},
     type_::{
         self, AccessorsMap, FieldMap, Module, RecordAccessor, Type, TypeConstructor,
         ValueConstructor, ValueConstructorVariant,
     },
-    Result,
+    Error, Result,
 };
 use std::{collections::HashMap, io::BufRead, sync::Arc};
 
@@ -53,9 +53,16 @@ impl ModuleDecoder {
         Default::default()
     }
 
-    pub fn read(&mut self, reader: impl BufRead) -> Result<Module> {
+    pub fn read(&mut self, mut reader: impl BufRead) -> Result<Module> {
+        let mut bytes = vec![];
+        let _ = reader
+            .read_to_end(&mut bytes)
+            .map_err(|error| Error::MetadataDecodeError {
+                error: Some(error.to_string()),
+            })?;
+        let message = super::header::without_header(&bytes)?;
         let message_reader =
-            capnp::serialize_packed::read_message(reader, capnp::message::ReaderOptions::new())?;
+            capnp::serialize_packed::read_message(message, capnp::message::ReaderOptions::new())?;
         let reader = message_reader.get_root::<module::Reader<'_>>()?;
 
         Ok(Module {
@@ -80,6 +87,7 @@ impl ModuleDecoder {
             module,
             parameters: read_vec!(reader.get_parameters()?, self, type_),
             typ: type_,
+            deprecation: deprecation(reader.get_deprecated()?),
         })
     }
 
@@ -141,6 +149,7 @@ impl ModuleDecoder {
             origin: Default::default(),
             type_,
             variant,
+            deprecation: deprecation(reader.get_deprecated()?),
         })
     }
 
@@ -220,6 +229,7 @@ impl ModuleDecoder {
             label: Default::default(),
             location: Default::default(),
             value: self.constant(reader)?,
+            shorthand: false,
         })
     }
 
@@ -338,6 +348,7 @@ impl ModuleDecoder {
             module: module_name(&reader.get_module()?)?,
             arity: reader.get_arity() as usize,
             field_map: self.field_map(&reader.get_field_map()?)?,
+            targets: targets(&reader.get_targets()?)?,
         })
     }
 
@@ -391,3 +402,25 @@ fn module_name(module: &capnp::text_list::Reader<'_>) -> Result<Vec<String>> {
     let name = module.iter().map_ok(String::from).try_collect()?;
     Ok(name)
 }
+
+fn targets(reader: &capnp::text_list::Reader<'_>) -> Result<Vec<Target>> {
+    let mut targets = Vec::with_capacity(reader.len() as usize);
+    for target in reader.iter() {
+        // Targets unknown to this version of the compiler cannot be compiled
+        // for so they can be skipped
+        if let Ok(target) = target?.parse() {
+            targets.push(target);
+        }
+    }
+    Ok(targets)
+}
+
+fn deprecation(message: &str) -> Deprecation {
+    if message.is_empty() {
+        Deprecation::NotDeprecated
+    } else {
+        Deprecation::Deprecated {
+            message: message.to_string(),
+        }
+    }
+}
@@ -37,7 +37,9 @@ impl<'a> ModuleEncoder<'a> {
         self.set_module_accessors(&mut module);
         module.set_package(self.data.package.as_str());
 
-        let result = capnp::serialize_packed::write_message(&mut writer, &message);
+        let mut bytes = vec![];
+        capnp::serialize_packed::write_message(&mut bytes, &message)?;
+        let result = writer.write_all(&super::header::with_header(&bytes));
         writer.convert_err(result)
     }
 
@@ -121,9 +123,12 @@ impl<'a> ModuleEncoder<'a> {
             &constructor.parameters,
         );
         self.build_module_name(
-            builder.init_module(constructor.module.len() as u32),
+            builder
+                .reborrow()
+                .init_module(constructor.module.len() as u32),
             &constructor.module,
         );
+        builder.set_deprecated(constructor.deprecation.message().unwrap_or_default());
     }
 
     fn build_value_constructor(
@@ -132,7 +137,11 @@ impl<'a> ModuleEncoder<'a> {
         constructor: &ValueConstructor,
     ) {
         self.build_type(builder.reborrow().init_type(), &constructor.type_);
-        self.build_value_constructor_variant(builder.init_variant(), &constructor.variant);
+        self.build_value_constructor_variant(
+            builder.reborrow().init_variant(),
+            &constructor.variant,
+        );
+        builder.set_deprecated(constructor.deprecation.message().unwrap_or_default());
     }
 
     fn build_value_constructor_variant(
@@ -165,6 +174,7 @@ impl<'a> ModuleEncoder<'a> {
                 field_map,
                 module,
                 name,
+                targets,
             } => {
                 let mut builder = builder.init_module_fn();
                 builder.set_name(name);
@@ -176,6 +186,10 @@ impl<'a> ModuleEncoder<'a> {
                     }
                 }
                 builder.set_arity(*arity as u16);
+                let mut builder = builder.init_targets(targets.len() as u32);
+                for (i, target) in targets.iter().enumerate() {
+                    builder.set(i as u32, &target.to_string());
+                }
             }
         }
     }
@@ -328,7 +342,7 @@ impl<'a> ModuleEncoder<'a> {
                 elems,
             ),
 
-            Type::Var { type_: typ } => match typ.borrow().deref() {
+            Type::Var { type_: typ } => match typ.read().deref() {
                 TypeVar::Link { type_: typ } => self.build_type(builder, typ),
                 TypeVar::Generic { id } => self.build_type_var(builder.init_var(), *id),
                 TypeVar::Unbound { .. } => {
@@ -3,10 +3,11 @@ use type_::{AccessorsMap, FieldMap, RecordAccessor};
 use super::*;
 use crate::{
     ast::{
-        BitStringSegment, BitStringSegmentOption, CallArg, Constant, TypedConstant,
+        BitStringSegment, BitStringSegmentOption, CallArg, Constant, Deprecation, TypedConstant,
         TypedConstantBitStringSegmentOption,
     },
-    build::Origin,
+    build::{Origin, Target},
+    error::MetadataProblem,
     io::test::InMemoryFile,
     type_::{self, Module, Type, TypeConstructor, ValueConstructor, ValueConstructorVariant},
 };
@@ -37,6 +38,7 @@ fn constant_module(constant: TypedConstant) -> Module {
                 origin: Default::default(),
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleConstant { literal: constant },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -72,6 +74,184 @@ fn empty_module() {
     assert_eq!(roundtrip(&module), module);
 }
 
+fn encode(module: &Module) -> Vec<u8> {
+    let buffer = InMemoryFile::new();
+    ModuleEncoder::new(module).write(buffer.clone()).unwrap();
+    buffer.into_contents().unwrap()
+}
+
+fn decode_error(bytes: &[u8]) -> crate::Error {
+    ModuleDecoder::new()
+        .read(BufReader::new(bytes))
+        .expect_err("should fail to decode")
+}
+
+fn empty_module_named(name: &str) -> Module {
+    Module {
+        package: "some_package".to_string(),
+        origin: Origin::Src,
+        name: vec![name.to_string()],
+        types: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+    }
+}
+
+#[test]
+fn metadata_without_header() {
+    let bytes = encode(&empty_module_named("one"));
+    let (_, message) = bytes.split_at(bytes.len() / 2);
+    assert_eq!(
+        decode_error(message),
+        crate::Error::IncompatibleMetadata {
+            path: None,
+            problem: MetadataProblem::MissingHeader,
+        }
+    );
+}
+
+#[test]
+fn metadata_from_another_schema_version() {
+    let mut bytes = encode(&empty_module_named("one"));
+    if let Some(byte) = bytes.get_mut(8) {
+        *byte += 1;
+    }
+    assert_eq!(
+        decode_error(&bytes),
+        crate::Error::IncompatibleMetadata {
+            path: None,
+            problem: MetadataProblem::SchemaVersion {
+                found: SCHEMA_VERSION + 1,
+                expected: SCHEMA_VERSION,
+            },
+        }
+    );
+}
+
+#[test]
+fn metadata_from_another_compiler_version() {
+    let bytes = encode(&empty_module_named("one"));
+    let message = header::without_header(&bytes).unwrap();
+    let mut bytes = b"GLEAMMOD".to_vec();
+    bytes.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+    bytes.push(6);
+    bytes.extend_from_slice(b"0.15.0");
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.extend_from_slice(message);
+    assert_eq!(
+        decode_error(&bytes),
+        crate::Error::IncompatibleMetadata {
+            path: None,
+            problem: MetadataProblem::CompilerVersion {
+                found: "0.15.0".to_string(),
+                expected: COMPILER_VERSION.to_string(),
+            },
+        }
+    );
+}
+
+#[test]
+fn corrupted_metadata() {
+    let mut bytes = encode(&empty_module_named("one"));
+    if let Some(byte) = bytes.last_mut() {
+        *byte ^= 0xff;
+    }
+    assert_eq!(
+        decode_error(&bytes),
+        crate::Error::IncompatibleMetadata {
+            path: None,
+            problem: MetadataProblem::ContentHash,
+        }
+    );
+}
+
+#[test]
+fn deprecated_type_and_value() {
+    let module = Module {
+        package: "some_package".to_string(),
+        origin: Origin::Src,
+        name: vec!["a".to_string()],
+        types: vec![(
+            "Old".to_string(),
+            TypeConstructor {
+                typ: type_::int(),
+                public: true,
+                origin: Default::default(),
+                module: vec!["a".to_string()],
+                parameters: vec![],
+                deprecation: Deprecation::Deprecated {
+                    message: "use New instead".to_string(),
+                },
+            },
+        )]
+        .into_iter()
+        .collect(),
+        values: vec![(
+            "old".to_string(),
+            ValueConstructor {
+                public: true,
+                origin: Default::default(),
+                type_: type_::int(),
+                variant: ValueConstructorVariant::ModuleConstant {
+                    literal: Constant::Int {
+                        location: Default::default(),
+                        value: "1".to_string(),
+                    },
+                },
+                deprecation: Deprecation::Deprecated {
+                    message: "use new instead".to_string(),
+                },
+            },
+        )]
+        .into_iter()
+        .collect(),
+        accessors: HashMap::new(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn deprecated_and_not_deprecated_functions() {
+    let function = |name: &str, deprecation| ValueConstructor {
+        public: true,
+        origin: Default::default(),
+        type_: type_::fn_(vec![], type_::int()),
+        variant: ValueConstructorVariant::ModuleFn {
+            name: name.to_string(),
+            field_map: None,
+            module: vec!["a".to_string()],
+            arity: 0,
+            targets: Target::all(),
+        },
+        deprecation,
+    };
+    let module = Module {
+        package: "some_package".to_string(),
+        origin: Origin::Src,
+        name: vec!["a".to_string()],
+        types: HashMap::new(),
+        values: vec![
+            (
+                "old".to_string(),
+                function(
+                    "old",
+                    Deprecation::Deprecated {
+                        message: "use new instead".to_string(),
+                    },
+                ),
+            ),
+            (
+                "new".to_string(),
+                function("new", Deprecation::NotDeprecated),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        accessors: HashMap::new(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
 #[test]
 fn module_with_app_type() {
     let module = Module {
@@ -86,6 +266,7 @@ fn module_with_app_type() {
                 origin: Default::default(),
                 module: vec!["the".to_string(), "module".to_string()],
                 parameters: vec![],
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -110,6 +291,7 @@ fn module_with_fn_type() {
                 origin: Default::default(),
                 module: vec!["the".to_string(), "module".to_string()],
                 parameters: vec![],
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -134,6 +316,7 @@ fn module_with_tuple_type() {
                 origin: Default::default(),
                 module: vec!["the".to_string(), "module".to_string()],
                 parameters: vec![],
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -164,6 +347,7 @@ fn module_with_generic_type() {
                     origin: Default::default(),
                     module: vec!["the".to_string(), "module".to_string()],
                     parameters: vec![t1, t2],
+                    deprecation: Deprecation::NotDeprecated,
                 },
             )]
             .into_iter()
@@ -194,6 +378,7 @@ fn module_with_type_links() {
                     origin: Default::default(),
                     module: vec!["a".to_string()],
                     parameters: vec![],
+                    deprecation: Deprecation::NotDeprecated,
                 },
             )]
             .into_iter()
@@ -225,7 +410,9 @@ fn module_fn_value() {
                     field_map: None,
                     module: vec!["a".to_string()],
                     arity: 5,
+                    targets: Target::all(),
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -259,7 +446,40 @@ fn module_fn_value_with_field_map() {
                     }),
                     module: vec!["a".to_string()],
                     arity: 5,
+                    targets: Target::all(),
+                },
+                deprecation: Deprecation::NotDeprecated,
+            },
+        )]
+        .into_iter()
+        .collect(),
+    };
+
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn module_fn_value_with_single_target() {
+    let module = Module {
+        package: "some_package".to_string(),
+        origin: Origin::Src,
+        name: vec!["a".to_string()],
+        types: HashMap::new(),
+        accessors: HashMap::new(),
+        values: vec![(
+            "one".to_string(),
+            ValueConstructor {
+                public: true,
+                origin: Default::default(),
+                type_: type_::int(),
+                variant: ValueConstructorVariant::ModuleFn {
+                    name: "one".to_string(),
+                    field_map: None,
+                    module: vec!["a".to_string()],
+                    arity: 0,
+                    targets: vec![Target::JavaScript],
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -288,6 +508,7 @@ fn record_value() {
                     field_map: None,
                     arity: 5,
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -321,6 +542,7 @@ fn record_value_with_field_map() {
                     }),
                     arity: 5,
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into_iter()
@@ -486,6 +708,7 @@ fn constant_record() {
         args: vec![
             CallArg {
                 label: None,
+                shorthand: false,
                 location: Default::default(),
                 value: Constant::Float {
                     location: Default::default(),
@@ -494,6 +717,7 @@ fn constant_record() {
             },
             CallArg {
                 label: None,
+                shorthand: false,
                 location: Default::default(),
                 value: Constant::Int {
                     location: Default::default(),
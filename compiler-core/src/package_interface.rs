@@ -0,0 +1,363 @@
+//! A description of the public interface of a package that can be serialised
+//! to JSON for use by tooling that is not written in Rust.
+//!
+//! Everything is stored in sorted maps so that the serialised form is stable
+//! from one compilation to the next.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    ast::{Statement, TypedModule},
+    type_::{self, Type, TypeVar, ValueConstructorVariant},
+    Error, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+    path::Path,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageInterface {
+    pub name: String,
+    pub version: String,
+    pub modules: BTreeMap<String, ModuleInterface>,
+}
+
+impl PackageInterface {
+    pub fn new(name: String, version: String) -> Self {
+        Self {
+            name,
+            version,
+            modules: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert_module(&mut self, module: ModuleInterface, name: String) {
+        let _ = self.modules.insert(name, module);
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("package interface JSON serialisation")
+    }
+
+    pub fn from_json(path: &Path, json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|error| Error::InvalidPackageInterface {
+            path: path.to_path_buf(),
+            error: error.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ModuleInterface {
+    pub documentation: Vec<String>,
+    pub types: BTreeMap<String, TypeDefinitionInterface>,
+    pub type_aliases: BTreeMap<String, TypeAliasInterface>,
+    pub functions: BTreeMap<String, FunctionInterface>,
+    pub constants: BTreeMap<String, ConstantInterface>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeDefinitionInterface {
+    pub documentation: Option<String>,
+    pub parameters: usize,
+    pub constructors: Vec<TypeConstructorInterface>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeConstructorInterface {
+    pub name: String,
+    pub documentation: Option<String>,
+    pub parameters: Vec<ParameterInterface>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeAliasInterface {
+    pub documentation: Option<String>,
+    pub parameters: usize,
+    pub alias: TypeInterface,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionInterface {
+    pub documentation: Option<String>,
+    pub parameters: Vec<ParameterInterface>,
+    #[serde(rename = "return")]
+    pub return_: TypeInterface,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstantInterface {
+    pub documentation: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: TypeInterface,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterInterface {
+    pub label: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: TypeInterface,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TypeInterface {
+    Named {
+        name: String,
+        module: String,
+        parameters: Vec<TypeInterface>,
+    },
+    Fn {
+        parameters: Vec<TypeInterface>,
+        #[serde(rename = "return")]
+        return_: Box<TypeInterface>,
+    },
+    Tuple {
+        elements: Vec<TypeInterface>,
+    },
+    Variable {
+        id: usize,
+    },
+}
+
+impl ModuleInterface {
+    /// Build the interface of a module from the type information of a
+    /// compiled module, such as that decoded from module metadata. This does
+    /// not include any documentation.
+    pub fn from_type_info(module: &type_::Module) -> Self {
+        let mut interface = Self::default();
+        let module_name = module.name.as_slice();
+
+        for (name, constructor) in &module.types {
+            let mut ids = TypeVarIds::default();
+            let parameters = constructor.parameters.len();
+            for parameter in &constructor.parameters {
+                let _ = ids.type_(parameter);
+            }
+            match type_::collapse_links(constructor.typ.clone()).deref() {
+                Type::App {
+                    name: type_name,
+                    module,
+                    ..
+                } if type_name == name && module.as_slice() == module_name => {
+                    let _ = interface.types.insert(
+                        name.clone(),
+                        TypeDefinitionInterface {
+                            documentation: None,
+                            parameters,
+                            constructors: vec![],
+                        },
+                    );
+                }
+                typ => {
+                    let _ = interface.type_aliases.insert(
+                        name.clone(),
+                        TypeAliasInterface {
+                            documentation: None,
+                            parameters,
+                            alias: ids.type_(typ),
+                        },
+                    );
+                }
+            }
+        }
+
+        for (name, value) in &module.values {
+            let mut ids = TypeVarIds::default();
+            match &value.variant {
+                ValueConstructorVariant::Record { field_map, .. } => {
+                    let (arguments, retrn) = match value.type_.fn_types() {
+                        Some((arguments, retrn)) => (arguments, retrn),
+                        None => (vec![], value.type_.clone()),
+                    };
+                    let type_name = match collapse(&retrn).deref() {
+                        Type::App { name, .. } => name.clone(),
+                        _ => continue,
+                    };
+                    // Register the type's parameters first so the ids used
+                    // match the order of the type's parameters.
+                    let _ = ids.type_(&retrn);
+                    let labels = labels(field_map.as_ref(), arguments.len());
+                    if let Some(definition) = interface.types.get_mut(&type_name) {
+                        definition.constructors.push(TypeConstructorInterface {
+                            name: name.clone(),
+                            documentation: None,
+                            parameters: parameters(&mut ids, labels, &arguments),
+                        })
+                    }
+                }
+
+                ValueConstructorVariant::ModuleFn { field_map, .. } => {
+                    let (arguments, retrn) = match value.type_.fn_types() {
+                        Some(fn_types) => fn_types,
+                        None => continue,
+                    };
+                    let labels = labels(field_map.as_ref(), arguments.len());
+                    let _ = interface.functions.insert(
+                        name.clone(),
+                        FunctionInterface {
+                            documentation: None,
+                            parameters: parameters(&mut ids, labels, &arguments),
+                            return_: ids.type_(&retrn),
+                        },
+                    );
+                }
+
+                ValueConstructorVariant::ModuleConstant { .. } => {
+                    let _ = interface.constants.insert(
+                        name.clone(),
+                        ConstantInterface {
+                            documentation: None,
+                            type_: ids.type_(&value.type_),
+                        },
+                    );
+                }
+
+                ValueConstructorVariant::LocalVariable => (),
+            }
+        }
+
+        for definition in interface.types.values_mut() {
+            definition.constructors.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        interface
+    }
+
+    /// Build the interface of a module that has been compiled from source,
+    /// including the documentation of the module and its public definitions.
+    pub fn from_typed_module(module: &TypedModule) -> Self {
+        let mut interface = Self::from_type_info(&module.type_info);
+        interface.documentation = module.documentation.clone();
+
+        for statement in &module.statements {
+            match statement {
+                Statement::Fn { name, doc, .. } | Statement::ExternalFn { name, doc, .. } => {
+                    if let Some(function) = interface.functions.get_mut(name) {
+                        function.documentation = doc.clone();
+                    }
+                }
+
+                Statement::ModuleConstant { name, doc, .. } => {
+                    if let Some(constant) = interface.constants.get_mut(name) {
+                        constant.documentation = doc.clone();
+                    }
+                }
+
+                Statement::TypeAlias {
+                    alias: name, doc, ..
+                } => {
+                    if let Some(alias) = interface.type_aliases.get_mut(name) {
+                        alias.documentation = doc.clone();
+                    }
+                }
+
+                Statement::ExternalType { name, doc, .. } => {
+                    if let Some(definition) = interface.types.get_mut(name) {
+                        definition.documentation = doc.clone();
+                    }
+                }
+
+                Statement::CustomType {
+                    name,
+                    doc,
+                    constructors,
+                    ..
+                } => {
+                    if let Some(definition) = interface.types.get_mut(name) {
+                        definition.documentation = doc.clone();
+                        for constructor in definition.constructors.iter_mut() {
+                            constructor.documentation = constructors
+                                .iter()
+                                .find(|c| c.name == constructor.name)
+                                .and_then(|c| c.documentation.clone());
+                        }
+                    }
+                }
+
+                Statement::Import { .. } => (),
+            }
+        }
+
+        interface
+    }
+}
+
+fn labels(field_map: Option<&type_::FieldMap>, arity: usize) -> Vec<Option<String>> {
+    let mut labels = vec![None; arity];
+    if let Some(field_map) = field_map {
+        for (label, index) in &field_map.fields {
+            if let Some(slot) = labels.get_mut(*index) {
+                *slot = Some(label.clone());
+            }
+        }
+    }
+    labels
+}
+
+fn parameters(
+    ids: &mut TypeVarIds,
+    labels: Vec<Option<String>>,
+    types: &[std::sync::Arc<Type>],
+) -> Vec<ParameterInterface> {
+    labels
+        .into_iter()
+        .zip(types)
+        .map(|(label, type_)| ParameterInterface {
+            label,
+            type_: ids.type_(type_),
+        })
+        .collect()
+}
+
+fn collapse(type_: &std::sync::Arc<Type>) -> std::sync::Arc<Type> {
+    type_::collapse_links(type_.clone())
+}
+
+/// Type variable ids are numbered from zero in the order they are first seen
+/// within each definition so that they are stable between compilations.
+#[derive(Debug, Default)]
+struct TypeVarIds {
+    ids: HashMap<usize, usize>,
+}
+
+impl TypeVarIds {
+    fn type_(&mut self, type_: &Type) -> TypeInterface {
+        match type_ {
+            Type::App {
+                name, module, args, ..
+            } => TypeInterface::Named {
+                name: name.clone(),
+                module: if module.is_empty() {
+                    "gleam".to_string()
+                } else {
+                    module.join("/")
+                },
+                parameters: args.iter().map(|a| self.type_(a)).collect(),
+            },
+
+            Type::Fn { args, retrn } => TypeInterface::Fn {
+                parameters: args.iter().map(|a| self.type_(a)).collect(),
+                return_: Box::new(self.type_(retrn)),
+            },
+
+            Type::Tuple { elems } => TypeInterface::Tuple {
+                elements: elems.iter().map(|e| self.type_(e)).collect(),
+            },
+
+            Type::Var { type_ } => match type_.read().deref() {
+                TypeVar::Link { type_ } => self.type_(type_),
+                TypeVar::Unbound { id, .. } | TypeVar::Generic { id } => {
+                    let next = self.ids.len();
+                    TypeInterface::Variable {
+                        id: *self.ids.entry(*id).or_insert(next),
+                    }
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::*;
+use crate::{
+    build::{Origin, Target},
+    type_::{build_prelude, infer_module, UidGenerator},
+};
+use pretty_assertions::assert_eq;
+
+fn interface(src: &str) -> ModuleInterface {
+    let (mut ast, extra) = crate::parse::parse_module(src).expect("syntax error");
+    ast.name = vec!["my".to_string(), "mod".to_string()];
+    let mut uid = UidGenerator::default();
+    let mut modules = std::collections::HashMap::new();
+    let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
+    let mut ast = infer_module(
+        Target::Erlang,
+        &mut uid,
+        ast,
+        Origin::Src,
+        "thepackage",
+        &modules,
+        &mut vec![],
+    )
+    .expect("should successfully infer");
+    ast.attach_doc_and_module_comments(&extra, src);
+    ModuleInterface::from_typed_module(&ast)
+}
+
+fn int() -> TypeInterface {
+    TypeInterface::Named {
+        name: "Int".to_string(),
+        module: "gleam".to_string(),
+        parameters: vec![],
+    }
+}
+
+#[test]
+fn functions() {
+    let module = interface(
+        r#"
+/// Add two numbers
+pub fn add(x: Int, to y: Int) -> Int { x + y }
+
+fn private() { 1 }
+"#,
+    );
+    assert_eq!(module.functions.len(), 1);
+    assert_eq!(
+        module.functions.get("add"),
+        Some(&FunctionInterface {
+            documentation: Some(" Add two numbers".to_string()),
+            parameters: vec![
+                ParameterInterface {
+                    label: None,
+                    type_: int(),
+                },
+                ParameterInterface {
+                    label: Some("to".to_string()),
+                    type_: int(),
+                },
+            ],
+            return_: int(),
+        })
+    );
+}
+
+#[test]
+fn type_variables_are_numbered_per_definition() {
+    let module = interface(
+        r#"
+pub fn swap(pair: #(a, b)) -> #(b, a) {
+  let #(a, b) = pair
+  #(b, a)
+}
+"#,
+    );
+    let function = module.functions.get("swap").unwrap();
+    assert_eq!(
+        function.return_,
+        TypeInterface::Tuple {
+            elements: vec![
+                TypeInterface::Variable { id: 1 },
+                TypeInterface::Variable { id: 0 },
+            ]
+        }
+    );
+}
+
+#[test]
+fn custom_types_and_aliases() {
+    let module = interface(
+        r#"
+//// A module of shapes
+
+/// A shape
+pub type Shape(a) {
+  /// A circle
+  Circle(radius: a)
+  Square(a)
+}
+
+pub opaque type Secret {
+  Secret(Int)
+}
+
+pub type Ints = List(Int)
+
+pub const size = 1
+"#,
+    );
+    assert_eq!(
+        module.documentation,
+        vec![" A module of shapes".to_string()]
+    );
+    assert_eq!(
+        module.types.get("Shape"),
+        Some(&TypeDefinitionInterface {
+            documentation: Some(" A shape".to_string()),
+            parameters: 1,
+            constructors: vec![
+                TypeConstructorInterface {
+                    name: "Circle".to_string(),
+                    documentation: Some(" A circle".to_string()),
+                    parameters: vec![ParameterInterface {
+                        label: Some("radius".to_string()),
+                        type_: TypeInterface::Variable { id: 0 },
+                    }],
+                },
+                TypeConstructorInterface {
+                    name: "Square".to_string(),
+                    documentation: None,
+                    parameters: vec![ParameterInterface {
+                        label: None,
+                        type_: TypeInterface::Variable { id: 0 },
+                    }],
+                },
+            ],
+        })
+    );
+    assert_eq!(
+        module.types.get("Secret").map(|t| t.constructors.len()),
+        Some(0)
+    );
+    assert_eq!(
+        module.type_aliases.get("Ints"),
+        Some(&TypeAliasInterface {
+            documentation: None,
+            parameters: 0,
+            alias: TypeInterface::Named {
+                name: "List".to_string(),
+                module: "gleam".to_string(),
+                parameters: vec![int()],
+            },
+        })
+    );
+    assert_eq!(
+        module.constants.get("size"),
+        Some(&ConstantInterface {
+            documentation: None,
+            type_: int(),
+        })
+    );
+}
+
+#[test]
+fn json_round_trip() {
+    let mut package = PackageInterface::new("thepackage".to_string(), "1.0.0".to_string());
+    package.insert_module(
+        interface("pub fn id(x) { x }\npub type Thing { Thing }"),
+        "my/mod".to_string(),
+    );
+    let json = package.to_json();
+    assert_eq!(
+        serde_json::from_str::<PackageInterface>(&json).unwrap(),
+        package
+    );
+    assert_eq!(json, package.to_json());
+}
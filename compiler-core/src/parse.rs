@@ -56,11 +56,11 @@ mod token;
 
 use crate::ast::{
     Arg, ArgNames, AssignmentKind, BinOp, BitStringSegment, BitStringSegmentOption, CallArg,
-    Clause, ClauseGuard, Constant, ExternalFnArg, HasLocation, Module, Pattern, RecordConstructor,
-    RecordConstructorArg, RecordUpdateSpread, SrcSpan, Statement, TargetGroup, TypeAst,
-    UnqualifiedImport, UntypedArg, UntypedClause, UntypedClauseGuard, UntypedConstant, UntypedExpr,
-    UntypedExternalFnArg, UntypedModule, UntypedPattern, UntypedRecordUpdateArg, UntypedStatement,
-    CAPTURE_VARIABLE,
+    Clause, ClauseGuard, Constant, Deprecation, ExternalFnArg, ExternalFnImplementation,
+    HasLocation, Module, Pattern, RecordConstructor, RecordConstructorArg, RecordUpdateSpread,
+    SrcSpan, Statement, TargetGroup, TypeAst, UnqualifiedImport, UntypedArg, UntypedClause,
+    UntypedClauseGuard, UntypedConstant, UntypedExpr, UntypedExternalFnArg, UntypedModule,
+    UntypedPattern, UntypedRecordUpdateArg, UntypedStatement, CAPTURE_VARIABLE,
 };
 use crate::build::Target;
 use crate::parse::extra::ModuleExtra;
@@ -69,7 +69,7 @@ use lexer::{LexResult, Spanned};
 use std::cmp::Ordering;
 use std::str::FromStr;
 use token::Token;
-use vec1::vec1;
+use vec1::{vec1, Vec1};
 #[cfg(test)]
 mod tests;
 
@@ -282,6 +282,12 @@ where
                 self.parse_custom_type(start, true, false)
             }
 
+            // Attributes
+            (Some((start, Token::At, _)), _) => {
+                let _ = self.next_tok();
+                self.parse_attribute(start)
+            }
+
             (t0, _) => {
                 self.tok0 = t0;
                 Ok(None)
@@ -291,6 +297,46 @@ where
         statement
     }
 
+    // examples:
+    //   @deprecated("use other_function instead")
+    //   pub fn function() { ... }
+    fn parse_attribute(&mut self, start: usize) -> Result<Option<UntypedStatement>, ParseError> {
+        let (_, name, end) = self.expect_name()?;
+        if name != "deprecated" {
+            return parse_error(ParseErrorType::UnknownAttribute, SrcSpan { start, end });
+        }
+        let _ = self.expect_one(&Token::LeftParen)?;
+        let (message_start, message, message_end) = self.expect_string()?;
+        // An empty message is how metadata records that a value is not
+        // deprecated, so a message has to be given.
+        if message.is_empty() {
+            return parse_error(
+                ParseErrorType::EmptyDeprecationMessage,
+                SrcSpan {
+                    start: message_start,
+                    end: message_end,
+                },
+            );
+        }
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        let location = SrcSpan { start, end };
+
+        let mut statement = match self.parse_statement()? {
+            Some(statement) => statement,
+            None => return parse_error(ParseErrorType::AttributeWithoutDefinition, location),
+        };
+        match statement.deprecation_mut() {
+            Some(deprecation @ Deprecation::NotDeprecated) => {
+                *deprecation = Deprecation::Deprecated { message };
+                Ok(Some(statement))
+            }
+            Some(Deprecation::Deprecated { .. }) => {
+                parse_error(ParseErrorType::DuplicateAttribute, location)
+            }
+            None => parse_error(ParseErrorType::InvalidAttributeTarget, location),
+        }
+    }
+
     //
     // Parse Expressions
     //
@@ -480,6 +526,32 @@ where
                 }
             }
 
+            // boolean negation
+            Some((start, Token::Bang, end)) => {
+                let _ = self.next_tok();
+                let value = self.expect_expression_unit(start, end)?;
+                UntypedExpr::NegateBool {
+                    location: SrcSpan {
+                        start,
+                        end: value.location().end,
+                    },
+                    value: Box::new(value),
+                }
+            }
+
+            // integer negation
+            Some((start, Token::Minus, end)) => {
+                let _ = self.next_tok();
+                let value = self.expect_expression_unit(start, end)?;
+                UntypedExpr::NegateInt {
+                    location: SrcSpan {
+                        start,
+                        end: value.location().end,
+                    },
+                    value: Box::new(value),
+                }
+            }
+
             // expression group  "{" "}"
             Some((start, Token::LeftBrace, _)) => {
                 let _ = self.next_tok();
@@ -636,6 +708,24 @@ where
         Ok(Some(expr))
     }
 
+    // The operand of a unary operator, which binds tighter than any binary operator
+    fn expect_expression_unit(
+        &mut self,
+        op_start: usize,
+        op_end: usize,
+    ) -> Result<UntypedExpr, ParseError> {
+        match self.parse_expression_unit()? {
+            Some(value) => Ok(value),
+            None => parse_error(
+                ParseErrorType::OpNakedRight,
+                SrcSpan {
+                    start: op_start,
+                    end: op_end,
+                },
+            ),
+        }
+    }
+
     // An assignment, with `Let` or `Assert` already consumed
     fn parse_assignment(
         &mut self,
@@ -660,21 +750,30 @@ where
                 end: eq_e,
             },
         })?;
+
+        // assert pattern = expr as "message"
+        let (message, end) = match kind {
+            AssignmentKind::Assert if self.maybe_one(&Token::As).is_some() => {
+                let (_, message, end) = self.expect_string()?;
+                (Some(message), end)
+            }
+            _ => (None, value.location().end),
+        };
+
         Ok(UntypedExpr::Assignment {
-            location: SrcSpan {
-                start,
-                end: value.location().end,
-            },
+            location: SrcSpan { start, end },
             value: Box::new(value),
             pattern,
             annotation,
             kind,
+            message,
         })
     }
 
     // examples:
     //   let pattern = expr
     //   assert pattern: Type = expr
+    //   assert pattern = expr as "message"
     //   try pattern = expr
     //   expr expr
     //   expr
@@ -1047,6 +1146,28 @@ where
                     }))
                 }
             }
+            Some((start, Token::Bang, end)) => {
+                let _ = self.next_tok();
+                let value = self.expect_case_clause_guard_unit(start, end)?;
+                Ok(Some(ClauseGuard::NegateBool {
+                    location: SrcSpan {
+                        start,
+                        end: value.location().end,
+                    },
+                    value: Box::new(value),
+                }))
+            }
+            Some((start, Token::Minus, end)) => {
+                let _ = self.next_tok();
+                let value = self.expect_case_clause_guard_unit(start, end)?;
+                Ok(Some(ClauseGuard::NegateInt {
+                    location: SrcSpan {
+                        start,
+                        end: value.location().end,
+                    },
+                    value: Box::new(value),
+                }))
+            }
             Some((_, Token::LeftBrace, _)) => {
                 // Nested guard expression
                 let _ = self.next_tok();
@@ -1066,6 +1187,23 @@ where
         }
     }
 
+    fn expect_case_clause_guard_unit(
+        &mut self,
+        op_start: usize,
+        op_end: usize,
+    ) -> Result<UntypedClauseGuard, ParseError> {
+        match self.parse_case_clause_guard_unit()? {
+            Some(value) => Ok(value),
+            None => parse_error(
+                ParseErrorType::OpNakedRight,
+                SrcSpan {
+                    start: op_start,
+                    end: op_end,
+                },
+            ),
+        }
+    }
+
     // examples:
     //   UpName( args )
     fn expect_constructor_pattern(
@@ -1130,6 +1268,7 @@ where
                         },
                         label: Some(name),
                         value,
+                        shorthand: false,
                     }))
                 } else {
                     parse_error(
@@ -1150,6 +1289,7 @@ where
                         location: value.location(),
                         label: None,
                         value,
+                        shorthand: false,
                     }))
                 } else {
                     Ok(None)
@@ -1160,9 +1300,24 @@ where
 
     // examples:
     //   a: expr
+    //   a:
     fn parse_record_update_arg(&mut self) -> Result<Option<UntypedRecordUpdateArg>, ParseError> {
-        if let Some((start, label, _)) = self.maybe_name() {
-            let _ = self.expect_one(&Token::Colon)?;
+        if let Some((start, label, end)) = self.maybe_name() {
+            let (_, colon_end) = self.expect_one(&Token::Colon)?;
+            if self.label_shorthand_follows() {
+                return Ok(Some(UntypedRecordUpdateArg {
+                    location: SrcSpan {
+                        start,
+                        end: colon_end,
+                    },
+                    value: UntypedExpr::Var {
+                        location: SrcSpan { start, end },
+                        name: label.clone(),
+                    },
+                    label,
+                    shorthand: true,
+                }));
+            }
             let value = self.parse_expression()?;
             if let Some(value) = value {
                 Ok(Some(UntypedRecordUpdateArg {
@@ -1172,6 +1327,7 @@ where
                         end: value.location().end,
                     },
                     value,
+                    shorthand: false,
                 }))
             } else {
                 self.next_tok_unexpected(vec!["An expression".to_string()])
@@ -1222,6 +1378,7 @@ where
                 body,
                 return_type: (),
                 return_annotation,
+                deprecation: Deprecation::NotDeprecated,
             }))
         } else {
             self.next_tok_unexpected(vec!["The body of a function".to_string()])
@@ -1233,6 +1390,7 @@ where
     // examples:
     //   external fn a(String) -> String = "x" "y"
     //   pub external fn a(name: String) -> String = "x" "y"
+    //   external fn a(String) -> String = erlang "x" "y" javascript "./x.mjs" "y"
     fn parse_external_fn(
         &mut self,
         start: usize,
@@ -1245,8 +1403,7 @@ where
         let (arr_s, arr_e) = self.expect_one(&Token::RArrow)?;
         let return_annotation = self.parse_type(false)?;
         let _ = self.expect_one(&Token::Equal)?;
-        let (_, module, _) = self.expect_string()?;
-        let (_, fun, end) = self.expect_string()?;
+        let (implementations, end) = self.parse_external_fn_implementations()?;
 
         if let Some(retrn) = return_annotation {
             Ok(Some(Statement::ExternalFn {
@@ -1255,10 +1412,10 @@ where
                 public,
                 name,
                 arguments: args,
-                module,
-                fun,
+                implementations,
                 return_: retrn,
                 return_type: (),
+                deprecation: Deprecation::NotDeprecated,
             }))
         } else {
             parse_error(
@@ -1271,6 +1428,69 @@ where
         }
     }
 
+    // Starts after "="
+    //
+    // examples:
+    //   "x" "y"
+    //   erlang "x" "y"
+    //   erlang "x" "y" javascript "./x.mjs" "y"
+    fn parse_external_fn_implementations(
+        &mut self,
+    ) -> Result<(Vec1<ExternalFnImplementation>, usize), ParseError> {
+        // A single implementation used for every target
+        if let Some((_, Token::String { .. }, _)) = &self.tok0 {
+            let (_, module, _) = self.expect_string()?;
+            let (_, fun, end) = self.expect_string()?;
+            let implementation = ExternalFnImplementation {
+                target: None,
+                module,
+                fun,
+            };
+            return Ok((vec1![implementation], end));
+        }
+
+        // An implementation for each target
+        let (implementation, mut end) = self.parse_external_fn_target_implementation()?;
+        let mut implementations = vec1![implementation];
+        while let Some((start, Token::Name { .. }, _)) = &self.tok0 {
+            let start = *start;
+            let (implementation, implementation_end) =
+                self.parse_external_fn_target_implementation()?;
+            if implementations
+                .iter()
+                .any(|existing| existing.target == implementation.target)
+            {
+                return parse_error(
+                    ParseErrorType::DuplicateExternalTarget,
+                    SrcSpan {
+                        start,
+                        end: implementation_end,
+                    },
+                );
+            }
+            implementations.push(implementation);
+            end = implementation_end;
+        }
+        Ok((implementations, end))
+    }
+
+    // examples:
+    //   erlang "x" "y"
+    //   javascript "./x.mjs" "y"
+    fn parse_external_fn_target_implementation(
+        &mut self,
+    ) -> Result<(ExternalFnImplementation, usize), ParseError> {
+        let target = self.expect_target()?;
+        let (_, module, _) = self.expect_string()?;
+        let (_, fun, end) = self.expect_string()?;
+        let implementation = ExternalFnImplementation {
+            target: Some(target),
+            module,
+            fun,
+        };
+        Ok((implementation, end))
+    }
+
     // Parse a single external function definition param
     //
     // examples:
@@ -1398,12 +1618,27 @@ where
     //   expr
     //   a: _
     //   a: expr
+    //   a:
     fn parse_fn_arg(&mut self) -> Result<Option<ParserArg>, ParseError> {
         let mut start = 0;
         let label = match (self.tok0.take(), self.tok1.as_ref()) {
-            (Some((s, Token::Name { name }, _)), Some((_, Token::Colon, _))) => {
-                let _ = self.next_tok();
+            (Some((s, Token::Name { name }, e)), Some((_, Token::Colon, _))) => {
                 let _ = self.next_tok();
+                let (_, colon_end) = self.expect_one(&Token::Colon)?;
+                if self.label_shorthand_follows() {
+                    return Ok(Some(ParserArg::Arg(CallArg {
+                        location: SrcSpan {
+                            start: s,
+                            end: colon_end,
+                        },
+                        value: UntypedExpr::Var {
+                            location: SrcSpan { start: s, end: e },
+                            name: name.clone(),
+                        },
+                        label: Some(name),
+                        shorthand: true,
+                    })));
+                }
                 start = s;
                 Some(name)
             }
@@ -1422,6 +1657,7 @@ where
                 label,
                 location,
                 value,
+                shorthand: false,
             })))
         } else if let Some((start, _, end)) = self.maybe_discard_name() {
             let mut location = SrcSpan { start, end };
@@ -1504,6 +1740,7 @@ where
                     parameters,
                     constructors,
                     typed_parameters: vec![],
+                    deprecation: Deprecation::NotDeprecated,
                 }))
             }
         } else if let Some((eq_s, eq_e)) = self.maybe_one(&Token::Equal) {
@@ -1522,6 +1759,7 @@ where
                         parameters,
                         type_ast: t,
                         type_: (),
+                        deprecation: Deprecation::NotDeprecated,
                     }))
                 } else {
                     parse_error(
@@ -1897,6 +2135,7 @@ where
                 annotation,
                 value: Box::new(value),
                 type_: (),
+                deprecation: Deprecation::NotDeprecated,
             }))
         } else {
             parse_error(
@@ -2074,12 +2313,14 @@ where
                     },
                     value,
                     label: Some(label),
+                    shorthand: false,
                 }))
             } else {
                 Ok(Some(CallArg {
                     location: value.location(),
                     value,
                     label: None,
+                    shorthand: false,
                 }))
             }
         } else if name.is_some() {
@@ -2326,6 +2567,16 @@ where
         }
     }
 
+    // Whether a label is written without a value, as a shorthand for a
+    // variable with the same name as the label.
+    //
+    // examples:
+    //   a:,
+    //   a:)
+    fn label_shorthand_follows(&self) -> bool {
+        matches!(self.tok0, Some((_, Token::Comma | Token::RightParen, _)))
+    }
+
     // If the next token matches the requested, consume it and return (start, end)
     fn maybe_one(&mut self, tok: &Token) -> Option<(usize, usize)> {
         match self.tok0.take() {
@@ -2545,6 +2796,7 @@ fn tok_to_binop(t: &Token) -> Option<BinOp> {
         Token::StarDot => Some(BinOp::MultFloat),
         Token::Slash => Some(BinOp::DivInt),
         Token::SlashDot => Some(BinOp::DivFloat),
+        Token::LtGt => Some(BinOp::Concatenate),
         _ => None,
     }
 }
@@ -2678,6 +2930,12 @@ fn clause_guard_reduction(
             right,
         },
 
+        Token::LtGt => ClauseGuard::Concatenate {
+            location,
+            left,
+            right,
+        },
+
         _ => panic!("Token could not be converted to Guard Op."),
     }
 }
@@ -2797,6 +3055,7 @@ pub fn make_call(
                         location,
                         name: CAPTURE_VARIABLE.to_string(),
                     },
+                    shorthand: false,
                 }
             }
         })
@@ -29,21 +29,26 @@ pub struct ParseError {
 
 #[derive(Debug, PartialEq)]
 pub enum ParseErrorType {
-    ExpectedExpr,            // after "->" in a case clause
-    ExpectedName,            // any token used when a Name was expected
-    ExpectedPattern,         // after ':' where a pattern is expected
-    ExpectedType,            // after ':' or '->' where a type annotation is expected
-    ExpectedUpName,          // any token used when a UpName was expected
-    ExpectedValue,           // no value after "="
-    ExprLparStart,           // it seems "(" was used to start an expression
-    ExprThenlessTry,         // a try in the tail position of an expression sequence
-    ExtraSeparator,          // tuple(1,,) <- the 2nd comma is an extra separator
-    IncorrectName,           // UpName or DiscardName used when Name was expected
-    IncorrectUpName,         // Name or DiscardName used when UpName was expected
-    InvalidBitStringSegment, // <<7:hello>> `hello` is an invalid bitstring segment
-    InvalidBitStringUnit,    // in <<1:unit(x)>> x must be 1 <= x <= 256
-    InvalidTailPattern,      // only name and _name are allowed after ".." in list pattern
-    InvalidTupleAccess,      // only positive int literals for tuple access
+    AttributeWithoutDefinition, // @deprecated("...") with nothing after it
+    DuplicateAttribute,         // @deprecated("...") given twice for the same definition
+    DuplicateExternalTarget,    // external fn a() -> Int = erlang "a" "b" erlang "c" "d"
+    EmptyDeprecationMessage,    // @deprecated("") must say why the definition is deprecated
+    ExpectedExpr,               // after "->" in a case clause
+    ExpectedName,               // any token used when a Name was expected
+    ExpectedPattern,            // after ':' where a pattern is expected
+    ExpectedType,               // after ':' or '->' where a type annotation is expected
+    ExpectedUpName,             // any token used when a UpName was expected
+    ExpectedValue,              // no value after "="
+    ExprLparStart,              // it seems "(" was used to start an expression
+    ExprThenlessTry,            // a try in the tail position of an expression sequence
+    ExtraSeparator,             // tuple(1,,) <- the 2nd comma is an extra separator
+    IncorrectName,              // UpName or DiscardName used when Name was expected
+    IncorrectUpName,            // Name or DiscardName used when UpName was expected
+    InvalidAttributeTarget,     // @deprecated("...") on an import or external type
+    InvalidBitStringSegment,    // <<7:hello>> `hello` is an invalid bitstring segment
+    InvalidBitStringUnit,       // in <<1:unit(x)>> x must be 1 <= x <= 256
+    InvalidTailPattern,         // only name and _name are allowed after ".." in list pattern
+    InvalidTupleAccess,         // only positive int literals for tuple access
     LexError { error: LexicalError },
     NestedBitStringPattern, // <<<<1>>, 2>>, <<1>> is not allowed in there
     NoConstructors,         // A type "A {}" must have at least one constructor
@@ -57,6 +62,7 @@ pub enum ParseErrorType {
     UnexpectedEof,
     UnexpectedReservedWord, // reserved word used when a name was expected
     UnexpectedToken { expected: Vec<String> },
+    UnknownAttribute, // only @deprecated("...") is supported
 }
 
 impl LexicalError {
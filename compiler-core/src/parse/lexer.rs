@@ -297,13 +297,8 @@ where
                     let tok_end = self.get_pos();
                     self.emit((tok_start, Token::NotEqual, tok_end));
                 } else {
-                    return Err(LexicalError {
-                        error: LexicalErrorType::UnrecognizedToken { tok: '!' },
-                        location: SrcSpan {
-                            start: tok_start,
-                            end: tok_start,
-                        },
-                    });
+                    let tok_end = self.get_pos();
+                    self.emit((tok_start, Token::Bang, tok_end));
                 }
             }
             '(' => {
@@ -336,6 +331,11 @@ where
                         let tok_end = self.get_pos();
                         self.emit((tok_start, Token::LtLt, tok_end));
                     }
+                    Some('>') => {
+                        let _ = self.next_char();
+                        let tok_end = self.get_pos();
+                        self.emit((tok_start, Token::LtGt, tok_end));
+                    }
                     Some('.') => {
                         let _ = self.next_char();
                         let tok_end = self.get_pos();
@@ -413,6 +413,9 @@ where
             '#' => {
                 self.eat_single_char(Token::Hash);
             }
+            '@' => {
+                self.eat_single_char(Token::At);
+            }
             '\n' => {
                 let _ = self.next_char();
                 let tok_start = self.get_pos();
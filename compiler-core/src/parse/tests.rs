@@ -10,6 +10,13 @@ macro_rules! assert_error {
     };
 }
 
+macro_rules! assert_module_error {
+    ($src:expr, $error:expr $(,)?) => {
+        let result = crate::parse::parse_module($src).expect_err("should not parse");
+        assert_eq!(($src, $error), ($src, result),);
+    };
+}
+
 #[test]
 fn int_tests() {
     // bad binary digit
@@ -190,3 +197,74 @@ fn target_group_trailing_brace() {
         }
     );
 }
+
+#[test]
+fn unknown_attribute() {
+    assert_module_error!(
+        r#"@inline("yes")
+pub fn main() { 1 }"#,
+        ParseError {
+            error: ParseErrorType::UnknownAttribute,
+            location: SrcSpan { start: 0, end: 7 },
+        }
+    );
+}
+
+#[test]
+fn deprecated_import() {
+    assert_module_error!(
+        r#"@deprecated("no")
+import one"#,
+        ParseError {
+            error: ParseErrorType::InvalidAttributeTarget,
+            location: SrcSpan { start: 0, end: 17 },
+        }
+    );
+}
+
+#[test]
+fn deprecated_twice() {
+    assert_module_error!(
+        r#"@deprecated("no")
+@deprecated("no")
+pub fn main() { 1 }"#,
+        ParseError {
+            error: ParseErrorType::DuplicateAttribute,
+            location: SrcSpan { start: 0, end: 17 },
+        }
+    );
+}
+
+#[test]
+fn deprecated_without_message() {
+    assert_module_error!(
+        r#"@deprecated("")
+pub fn main() { 1 }"#,
+        ParseError {
+            error: ParseErrorType::EmptyDeprecationMessage,
+            location: SrcSpan { start: 12, end: 14 },
+        }
+    );
+}
+
+#[test]
+fn attribute_without_definition() {
+    assert_module_error!(
+        r#"@deprecated("no")"#,
+        ParseError {
+            error: ParseErrorType::AttributeWithoutDefinition,
+            location: SrcSpan { start: 0, end: 17 },
+        }
+    );
+}
+
+#[test]
+fn external_fn_duplicate_target() {
+    assert_module_error!(
+        r#"external fn f() -> Int = erlang "a" "b" erlang "c" "d""#,
+        ParseError {
+            error: ParseErrorType::DuplicateExternalTarget,
+            location: SrcSpan { start: 40, end: 54 },
+        }
+    );
+}
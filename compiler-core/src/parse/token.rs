@@ -38,6 +38,8 @@ pub enum Token {
     Colon,
     Comma,
     Hash, // '#'
+    Bang, // '!'
+    At,   // '@'
     Equal,
     EqualEqual, // '=='
     NotEqual,   // '!='
@@ -45,6 +47,7 @@ pub enum Token {
     VbarVbar,   // '||'
     AmperAmper, // '&&'
     LtLt,       // '<<'
+    LtGt,       // '<>'
     GtGt,       // '>>'
     Pipe,       // '|>'
     Dot,        // '.'
@@ -92,6 +95,8 @@ impl Token {
             | Self::GreaterEqualDot
             | Self::GreaterDot => Some(4),
 
+            Self::LtGt => Some(6),
+
             _ => None,
         }
     }
@@ -132,6 +137,8 @@ impl fmt::Display for Token {
             Token::Colon => ":",
             Token::Comma => ",",
             Token::Hash => "#",
+            Token::Bang => "!",
+            Token::At => "@",
             Token::Equal => "=",
             Token::EqualEqual => "==",
             Token::NotEqual => "!=",
@@ -139,6 +146,7 @@ impl fmt::Display for Token {
             Token::VbarVbar => "||",
             Token::AmperAmper => "&&",
             Token::LtLt => "<<",
+            Token::LtGt => "<>",
             Token::GtGt => ">>",
             Token::Pipe => "|>",
             Token::Dot => ".",
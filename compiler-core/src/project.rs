@@ -3,13 +3,13 @@ mod source_tree;
 mod tests;
 
 use crate::{
-    ast::{self, SrcSpan, Statement, TypedModule},
+    ast::{self, TypedModule},
     build::{Origin, Target},
-    parse::extra::{Comment, ModuleExtra},
+    parse::extra::ModuleExtra,
     type_, Error, Result, Warning,
 };
 use source_tree::SourceTree;
-use std::{collections::HashMap, iter::Peekable, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf};
 
 pub const OUTPUT_DIR_NAME: &str = "gen";
 
@@ -29,64 +29,9 @@ pub struct Analysed {
 
 impl Analysed {
     pub fn attach_doc_and_module_comments(&mut self) {
-        // Module Comments
-        self.ast.documentation = self
-            .module_extra
-            .module_comments
-            .iter()
-            .map(|span| Comment::from((span, self.src.as_str())).content.to_string())
-            .collect();
-
-        // Doc Comments
-        let mut doc_comments = self.module_extra.doc_comments.iter().peekable();
-        for statement in &mut self.ast.statements {
-            let docs: Vec<&str> =
-                comments_before(&mut doc_comments, statement.location().start, &self.src);
-            if !docs.is_empty() {
-                let doc = docs.join("\n");
-                statement.put_doc(doc);
-            }
-
-            if let Statement::CustomType { constructors, .. } = statement {
-                for constructor in constructors {
-                    let docs: Vec<&str> =
-                        comments_before(&mut doc_comments, constructor.location.start, &self.src);
-                    if !docs.is_empty() {
-                        let doc = docs.join("\n");
-                        constructor.put_doc(doc);
-                    }
-
-                    for argument in constructor.arguments.iter_mut() {
-                        let docs: Vec<&str> =
-                            comments_before(&mut doc_comments, argument.location.start, &self.src);
-                        if !docs.is_empty() {
-                            let doc = docs.join("\n");
-                            argument.put_doc(doc);
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn comments_before<'a>(
-    comment_spans: &mut Peekable<impl Iterator<Item = &'a SrcSpan>>,
-    byte: usize,
-    src: &'a str,
-) -> Vec<&'a str> {
-    let mut comments = vec![];
-    while let Some(SrcSpan { start, .. }) = comment_spans.peek() {
-        if start <= &byte {
-            let comment = comment_spans
-                .next()
-                .expect("Comment before accessing next span");
-            comments.push(Comment::from((comment, src)).content)
-        } else {
-            break;
-        }
+        self.ast
+            .attach_doc_and_module_comments(&self.module_extra, &self.src)
     }
-    comments
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -135,7 +80,7 @@ pub fn analysed(inputs: Vec<Input>) -> Result<Vec<Analysed>> {
     let mut source_tree = SourceTree::new(inputs)?;
     let mut modules_type_infos = HashMap::new();
     let mut compiled_modules = Vec::with_capacity(module_count);
-    let mut uid = 0;
+    let mut uid = type_::UidGenerator::default();
 
     // Insert the prelude
     // DUPE: preludeinsertion
@@ -1,5 +1,5 @@
 use super::*;
-use crate::io::OutputFile;
+use crate::{ast::SrcSpan, io::OutputFile};
 use std::sync::Arc;
 
 use pretty_assertions::assert_eq;
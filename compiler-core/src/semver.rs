@@ -0,0 +1,238 @@
+//! Comparison of the public interfaces of two releases of a package to find
+//! out which semantic version bump the changes between them require.
+
+#[cfg(test)]
+mod tests;
+
+use crate::package_interface::{ModuleInterface, PackageInterface};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parse a `major.minor.patch` version. Any pre-release or build suffix is
+    /// ignored.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let core = version.split(&['-', '+'][..]).next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+        };
+        match parts.next() {
+            None => Some(version),
+            Some(_) => None,
+        }
+    }
+
+    /// The smallest version that may follow this one when releasing changes
+    /// that need the given bump. Before 1.0.0 breaking changes bump the minor
+    /// version and additions bump the patch version.
+    pub fn minimum_next(&self, bump: Bump) -> Self {
+        let Self {
+            major,
+            minor,
+            patch,
+        } = *self;
+        let bump = match (bump, major) {
+            (Bump::Major, 0) => Bump::Minor,
+            (Bump::Minor, 0) => Bump::Patch,
+            (bump, _) => bump,
+        };
+        match bump {
+            Bump::Major => Self {
+                major: major + 1,
+                minor: 0,
+                patch: 0,
+            },
+            Bump::Minor => Self {
+                major,
+                minor: minor + 1,
+                patch: 0,
+            },
+            Bump::Patch => Self {
+                major,
+                minor,
+                patch: patch + 1,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+impl ChangeKind {
+    pub fn bump(&self) -> Bump {
+        match self {
+            ChangeKind::Added => Bump::Minor,
+            ChangeKind::Changed | ChangeKind::Removed => Bump::Major,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub module: String,
+    /// The kind and name of the definition that changed, or `None` if the
+    /// change is to the whole module.
+    pub item: Option<(&'static str, String)>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ChangeKind::Added => "Added",
+            ChangeKind::Changed => "Changed",
+            ChangeKind::Removed => "Removed",
+        };
+        match &self.item {
+            None => write!(f, "{} module {}", kind, self.module),
+            Some((item_kind, name)) => {
+                write!(f, "{} {} {}.{}", kind, item_kind, self.module, name)
+            }
+        }
+    }
+}
+
+/// The changes to the public interface between the old and new releases of a
+/// package, sorted with the most significant changes first.
+pub fn changes(old: &PackageInterface, new: &PackageInterface) -> Vec<Change> {
+    let mut changes = vec![];
+    for name in keys(&old.modules, &new.modules) {
+        match (old.modules.get(name), new.modules.get(name)) {
+            (Some(old), Some(new)) => module_changes(name, old, new, &mut changes),
+            (old, _) => changes.push(Change {
+                kind: change_kind(old.is_some()),
+                module: name.clone(),
+                item: None,
+            }),
+        }
+    }
+    changes.sort_by(|a, b| b.kind.cmp(&a.kind).then_with(|| a.cmp(b)));
+    changes
+}
+
+/// The smallest version bump that permits all of the given changes.
+pub fn required_bump(changes: &[Change]) -> Bump {
+    changes
+        .iter()
+        .map(|change| change.kind.bump())
+        .max()
+        .unwrap_or(Bump::Patch)
+}
+
+fn module_changes(
+    module: &str,
+    old: &ModuleInterface,
+    new: &ModuleInterface,
+    changes: &mut Vec<Change>,
+) {
+    // Documentation is not part of the interface so it is ignored when
+    // checking whether a definition has changed.
+    compare(module, "type", &old.types, &new.types, changes, |a, b| {
+        a.parameters == b.parameters
+            && a.constructors.len() == b.constructors.len()
+            && a.constructors
+                .iter()
+                .zip(&b.constructors)
+                .all(|(a, b)| a.name == b.name && a.parameters == b.parameters)
+    });
+    compare(
+        module,
+        "type alias",
+        &old.type_aliases,
+        &new.type_aliases,
+        changes,
+        |a, b| a.parameters == b.parameters && a.alias == b.alias,
+    );
+    compare(
+        module,
+        "function",
+        &old.functions,
+        &new.functions,
+        changes,
+        |a, b| a.parameters == b.parameters && a.return_ == b.return_,
+    );
+    compare(
+        module,
+        "constant",
+        &old.constants,
+        &new.constants,
+        changes,
+        |a, b| a.type_ == b.type_,
+    );
+}
+
+fn compare<T>(
+    module: &str,
+    item_kind: &'static str,
+    old: &BTreeMap<String, T>,
+    new: &BTreeMap<String, T>,
+    changes: &mut Vec<Change>,
+    same: impl Fn(&T, &T) -> bool,
+) {
+    for name in keys(old, new) {
+        let kind = match (old.get(name), new.get(name)) {
+            (Some(old), Some(new)) if same(old, new) => continue,
+            (Some(_), Some(_)) => ChangeKind::Changed,
+            (old, _) => change_kind(old.is_some()),
+        };
+        changes.push(Change {
+            kind,
+            module: module.to_string(),
+            item: Some((item_kind, name.clone())),
+        });
+    }
+}
+
+fn change_kind(in_old: bool) -> ChangeKind {
+    if in_old {
+        ChangeKind::Removed
+    } else {
+        ChangeKind::Added
+    }
+}
+
+fn keys<'a, T>(old: &'a BTreeMap<String, T>, new: &'a BTreeMap<String, T>) -> BTreeSet<&'a String> {
+    old.keys().chain(new.keys()).collect()
+}
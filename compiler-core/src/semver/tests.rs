@@ -0,0 +1,133 @@
+use super::*;
+use crate::package_interface::{
+    ConstantInterface, FunctionInterface, ParameterInterface, TypeInterface,
+};
+
+fn version(major: u64, minor: u64, patch: u64) -> Version {
+    Version {
+        major,
+        minor,
+        patch,
+    }
+}
+
+fn named(name: &str) -> TypeInterface {
+    TypeInterface::Named {
+        name: name.to_string(),
+        module: "gleam".to_string(),
+        parameters: vec![],
+    }
+}
+
+fn function(parameters: Vec<TypeInterface>, return_: TypeInterface) -> FunctionInterface {
+    FunctionInterface {
+        documentation: None,
+        parameters: parameters
+            .into_iter()
+            .map(|type_| ParameterInterface { label: None, type_ })
+            .collect(),
+        return_,
+    }
+}
+
+fn package(modules: Vec<(&str, ModuleInterface)>) -> PackageInterface {
+    let mut package = PackageInterface::new("thepackage".to_string(), "1.0.0".to_string());
+    for (name, module) in modules {
+        package.insert_module(module, name.to_string());
+    }
+    package
+}
+
+fn module(functions: Vec<(&str, FunctionInterface)>) -> ModuleInterface {
+    let mut module = ModuleInterface::default();
+    for (name, function) in functions {
+        let _ = module.functions.insert(name.to_string(), function);
+    }
+    module
+}
+
+#[test]
+fn parse_version() {
+    assert_eq!(Version::parse("1.2.3"), Some(version(1, 2, 3)));
+    assert_eq!(Version::parse("v0.10.0"), Some(version(0, 10, 0)));
+    assert_eq!(Version::parse("1.0.0-rc1"), Some(version(1, 0, 0)));
+    assert_eq!(Version::parse("1.0"), None);
+    assert_eq!(Version::parse("1.0.0.0"), None);
+    assert_eq!(Version::parse("one"), None);
+}
+
+#[test]
+fn minimum_next_version() {
+    assert_eq!(version(1, 2, 3).minimum_next(Bump::Major), version(2, 0, 0));
+    assert_eq!(version(1, 2, 3).minimum_next(Bump::Minor), version(1, 3, 0));
+    assert_eq!(version(1, 2, 3).minimum_next(Bump::Patch), version(1, 2, 4));
+    assert_eq!(version(0, 2, 3).minimum_next(Bump::Major), version(0, 3, 0));
+    assert_eq!(version(0, 2, 3).minimum_next(Bump::Minor), version(0, 2, 4));
+    assert_eq!(version(0, 2, 3).minimum_next(Bump::Patch), version(0, 2, 4));
+}
+
+#[test]
+fn no_changes() {
+    let old = package(vec![(
+        "one",
+        module(vec![("id", function(vec![named("Int")], named("Int")))]),
+    )]);
+    let mut new = old.clone();
+    if let Some(module) = new.modules.get_mut("one") {
+        module.documentation = vec![" Docs are not part of the API".to_string()];
+    }
+    assert_eq!(changes(&old, &new), vec![]);
+    assert_eq!(required_bump(&changes(&old, &new)), Bump::Patch);
+}
+
+#[test]
+fn added_removed_and_changed() {
+    let old = package(vec![
+        (
+            "one",
+            module(vec![
+                ("changed", function(vec![named("Int")], named("Int"))),
+                ("removed", function(vec![], named("Int"))),
+            ]),
+        ),
+        ("gone", module(vec![])),
+    ]);
+    let new = package(vec![
+        (
+            "one",
+            module(vec![
+                ("changed", function(vec![named("Float")], named("Int"))),
+                ("added", function(vec![], named("Int"))),
+            ]),
+        ),
+        ("new", module(vec![])),
+    ]);
+    let changes = changes(&old, &new);
+    assert_eq!(
+        changes.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+        vec![
+            "Removed module gone",
+            "Removed function one.removed",
+            "Changed function one.changed",
+            "Added module new",
+            "Added function one.added",
+        ]
+    );
+    assert_eq!(required_bump(&changes), Bump::Major);
+}
+
+#[test]
+fn additions_need_a_minor_bump() {
+    let old = package(vec![("one", module(vec![]))]);
+    let mut new = old.clone();
+    if let Some(module) = new.modules.get_mut("one") {
+        let _ = module.constants.insert(
+            "size".to_string(),
+            ConstantInterface {
+                documentation: None,
+                type_: named("Int"),
+            },
+        );
+    }
+    assert_eq!(required_bump(&changes(&old, &new)), Bump::Minor);
+}
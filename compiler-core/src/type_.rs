@@ -7,6 +7,8 @@ mod pattern;
 mod pipe;
 mod prelude;
 pub mod pretty;
+mod refutability;
+mod target_support;
 #[cfg(test)]
 mod test_helpers;
 #[cfg(test)]
@@ -19,22 +21,23 @@ pub use prelude::*;
 
 use crate::{
     ast::{
-        self, ArgNames, BitStringSegment, BitStringSegmentOption, CallArg, Constant, Pattern,
-        RecordConstructor, RecordConstructorArg, SrcSpan, Statement, TypeAst, TypedConstant,
-        TypedExpr, TypedModule, TypedPattern, TypedPatternBitStringSegment, TypedRecordUpdateArg,
-        TypedStatement, UnqualifiedImport, UntypedModule, UntypedMultiPattern, UntypedPattern,
-        UntypedRecordUpdateArg, UntypedStatement,
+        self, ArgNames, BitStringSegment, BitStringSegmentOption, CallArg, Constant, Deprecation,
+        Pattern, RecordConstructor, RecordConstructorArg, SrcSpan, Statement, TypeAst,
+        TypedConstant, TypedExpr, TypedModule, TypedPattern, TypedPatternBitStringSegment,
+        TypedRecordUpdateArg, TypedStatement, UnqualifiedImport, UntypedModule,
+        UntypedMultiPattern, UntypedPattern, UntypedRecordUpdateArg, UntypedStatement,
     },
     bit_string,
     build::{Origin, Target},
 };
 use expr::*;
+use target_support::{FunctionReference, TargetSupport};
 
 use std::{
-    cell::RefCell,
     collections::{HashMap, HashSet},
+    fmt::Debug,
     ops::Deref,
-    sync::Arc,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
 use error::*;
@@ -60,7 +63,7 @@ pub enum Type {
     },
 
     Var {
-        type_: Arc<RefCell<TypeVar>>,
+        type_: Arc<TypeVarCell>,
     },
 
     Tuple {
@@ -74,7 +77,7 @@ impl Type {
     }
 
     pub fn is_unbound(&self) -> bool {
-        matches!(self, Self::Var { type_: typ } if typ.borrow().is_unbound())
+        matches!(self, Self::Var { type_: typ } if typ.read().is_unbound())
     }
 
     pub fn return_type(&self) -> Option<Arc<Self>> {
@@ -84,6 +87,20 @@ impl Type {
         }
     }
 
+    /// The module and name of the custom type this type is, or of the custom
+    /// type returned if this is the type of a record constructor function.
+    pub fn constructed_type(&self) -> Option<(Vec<String>, String)> {
+        match self {
+            Self::App { module, name, .. } => Some((module.clone(), name.clone())),
+            Self::Fn { retrn, .. } => retrn.constructed_type(),
+            Self::Var { type_ } => match type_.read().deref() {
+                TypeVar::Link { type_ } => type_.constructed_type(),
+                TypeVar::Unbound { .. } | TypeVar::Generic { .. } => None,
+            },
+            Self::Tuple { .. } => None,
+        }
+    }
+
     pub fn fn_types(&self) -> Option<(Vec<Arc<Self>>, Arc<Self>)> {
         match self {
             Self::Fn { args, retrn, .. } => Some((args.clone(), retrn.clone())),
@@ -94,7 +111,7 @@ impl Type {
     pub fn is_nil(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Nil" == name && module.is_empty() => true,
-            Self::Var { type_ } => type_.borrow().is_nil(),
+            Self::Var { type_ } => type_.read().is_nil(),
             _ => false,
         }
     }
@@ -102,7 +119,7 @@ impl Type {
     pub fn is_bool(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Bool" == name && module.is_empty() => true,
-            Self::Var { type_ } => type_.borrow().is_bool(),
+            Self::Var { type_ } => type_.read().is_bool(),
             _ => false,
         }
     }
@@ -110,7 +127,7 @@ impl Type {
     pub fn is_int(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Int" == name && module.is_empty() => true,
-            Self::Var { type_ } => type_.borrow().is_int(),
+            Self::Var { type_ } => type_.read().is_int(),
             _ => false,
         }
     }
@@ -118,7 +135,7 @@ impl Type {
     pub fn is_float(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Float" == name && module.is_empty() => true,
-            Self::Var { type_ } => type_.borrow().is_float(),
+            Self::Var { type_ } => type_.read().is_float(),
             _ => false,
         }
     }
@@ -126,7 +143,7 @@ impl Type {
     pub fn is_string(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "String" == name && module.is_empty() => true,
-            Self::Var { type_ } => type_.borrow().is_string(),
+            Self::Var { type_ } => type_.read().is_string(),
             _ => false,
         }
     }
@@ -157,7 +174,7 @@ impl Type {
             }
 
             Self::Var { type_: typ } => {
-                let args: Vec<_> = match typ.borrow().deref() {
+                let args: Vec<_> = match typ.read().deref() {
                     TypeVar::Link { type_: typ } => {
                         return typ.get_app_args(public, module, name, arity, environment);
                     }
@@ -170,7 +187,7 @@ impl Type {
                 };
 
                 // TODO: use the real type here rather than making a copy
-                *typ.borrow_mut() = TypeVar::Link {
+                *typ.write() = TypeVar::Link {
                     type_: Arc::new(Self::App {
                         name: name.to_string(),
                         module: module.to_owned(),
@@ -197,7 +214,7 @@ impl Type {
                 .find_private_type()
                 .or_else(|| args.iter().find_map(|t| t.find_private_type())),
 
-            Self::Var { type_: typ, .. } => match typ.borrow().deref() {
+            Self::Var { type_: typ, .. } => match typ.read().deref() {
                 TypeVar::Unbound { .. } => None,
 
                 TypeVar::Generic { .. } => None,
@@ -217,7 +234,7 @@ impl Type {
 
 pub fn collapse_links(t: Arc<Type>) -> Arc<Type> {
     if let Type::Var { type_: typ } = t.deref() {
-        if let TypeVar::Link { type_: typ } = typ.borrow().deref() {
+        if let TypeVar::Link { type_: typ } = typ.read().deref() {
             return typ.clone();
         }
     }
@@ -255,6 +272,9 @@ pub enum ValueConstructorVariant {
         field_map: Option<FieldMap>,
         module: Vec<String>,
         arity: usize,
+        /// The targets the function can be used on. External functions may
+        /// only be implemented for some targets.
+        targets: Vec<Target>,
     },
 
     /// A constructor for a custom type
@@ -332,6 +352,86 @@ pub enum TypeVar {
     Generic { id: usize },
 }
 
+/// A type variable that can be updated as types are unified. A lock is used
+/// rather than a `RefCell` so that types can be shared between the threads
+/// type checking modules concurrently. Exported types have no unbound type
+/// variables, so a variable is only ever written to by the module that created
+/// it and threads never wait for each other's locks.
+pub struct TypeVarCell(RwLock<TypeVar>);
+
+impl TypeVarCell {
+    pub fn new(type_: TypeVar) -> Self {
+        Self(RwLock::new(type_))
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, TypeVar> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, TypeVar> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Debug for TypeVarCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.read().fmt(f)
+    }
+}
+
+impl PartialEq for TypeVarCell {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other) || *self.read() == *other.read()
+    }
+}
+
+/// Allocates the ids of type variables. Modules type checked at the same time
+/// are given interleaved ids so that their type variables are always distinct,
+/// while each module still gets the same ids however they are scheduled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UidGenerator {
+    next: usize,
+    step: usize,
+}
+
+impl Default for UidGenerator {
+    fn default() -> Self {
+        Self { next: 0, step: 1 }
+    }
+}
+
+impl UidGenerator {
+    pub fn next_uid(&mut self) -> usize {
+        let uid = self.next;
+        self.next += self.step;
+        uid
+    }
+
+    pub fn previous_uid(&self) -> usize {
+        self.next - self.step
+    }
+
+    /// Split the ids still to be allocated between a number of modules.
+    pub fn interleave(&self, count: usize) -> Vec<Self> {
+        (0..count)
+            .map(|index| Self {
+                next: self.next + index * self.step,
+                step: self.step * count,
+            })
+            .collect()
+    }
+
+    /// Continue allocating after the ids used by the generators returned by
+    /// `interleave`.
+    pub fn resume(&mut self, interleaved: impl IntoIterator<Item = Self>) {
+        // Each of the interleaved ids is a whole number of steps from `next`
+        // so the largest can be used without skipping over any gaps.
+        for generator in interleaved {
+            self.next = self.next.max(generator.next);
+        }
+    }
+}
+
 impl TypeVar {
     pub fn is_unbound(&self) -> bool {
         matches!(self, Self::Unbound { .. })
@@ -380,6 +480,7 @@ pub struct TypeConstructor {
     pub module: Vec<String>,
     pub parameters: Vec<Arc<Type>>,
     pub typ: Arc<Type>,
+    pub deprecation: Deprecation,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -388,6 +489,7 @@ pub struct ValueConstructor {
     pub origin: SrcSpan,
     pub variant: ValueConstructorVariant,
     pub type_: Arc<Type>,
+    pub deprecation: Deprecation,
 }
 
 impl ValueConstructor {
@@ -420,7 +522,7 @@ impl ValueConstructor {
 ///
 pub fn infer_module(
     target: Target,
-    uid: &mut usize,
+    uid: &mut UidGenerator,
     mut module: UntypedModule,
     origin: Origin,
     package: &str,
@@ -429,7 +531,7 @@ pub fn infer_module(
 ) -> Result<TypedModule, Error> {
     let name = module.name.clone();
     let documentation = std::mem::take(&mut module.documentation);
-    let mut environment = Environment::new(uid, &name, modules, warnings);
+    let mut environment = Environment::new(uid, &name, target, modules, warnings);
     validate_module_name(&name)?;
 
     let mut type_names = HashMap::with_capacity(module.statements.len());
@@ -479,11 +581,33 @@ pub fn infer_module(
     }
 
     // Generalise functions now that the entire module has been inferred
-    let statements = statements
+    let mut statements: Vec<_> = statements
         .into_iter()
         .map(|s| generalise_statement(s, &name, &mut environment))
         .collect();
 
+    // Work out which targets each function can be compiled for now that
+    // every function they could reference has been inferred
+    let function_targets = environment.target_support.resolve();
+    environment.target_support.check_references(target)?;
+    for (function, function_targets) in function_targets {
+        if let Some(ValueConstructor {
+            variant: ValueConstructorVariant::ModuleFn { targets, .. },
+            ..
+        }) = environment.module_values.get_mut(&function)
+        {
+            *targets = function_targets;
+        }
+    }
+
+    // Functions that cannot be compiled for the target are left out of the
+    // generated code, the same as external functions without an
+    // implementation for it
+    let unsupported = environment.target_support.unsupported_functions(target);
+    statements.retain(
+        |statement| !matches!(statement, Statement::Fn { name, .. } if unsupported.contains(name)),
+    );
+
     // Generate warnings for unused items
     environment.convert_unused_to_warnings();
 
@@ -492,6 +616,9 @@ pub fn infer_module(
         .module_types
         .retain(|_, info| info.public && info.module.as_slice() == name.as_slice());
     environment.module_values.retain(|_, info| info.public);
+    for value in environment.module_values.values_mut() {
+        value.type_ = generalise_exported(&value.type_);
+    }
     environment
         .accessors
         .retain(|_, accessors| accessors.public);
@@ -647,6 +774,7 @@ fn register_values<'a>(
                     field_map,
                     module: module_name.to_vec(),
                     arity: args.len(),
+                    targets: Target::all(),
                 },
                 typ,
                 *location,
@@ -662,8 +790,8 @@ fn register_values<'a>(
             public,
             arguments: args,
             return_: retrn,
-            module,
-            fun,
+            implementations,
+            deprecation,
             ..
         } => {
             assert_unique_value_name(names, name, location)?;
@@ -692,6 +820,26 @@ fn register_values<'a>(
                 Ok((typ, field_map))
             })?;
 
+            // Erlang code calls the implementing function directly, so when there
+            // is no Erlang implementation the function is referred to by its own
+            // name. Calling it from Erlang is an error as the target is not
+            // supported.
+            let (module, fun) =
+                match ast::external_fn_implementation(implementations, Target::Erlang) {
+                    Some(implementation) => (
+                        vec![implementation.module.clone()],
+                        implementation.fun.clone(),
+                    ),
+                    None => (module_name.to_vec(), name.clone()),
+                };
+            let variant = ValueConstructorVariant::ModuleFn {
+                name: fun,
+                field_map,
+                module,
+                arity: args.len(),
+                targets: ast::external_fn_targets(implementations),
+            };
+
             // Insert function into module
             environment.insert_module_value(
                 name,
@@ -699,27 +847,13 @@ fn register_values<'a>(
                     public: *public,
                     type_: typ.clone(),
                     origin: *location,
-                    variant: ValueConstructorVariant::ModuleFn {
-                        name: fun.clone(),
-                        field_map: field_map.clone(),
-                        module: vec![module.clone()],
-                        arity: args.len(),
-                    },
+                    variant: variant.clone(),
+                    deprecation: deprecation.clone(),
                 },
             );
 
             // Insert function into module's internal scope
-            environment.insert_variable(
-                name.clone(),
-                ValueConstructorVariant::ModuleFn {
-                    name: fun.clone(),
-                    module: vec![module.clone()],
-                    arity: args.len(),
-                    field_map,
-                },
-                typ,
-                *location,
-            );
+            environment.insert_variable(name.clone(), variant, typ, *location);
             if !public {
                 environment.init_usage(name.clone(), EntityKind::PrivateFunction, *location);
             }
@@ -731,6 +865,7 @@ fn register_values<'a>(
             opaque,
             name,
             constructors,
+            deprecation,
             ..
         } => {
             let mut hydrator = hydrators
@@ -745,8 +880,8 @@ fn register_values<'a>(
                 .typ
                 .clone();
 
-            // If the custom type only has a single constructor then we can access the
-            // fields using the record.field syntax, so store any fields accessors.
+            // Fields shared by every constructor can be accessed using the
+            // record.field syntax, so store any fields accessors.
             if let Some(accessors) =
                 custom_type_accessors(constructors, &mut hydrator, environment)?
             {
@@ -760,6 +895,11 @@ fn register_values<'a>(
                 environment.insert_accessors(name, map)
             }
 
+            let _ = environment.module_types_constructors.insert(
+                name.clone(),
+                constructors.iter().map(|c| c.name.clone()).collect(),
+            );
+
             // Check and register constructors
             for constructor in constructors {
                 assert_unique_value_name(names, &constructor.name, &constructor.location)?;
@@ -799,6 +939,7 @@ fn register_values<'a>(
                                 arity: constructor.arguments.len(),
                                 field_map: field_map.clone(),
                             },
+                            deprecation: deprecation.clone(),
                         },
                     );
                 }
@@ -850,6 +991,7 @@ fn generalise_statement(
             return_annotation,
             end_location,
             return_type,
+            deprecation,
         } => {
             // Lookup the inferred function information
             let function = environment
@@ -878,7 +1020,9 @@ fn generalise_statement(
                         field_map,
                         module: module_name.to_vec(),
                         arity: args.len(),
+                        targets: Target::all(),
                     },
+                    deprecation: deprecation.clone(),
                 },
             );
 
@@ -892,12 +1036,11 @@ fn generalise_statement(
                 return_annotation,
                 return_type,
                 body,
+                deprecation,
             }
         }
 
-        statement
-        @
-        (Statement::TypeAlias { .. }
+        statement @ (Statement::TypeAlias { .. }
         | Statement::CustomType { .. }
         | Statement::ExternalFn { .. }
         | Statement::ExternalType { .. }
@@ -922,6 +1065,7 @@ fn infer_statement(
             body,
             return_annotation,
             end_location,
+            deprecation,
             ..
         } => {
             let preregistered_fn = environment
@@ -934,7 +1078,7 @@ fn infer_statement(
                 .expect("Preregistered type for fn was not a fn");
 
             // Infer the type using the preregistered args + return types as a starting point
-            let (typ, args, body, safe_to_generalise) =
+            let (typ, args, body, safe_to_generalise, references) =
                 environment.in_new_scope(|environment| {
                     let args = args
                         .into_iter()
@@ -950,8 +1094,12 @@ fn infer_statement(
                     let args_types = args.iter().map(|a| a.type_.clone()).collect();
                     let typ = fn_(args_types, body.type_());
                     let safe_to_generalise = !expr_typer.ungeneralised_function_used;
-                    Ok((typ, args, body, safe_to_generalise))
+                    let references = expr_typer.function_references;
+                    Ok((typ, args, body, safe_to_generalise, references))
                 })?;
+            environment
+                .target_support
+                .register(&name, public, Target::all(), references);
 
             // Assert that the inferred type matches the type of any recursive call
             environment
@@ -969,6 +1117,7 @@ fn infer_statement(
                         field_map,
                         module: module_name.to_vec(),
                         arity: args.len(),
+                        targets: Target::all(),
                     },
                     typ.clone(),
                     location,
@@ -990,6 +1139,7 @@ fn infer_statement(
                     .return_type()
                     .expect("Could not find return type for fn"),
                 body,
+                deprecation,
             })
         }
 
@@ -1000,8 +1150,8 @@ fn infer_statement(
             public,
             arguments: args,
             return_: retrn,
-            module,
-            fun,
+            implementations,
+            deprecation,
             ..
         } => {
             let preregistered_fn = environment
@@ -1016,6 +1166,12 @@ fn infer_statement(
                 .zip(&args_types)
                 .map(|(a, t)| a.set_type(t.clone()))
                 .collect();
+            environment.target_support.register(
+                &name,
+                public,
+                ast::external_fn_targets(&implementations),
+                vec![],
+            );
             Ok(Statement::ExternalFn {
                 return_type,
                 doc,
@@ -1024,8 +1180,8 @@ fn infer_statement(
                 public,
                 arguments: args,
                 return_: retrn,
-                module,
-                fun,
+                implementations,
+                deprecation,
             })
         }
 
@@ -1036,6 +1192,7 @@ fn infer_statement(
             alias,
             parameters: args,
             type_ast: resolved_type,
+            deprecation,
             ..
         } => {
             let typ = environment
@@ -1051,6 +1208,7 @@ fn infer_statement(
                 parameters: args,
                 type_ast: resolved_type,
                 type_: typ,
+                deprecation,
             })
         }
 
@@ -1062,6 +1220,7 @@ fn infer_statement(
             name,
             parameters,
             constructors,
+            deprecation,
             ..
         } => {
             let constructors = constructors
@@ -1131,6 +1290,7 @@ fn infer_statement(
                 parameters,
                 constructors,
                 typed_parameters,
+                deprecation,
             })
         }
 
@@ -1193,6 +1353,7 @@ fn infer_statement(
             annotation,
             public,
             value,
+            deprecation,
             ..
         } => {
             let typed_expr = ExprTyper::new(environment).infer_const(&annotation, *value)?;
@@ -1207,6 +1368,7 @@ fn infer_statement(
                         literal: typed_expr.clone(),
                     },
                     type_: type_.clone(),
+                    deprecation: deprecation.clone(),
                 },
             );
 
@@ -1222,6 +1384,7 @@ fn infer_statement(
                 public,
                 value: Box::new(typed_expr),
                 type_,
+                deprecation,
             })
         }
     }
@@ -1319,7 +1482,7 @@ fn assert_no_labelled_arguments<A>(args: &[CallArg<A>]) -> Result<(), Error> {
 ///
 fn update_levels(typ: Arc<Type>, own_level: usize, own_id: usize) -> Result<(), UnifyError> {
     if let Type::Var { type_: typ } = typ.deref() {
-        let new_value = match typ.borrow().deref() {
+        let new_value = match typ.read().deref() {
             TypeVar::Link { type_: typ, .. } => {
                 return update_levels(typ.clone(), own_level, own_id)
             }
@@ -1341,7 +1504,7 @@ fn update_levels(typ: Arc<Type>, own_level: usize, own_id: usize) -> Result<(),
         };
 
         if let Some(t) = new_value {
-            *typ.borrow_mut() = t;
+            *typ.write() = t;
         }
         return Ok(());
     }
@@ -1378,7 +1541,7 @@ fn match_fun_type(
     environment: &mut Environment<'_, '_>,
 ) -> Result<(Vec<Arc<Type>>, Arc<Type>), MatchFunTypeError> {
     if let Type::Var { type_: typ } = typ.deref() {
-        let new_value = match typ.borrow().deref() {
+        let new_value = match typ.read().deref() {
             TypeVar::Link { type_: typ, .. } => {
                 return match_fun_type(typ.clone(), arity, environment)
             }
@@ -1395,7 +1558,7 @@ fn match_fun_type(
         };
 
         if let Some((args, retrn)) = new_value {
-            *typ.borrow_mut() = TypeVar::Link {
+            *typ.write() = TypeVar::Link {
                 type_: fn_(args.clone(), retrn.clone()),
             };
             return Ok((args, retrn));
@@ -1416,18 +1579,60 @@ fn match_fun_type(
     Err(MatchFunTypeError::NotFn { typ })
 }
 
+/// Copy a type with any type variables that are still unbound made generic,
+/// such as the element type of `pub const empty = []`.
+///
+/// This changes what programs type check: each module importing the value
+/// gets its own instance of the variable and so can use the value as a
+/// different type. If the variable were shared instead the first module to
+/// use the value would decide its type for every other module, and as modules
+/// that do not depend on each other are checked concurrently which module that
+/// is would not be deterministic. Within the module that defines the value it
+/// still has a single type, decided by its uses there.
+fn generalise_exported(t: &Arc<Type>) -> Arc<Type> {
+    match t.deref() {
+        Type::Var { type_ } => {
+            let var = type_.read().clone();
+            match var {
+                TypeVar::Link { type_ } => generalise_exported(&type_),
+                TypeVar::Unbound { id, .. } => generic_var(id),
+                TypeVar::Generic { .. } => t.clone(),
+            }
+        }
+
+        Type::App {
+            public,
+            module,
+            name,
+            args,
+        } => Arc::new(Type::App {
+            public: *public,
+            module: module.clone(),
+            name: name.clone(),
+            args: args.iter().map(generalise_exported).collect(),
+        }),
+
+        Type::Fn { args, retrn } => fn_(
+            args.iter().map(generalise_exported).collect(),
+            generalise_exported(retrn),
+        ),
+
+        Type::Tuple { elems } => tuple(elems.iter().map(generalise_exported).collect()),
+    }
+}
+
 /// Takes a level and a type and turns all type variables within the type that have
 /// level higher than the input level into generalized (polymorphic) type variables.
 ///
 fn generalise(t: Arc<Type>, ctx_level: usize) -> Arc<Type> {
     match t.deref() {
         Type::Var { type_: typ } => {
-            let new_var = match typ.borrow().deref() {
+            let new_var = match typ.read().deref() {
                 TypeVar::Unbound { id, level } => {
                     let id = *id;
                     if *level > ctx_level {
                         return Arc::new(Type::Var {
-                            type_: Arc::new(RefCell::new(TypeVar::Generic { id })),
+                            type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id })),
                         });
                     } else {
                         Some(TypeVar::Unbound { id, level: *level })
@@ -1440,7 +1645,7 @@ fn generalise(t: Arc<Type>, ctx_level: usize) -> Arc<Type> {
             };
 
             if let Some(v) = new_var {
-                *typ.borrow_mut() = v;
+                *typ.write() = v;
             }
             Arc::new(Type::Var { type_: typ.clone() })
         }
@@ -1499,11 +1704,14 @@ fn custom_type_accessors<A>(
     hydrator: &mut Hydrator,
     environment: &mut Environment<'_, '_>,
 ) -> Result<Option<HashMap<String, RecordAccessor>>, Error> {
-    // Get the constructor for this custom type.
-    let args = match constructors {
-        [constructor] if !constructor.arguments.is_empty() => &constructor.arguments,
-        // If there is not exactly 1 constructor we return as we cannot
-        // build any constructors.
+    // Fields are found by position at runtime so a field can only be accessed
+    // if every constructor has it, at the same position and with the same type.
+    let (args, other_constructors) = match constructors.split_first() {
+        Some((constructor, rest)) if !constructor.arguments.is_empty() => {
+            (&constructor.arguments, rest)
+        }
+        // If there are no constructors with fields we return as we cannot
+        // build any accessors.
         _ => return Ok(None),
     };
 
@@ -1512,6 +1720,16 @@ fn custom_type_accessors<A>(
     for (index, RecordConstructorArg { label, ast, .. }) in args.iter().enumerate() {
         if let Some(label) = label {
             let typ = hydrator.type_from_ast(ast, environment)?;
+            if !field_is_shared(
+                other_constructors,
+                index,
+                label,
+                &typ,
+                hydrator,
+                environment,
+            )? {
+                continue;
+            }
             let _ = fields.insert(
                 label.to_string(),
                 RecordAccessor {
@@ -1525,6 +1743,33 @@ fn custom_type_accessors<A>(
     Ok(Some(fields))
 }
 
+/// Whether each of the constructors has a field with the given label, index
+/// and type.
+fn field_is_shared<A>(
+    constructors: &[RecordConstructor<A>],
+    index: usize,
+    label: &str,
+    typ: &Arc<Type>,
+    hydrator: &mut Hydrator,
+    environment: &mut Environment<'_, '_>,
+) -> Result<bool, Error> {
+    for constructor in constructors {
+        match constructor.arguments.get(index) {
+            Some(RecordConstructorArg {
+                label: Some(other_label),
+                ast,
+                ..
+            }) if other_label == label => {
+                if hydrator.type_from_ast(ast, environment)? != *typ {
+                    return Ok(false);
+                }
+            }
+            _ => return Ok(false),
+        }
+    }
+    Ok(true)
+}
+
 /// Iterate over a module, registering any new types created by the module into the typer
 pub fn register_types<'a>(
     statement: &'a UntypedStatement,
@@ -1562,6 +1807,7 @@ pub fn register_types<'a>(
                     public: *public,
                     parameters,
                     typ,
+                    deprecation: Deprecation::NotDeprecated,
                 },
             )?;
 
@@ -1576,6 +1822,7 @@ pub fn register_types<'a>(
             public,
             parameters,
             location,
+            deprecation,
             ..
         } => {
             assert_unique_type_name(names, name, location)?;
@@ -1599,6 +1846,7 @@ pub fn register_types<'a>(
                     public: *public,
                     parameters,
                     typ,
+                    deprecation: deprecation.clone(),
                 },
             )?;
             // Keep track of private types so we can tell if they are later unused
@@ -1613,6 +1861,7 @@ pub fn register_types<'a>(
             parameters: args,
             alias: name,
             type_ast: resolved_type,
+            deprecation,
             ..
         } => {
             assert_unique_type_name(names, name, location)?;
@@ -1634,6 +1883,7 @@ pub fn register_types<'a>(
                     public: *public,
                     parameters,
                     typ,
+                    deprecation: deprecation.clone(),
                 },
             )?;
 
@@ -1706,11 +1956,15 @@ pub fn register_import(
                 }
                 // Register the unqualified import if it is a value
                 if let Some(value) = module_info.values.get(name) {
-                    environment.insert_variable(
+                    // The value keeps its deprecation so that it can be reported
+                    // where the value is used
+                    let _ = environment.local_values.insert(
                         imported_name.clone(),
-                        value.variant.clone(),
-                        value.type_.clone(),
-                        *location,
+                        ValueConstructor {
+                            public: false,
+                            origin: *location,
+                            ..value.clone()
+                        },
                     );
                     variant = Some(&value.variant);
                     value_imported = true;
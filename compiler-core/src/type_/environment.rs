@@ -6,7 +6,9 @@ use std::collections::HashMap;
 #[derive(Debug)]
 pub struct Environment<'a, 'b> {
     pub current_module: &'a [String],
-    pub uid: &'b mut usize,
+    /// The target the current module is being compiled for
+    pub target: Target,
+    pub uid: &'b mut UidGenerator,
     pub level: usize,
     pub importable_modules: &'a HashMap<String, Module>,
     pub imported_modules: HashMap<String, Module>,
@@ -23,6 +25,10 @@ pub struct Environment<'a, 'b> {
     /// Accessors defined in the current module
     pub accessors: HashMap<String, AccessorsMap>,
 
+    /// The names of the constructors of each custom type defined in the
+    /// current module
+    pub module_types_constructors: HashMap<String, Vec<String>>,
+
     /// Warnings
     pub warnings: &'a mut Vec<Warning>,
 
@@ -35,6 +41,9 @@ pub struct Environment<'a, 'b> {
     /// added to the top scope. When an entity is used we crawl down the scope
     /// stack for an entity with that name and mark it as used.
     pub entity_usages: Vec<HashMap<String, (EntityKind, SrcSpan, bool)>>,
+
+    /// The targets each function in the current module can be compiled for
+    pub target_support: TargetSupport,
 }
 
 /// For Keeping track of entity usages and knowing which error to display.
@@ -54,8 +63,9 @@ pub enum EntityKind {
 
 impl<'a, 'b> Environment<'a, 'b> {
     pub fn new(
-        uid: &'b mut usize,
+        uid: &'b mut UidGenerator,
         current_module: &'a [String],
+        target: Target,
         importable_modules: &'a HashMap<String, Module>,
         warnings: &'a mut Vec<Warning>,
     ) -> Self {
@@ -70,11 +80,14 @@ impl<'a, 'b> Environment<'a, 'b> {
             module_values: HashMap::new(),
             imported_modules: HashMap::new(),
             accessors: prelude.accessors.clone(),
+            module_types_constructors: HashMap::new(),
             local_values: prelude.values.clone().into(),
             importable_modules,
             current_module,
+            target,
             warnings,
             entity_usages: vec![HashMap::new()],
+            target_support: TargetSupport::default(),
         }
     }
 }
@@ -116,13 +129,11 @@ impl<'a, 'b> Environment<'a, 'b> {
     }
 
     pub fn next_uid(&mut self) -> usize {
-        let i = *self.uid;
-        *self.uid += 1;
-        i
+        self.uid.next_uid()
     }
 
     pub fn previous_uid(&self) -> usize {
-        *self.uid - 1
+        self.uid.previous_uid()
     }
 
     /// Create a new unbound type that is a specific type, we just don't
@@ -154,6 +165,7 @@ impl<'a, 'b> Environment<'a, 'b> {
                 origin,
                 variant,
                 type_: typ,
+                deprecation: Deprecation::NotDeprecated,
             },
         );
     }
@@ -165,6 +177,37 @@ impl<'a, 'b> Environment<'a, 'b> {
         let _ = self.module_values.insert(name.to_string(), value);
     }
 
+    /// The number of constructors the custom type `module.name` has, if it is
+    /// known.
+    ///
+    /// Imported types are counted using the record constructors their module
+    /// exposes. The constructors of opaque types are not exposed, but they
+    /// cannot be pattern matched on outside of their own module.
+    ///
+    pub fn type_constructors_count(&self, module: &[String], name: &str) -> Option<usize> {
+        if module == self.current_module {
+            if let Some(constructors) = self.module_types_constructors.get(name) {
+                return Some(constructors.len());
+            }
+        }
+        // Types from the prelude have no module name
+        let module_name = if module.is_empty() {
+            "gleam".to_string()
+        } else {
+            module.join("/")
+        };
+        let count = self
+            .importable_modules
+            .get(&module_name)?
+            .values
+            .values()
+            .filter(|value| matches!(value.variant, ValueConstructorVariant::Record { .. }))
+            .filter_map(|value| value.type_.constructed_type())
+            .filter(|(type_module, type_name)| type_module == module && type_name == name)
+            .count();
+        Some(count)
+    }
+
     /// Lookup a variable in the current scope.
     ///
     pub fn get_variable(&self, name: &str) -> Option<&ValueConstructor> {
@@ -318,7 +361,7 @@ impl<'a, 'b> Environment<'a, 'b> {
             }
 
             Type::Var { type_: typ } => {
-                match typ.borrow().deref() {
+                match typ.read().deref() {
                     TypeVar::Link { type_: typ } => {
                         return self.instantiate(typ.clone(), ctx_level, ids, hydrator)
                     }
@@ -368,7 +411,7 @@ impl<'a, 'b> Environment<'a, 'b> {
 
         // Collapse right hand side type links. Left hand side will be collapsed in the next block.
         if let Type::Var { type_: typ } = t2.deref() {
-            if let TypeVar::Link { type_: typ } = typ.borrow().deref() {
+            if let TypeVar::Link { type_: typ } = typ.read().deref() {
                 return self.unify(t1, typ.clone());
             }
         }
@@ -380,7 +423,7 @@ impl<'a, 'b> Environment<'a, 'b> {
                 Link,
             }
 
-            let action = match typ.borrow().deref() {
+            let action = match typ.read().deref() {
                 TypeVar::Link { type_: typ } => Action::Unify(typ.clone()),
 
                 TypeVar::Unbound { id, level } => {
@@ -390,8 +433,8 @@ impl<'a, 'b> Environment<'a, 'b> {
 
                 TypeVar::Generic { id } => {
                     if let Type::Var { type_: typ } = t2.deref() {
-                        if typ.borrow().is_unbound() {
-                            *typ.borrow_mut() = TypeVar::Generic { id: *id };
+                        if typ.read().is_unbound() {
+                            *typ.write() = TypeVar::Generic { id: *id };
                             return Ok(());
                         }
                     }
@@ -401,7 +444,7 @@ impl<'a, 'b> Environment<'a, 'b> {
 
             return match action {
                 Action::Link => {
-                    *typ.borrow_mut() = TypeVar::Link { type_: t2 };
+                    *typ.write() = TypeVar::Link { type_: t2 };
                     Ok(())
                 }
 
@@ -535,6 +578,18 @@ impl<'a, 'b> Environment<'a, 'b> {
         }
     }
 
+    /// Emits a warning if an item imported from another module has been
+    /// deprecated.
+    pub fn warn_if_deprecated(&mut self, name: &str, deprecation: &Deprecation, location: SrcSpan) {
+        if let Deprecation::Deprecated { message } = deprecation {
+            self.warnings.push(Warning::DeprecatedItem {
+                location,
+                name: name.to_string(),
+                message: message.clone(),
+            });
+        }
+    }
+
     /// Converts entities with a usage count of 0 to warnings
     pub fn convert_unused_to_warnings(&mut self) {
         let unused = self
@@ -1,5 +1,6 @@
 use crate::{
     ast::{BinOp, SrcSpan},
+    build::Target,
     type_::Type,
 };
 
@@ -206,6 +207,25 @@ pub enum Error {
         name: String,
         keyword: String,
     },
+
+    UnsupportedTarget {
+        location: SrcSpan,
+        name: String,
+        /// The functions used by `name` that lead to a function without an
+        /// implementation for the target
+        via: Vec<String>,
+        target: Target,
+    },
+
+    /// A `let` with a pattern that does not match every value of its type
+    InexhaustiveLetAssignment {
+        location: SrcSpan,
+    },
+
+    /// An `assert` with a pattern that can never match the value
+    PatternNeverMatches {
+        location: SrcSpan,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -262,6 +282,20 @@ pub enum Warning {
         location: SrcSpan,
         name: String,
     },
+
+    DeprecatedItem {
+        location: SrcSpan,
+        name: String,
+        message: String,
+    },
+
+    IrrefutableAssert {
+        location: SrcSpan,
+    },
+
+    UnreachableCode {
+        location: SrcSpan,
+    },
 }
 
 impl Error {
@@ -276,6 +310,52 @@ impl Error {
             _ => self,
         }
     }
+
+    /// The location of the code the error is about, if there is one.
+    pub fn location(&self) -> Option<SrcSpan> {
+        match self {
+            Error::BitStringSegmentError { location, .. }
+            | Error::UnknownVariable { location, .. }
+            | Error::UnknownType { location, .. }
+            | Error::UnknownModule { location, .. }
+            | Error::UnknownModuleType { location, .. }
+            | Error::UnknownModuleValue { location, .. }
+            | Error::UnknownModuleField { location, .. }
+            | Error::NotFn { location, .. }
+            | Error::UnknownField { location, .. }
+            | Error::IncorrectArity { location, .. }
+            | Error::UnnecessarySpreadOperator { location, .. }
+            | Error::IncorrectTypeArity { location, .. }
+            | Error::CouldNotUnify { location, .. }
+            | Error::RecursiveType { location, .. }
+            | Error::DuplicateName { location, .. }
+            | Error::DuplicateImport { location, .. }
+            | Error::DuplicateTypeName { location, .. }
+            | Error::DuplicateConstName { location, .. }
+            | Error::DuplicateArgument { location, .. }
+            | Error::DuplicateField { location, .. }
+            | Error::PrivateTypeLeak { location, .. }
+            | Error::UnexpectedLabelledArg { location, .. }
+            | Error::PositionalArgumentAfterLabelled { location, .. }
+            | Error::IncorrectNumClausePatterns { location, .. }
+            | Error::NonLocalClauseGuardVariable { location, .. }
+            | Error::ExtraVarInAlternativePattern { location, .. }
+            | Error::DuplicateVarInPattern { location, .. }
+            | Error::OutOfBoundsTupleIndex { location, .. }
+            | Error::NotATuple { location, .. }
+            | Error::NotATupleUnbound { location, .. }
+            | Error::RecordAccessUnknownType { location, .. }
+            | Error::RecordUpdateInvalidConstructor { location, .. }
+            | Error::UnexpectedTypeHole { location, .. }
+            | Error::UnsupportedTarget { location, .. }
+            | Error::InexhaustiveLetAssignment { location, .. }
+            | Error::PatternNeverMatches { location, .. } => Some(*location),
+
+            Error::UnknownLabels { unknown, .. } => unknown.first().map(|(_, location)| *location),
+
+            Error::ReservedModuleName { .. } | Error::KeywordInModuleName { .. } => None,
+        }
+    }
 }
 
 impl Warning {
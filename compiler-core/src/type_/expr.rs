@@ -23,6 +23,10 @@ pub(crate) struct ExprTyper<'a, 'b, 'c> {
     // to determine whether it is safe to generalise this expression after
     // it has been inferred.
     pub(crate) ungeneralised_function_used: bool,
+
+    // The module functions referenced, used to determine which targets the
+    // function being inferred can be compiled for.
+    pub(crate) function_references: Vec<FunctionReference>,
 }
 
 impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
@@ -33,6 +37,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
             hydrator,
             environment,
             ungeneralised_function_used: false,
+            function_references: vec![],
         }
     }
 
@@ -123,8 +128,9 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                 value,
                 kind,
                 annotation,
+                message,
                 ..
-            } => self.infer_assignment(pattern, *value, kind, &annotation, location),
+            } => self.infer_assignment(pattern, *value, kind, message, &annotation, location),
 
             UntypedExpr::Try {
                 location,
@@ -188,9 +194,41 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                 spread,
                 arguments: args,
             } => self.infer_record_update(*constructor, spread, args, location),
+
+            UntypedExpr::NegateBool { location, value } => self.infer_negate_bool(location, *value),
+
+            UntypedExpr::NegateInt { location, value } => self.infer_negate_int(location, *value),
         }
     }
 
+    fn infer_negate_bool(
+        &mut self,
+        location: SrcSpan,
+        value: UntypedExpr,
+    ) -> Result<TypedExpr, Error> {
+        let value = self.infer(value)?;
+        self.unify(bool(), value.type_())
+            .map_err(|e| convert_unify_error(e, value.location()))?;
+        Ok(TypedExpr::NegateBool {
+            location,
+            value: Box::new(value),
+        })
+    }
+
+    fn infer_negate_int(
+        &mut self,
+        location: SrcSpan,
+        value: UntypedExpr,
+    ) -> Result<TypedExpr, Error> {
+        let value = self.infer(value)?;
+        self.unify(int(), value.type_())
+            .map_err(|e| convert_unify_error(e, value.location()))?;
+        Ok(TypedExpr::NegateInt {
+            location,
+            value: Box::new(value),
+        })
+    }
+
     fn infer_pipeline(&mut self, expressions: Vec1<UntypedExpr>) -> Result<TypedExpr, Error> {
         PipeTyper::infer(self, expressions)
     }
@@ -268,6 +306,14 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
             }
             expressions.push(expression);
         }
+
+        // Any expressions following one that never returns cannot be run
+        if let Some(index) = expressions.iter().position(TypedExpr::never_returns) {
+            if let (Some(first), Some(last)) = (expressions.get(index + 1), expressions.last()) {
+                self.unreachable_code(first.start_byte_index(), last.location().end);
+            }
+        }
+
         Ok(TypedExpr::Sequence {
             location,
             expressions,
@@ -577,6 +623,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
             BinOp::DivInt => (int(), int()),
             BinOp::DivFloat => (float(), float()),
             BinOp::ModuloInt => (int(), int()),
+            BinOp::Concatenate => (string(), string()),
         };
 
         let left = self.infer(left)?;
@@ -600,6 +647,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
         pattern: UntypedPattern,
         value: UntypedExpr,
         kind: AssignmentKind,
+        message: Option<String>,
         annotation: &Option<TypeAst>,
         location: SrcSpan,
     ) -> Result<TypedExpr, Error> {
@@ -620,10 +668,32 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                 .map_err(|e| convert_unify_error(e, value.location()))?;
         }
 
+        // Only `assert` may use a pattern that could fail to match
+        let irrefutable = refutability::is_irrefutable(&pattern, self.environment);
+        match kind {
+            AssignmentKind::Let if !irrefutable => {
+                return Err(Error::InexhaustiveLetAssignment {
+                    location: pattern.location(),
+                })
+            }
+            AssignmentKind::Assert if irrefutable => {
+                self.environment.warnings.push(Warning::IrrefutableAssert {
+                    location: pattern.location(),
+                })
+            }
+            AssignmentKind::Assert if refutability::never_matches(&pattern, &value) => {
+                return Err(Error::PatternNeverMatches {
+                    location: pattern.location(),
+                })
+            }
+            AssignmentKind::Let | AssignmentKind::Assert => (),
+        }
+
         Ok(TypedExpr::Assignment {
             location,
             typ: value.type_(),
             kind,
+            message,
             pattern,
             value: Box::new(value),
         })
@@ -717,6 +787,18 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                 .map_err(|e| e.case_clause_mismatch().into_error(typed_clause.location()))?;
             typed_clauses.push(typed_clause);
         }
+
+        // Any clauses following one that matches every value cannot be run
+        if let Some(index) = typed_clauses
+            .iter()
+            .position(|clause| self.is_catch_all(clause))
+        {
+            if let (Some(first), Some(last)) = (typed_clauses.get(index + 1), typed_clauses.last())
+            {
+                self.unreachable_code(first.location().start, last.location().end);
+            }
+        }
+
         Ok(TypedExpr::Case {
             location,
             typ: return_type,
@@ -762,6 +844,25 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
         })
     }
 
+    /// Returns `true` if the clause has no guard and one of its patterns
+    /// matches every value of the subjects.
+    fn is_catch_all(&self, clause: &TypedClause) -> bool {
+        clause.guard.is_none()
+            && std::iter::once(&clause.pattern)
+                .chain(&clause.alternative_patterns)
+                .any(|patterns| {
+                    patterns
+                        .iter()
+                        .all(|pattern| refutability::is_irrefutable(pattern, self.environment))
+                })
+    }
+
+    fn unreachable_code(&mut self, start: usize, end: usize) {
+        self.environment.warnings.push(Warning::UnreachableCode {
+            location: SrcSpan { start, end },
+        });
+    }
+
     fn infer_clause_pattern(
         &mut self,
         pattern: UntypedMultiPattern,
@@ -1088,6 +1189,45 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                 })
             }
 
+            ClauseGuard::NegateBool { location, value } => {
+                let value = self.infer_clause_guard(*value)?;
+                self.unify(bool(), value.type_())
+                    .map_err(|e| convert_unify_error(e, value.location()))?;
+                Ok(ClauseGuard::NegateBool {
+                    location,
+                    value: Box::new(value),
+                })
+            }
+
+            ClauseGuard::NegateInt { location, value } => {
+                let value = self.infer_clause_guard(*value)?;
+                self.unify(int(), value.type_())
+                    .map_err(|e| convert_unify_error(e, value.location()))?;
+                Ok(ClauseGuard::NegateInt {
+                    location,
+                    value: Box::new(value),
+                })
+            }
+
+            ClauseGuard::Concatenate {
+                location,
+                left,
+                right,
+                ..
+            } => {
+                let left = self.infer_clause_guard(*left)?;
+                self.unify(string(), left.type_())
+                    .map_err(|e| convert_unify_error(e, left.location()))?;
+                let right = self.infer_clause_guard(*right)?;
+                self.unify(string(), right.type_())
+                    .map_err(|e| convert_unify_error(e, right.location()))?;
+                Ok(ClauseGuard::Concatenate {
+                    location,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+            }
+
             ClauseGuard::Constant(constant) => {
                 self.infer_const(&None, constant).map(ClauseGuard::Constant)
             }
@@ -1133,6 +1273,14 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
 
             (module.name.clone(), constructor.clone())
         };
+        self.environment
+            .warn_if_deprecated(&label, &constructor.deprecation, select_location);
+        self.register_function_reference(
+            format!("{}.{}", module_alias, label),
+            false,
+            &constructor.variant,
+            select_location,
+        );
 
         let type_ = self.instantiate(constructor.type_, self.environment.level, &mut hashmap![]);
         Ok(TypedExpr::ModuleSelect {
@@ -1287,6 +1435,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                              label,
                              value,
                              location,
+                             ..
                          }| {
                             let value = self.infer(value.clone())?;
                             let spread_field = self.infer_known_record_access(
@@ -1343,6 +1492,25 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
         })
     }
 
+    /// Records a reference to a module function so that the targets the
+    /// function being inferred supports can be determined.
+    fn register_function_reference(
+        &mut self,
+        name: String,
+        unqualified: bool,
+        variant: &ValueConstructorVariant,
+        location: SrcSpan,
+    ) {
+        if let ValueConstructorVariant::ModuleFn { targets, .. } = variant {
+            self.function_references.push(FunctionReference {
+                unqualified,
+                name,
+                targets: targets.clone(),
+                location,
+            });
+        }
+    }
+
     fn infer_value_constructor(
         &mut self,
         module: &Option<String>,
@@ -1353,7 +1521,14 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
             // Look in the local scope for a binding with this name
             None => {
                 let constructor = match self.environment.get_variable(name) {
-                    Some(var) => Ok(var.clone()),
+                    // Local definitions are inserted without their deprecation so
+                    // only imported values can be reported here.
+                    Some(var) => {
+                        let var = var.clone();
+                        self.environment
+                            .warn_if_deprecated(name, &var.deprecation, *location);
+                        Ok(var)
+                    }
                     None => self
                         .environment
                         .get_module_const(name)
@@ -1412,11 +1587,27 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
             }
         };
 
+        if module.is_some() {
+            self.environment
+                .warn_if_deprecated(name, &constructor.deprecation, *location);
+        }
+        let (reference_name, unqualified) = match module {
+            Some(module) => (format!("{}.{}", module, name), false),
+            None => (name.to_string(), true),
+        };
+        self.register_function_reference(
+            reference_name,
+            unqualified,
+            &constructor.variant,
+            *location,
+        );
+
         let ValueConstructor {
             public,
             variant,
             origin,
             type_: typ,
+            deprecation,
         } = constructor;
 
         // Instantiate generic variables into unbound variables for this usage
@@ -1426,6 +1617,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
             variant,
             origin,
             type_: typ,
+            deprecation,
         })
     }
 
@@ -1578,6 +1770,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                             label,
                             value,
                             location,
+                            shorthand,
                         } = arg;
                         let value = self.infer_const(&None, value)?;
                         self.unify(typ.clone(), value.type_())
@@ -1586,6 +1779,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                             label,
                             value,
                             location,
+                            shorthand,
                         })
                     })
                     .try_collect()?;
@@ -1714,6 +1908,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                     label,
                     value,
                     location,
+                    shorthand,
                 } = arg;
                 let value = self.infer(value)?;
                 self.unify(typ.clone(), value.type_())
@@ -1722,6 +1917,7 @@ impl<'a, 'b, 'c> ExprTyper<'a, 'b, 'c> {
                     label,
                     value,
                     location,
+                    shorthand,
                 })
             })
             .try_collect()?;
@@ -109,12 +109,20 @@ impl Hydrator {
                 let TypeConstructor {
                     parameters,
                     typ: return_type,
+                    module: type_module,
+                    deprecation,
                     ..
                 } = environment
                     .get_type_constructor(module, name)
                     .map_err(|e| convert_get_type_constructor_error(e, location))?
                     .clone();
 
+                // Types defined in this module can be used without warning even if
+                // they have been deprecated.
+                if type_module != environment.current_module {
+                    environment.warn_if_deprecated(name, &deprecation, *location);
+                }
+
                 // Register the type constructor as being used if it is unqualifed.
                 // We do not track use of qualified type constructors as they may be
                 // used in another module.
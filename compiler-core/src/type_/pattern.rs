@@ -409,6 +409,7 @@ impl<'a, 'b, 'c> PatternTyper<'a, 'b, 'c> {
                                     },
                                     location: spread_location,
                                     label: None,
+                                    shorthand: false,
                                 };
 
                                 pattern_args.insert(index_of_first_labelled_arg, new_call_arg);
@@ -423,6 +424,7 @@ impl<'a, 'b, 'c> PatternTyper<'a, 'b, 'c> {
                 }
 
                 let constructor_typ = cons.type_.clone();
+                let deprecation = cons.deprecation.clone();
                 let constructor = match cons.variant {
                     ValueConstructorVariant::Record { ref name, .. } => {
                         PatternConstructor::Record {
@@ -436,6 +438,8 @@ impl<'a, 'b, 'c> PatternTyper<'a, 'b, 'c> {
                         panic!("Unexpected value constructor type for a constructor pattern.",)
                     }
                 };
+                self.environment
+                    .warn_if_deprecated(&name, &deprecation, location);
 
                 let instantiated_constructor_type = self.environment.instantiate(
                     constructor_typ,
@@ -454,12 +458,14 @@ impl<'a, 'b, 'c> PatternTyper<'a, 'b, 'c> {
                                         value,
                                         location,
                                         label,
+                                        shorthand,
                                     } = arg;
                                     let value = self.unify(value, typ.clone())?;
                                     Ok(CallArg {
                                         value,
                                         location,
                                         label,
+                                        shorthand,
                                     })
                                 })
                                 .try_collect()?;
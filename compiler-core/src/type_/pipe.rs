@@ -103,6 +103,7 @@ impl<'a, 'b, 'c, 'd> PipeTyper<'a, 'b, 'c, 'd> {
             label: None,
             location: self.argument_location,
             value: self.typed_left_hand_value_variable(),
+            shorthand: false,
         }
     }
 
@@ -113,6 +114,7 @@ impl<'a, 'b, 'c, 'd> PipeTyper<'a, 'b, 'c, 'd> {
             label: None,
             location: self.argument_location,
             value: self.untyped_left_hand_value_variable(),
+            shorthand: false,
         }
     }
 
@@ -127,6 +129,7 @@ impl<'a, 'b, 'c, 'd> PipeTyper<'a, 'b, 'c, 'd> {
                 origin: self.argument_location,
                 type_: self.argument_type.clone(),
                 variant: ValueConstructorVariant::LocalVariable,
+                deprecation: Deprecation::NotDeprecated,
             },
         }
     }
@@ -161,6 +164,7 @@ impl<'a, 'b, 'c, 'd> PipeTyper<'a, 'b, 'c, 'd> {
             location,
             typ: expression.type_(),
             kind: AssignmentKind::Let,
+            message: None,
             value: Box::new(expression),
             pattern: Pattern::Var {
                 location,
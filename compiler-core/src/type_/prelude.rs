@@ -1,7 +1,10 @@
-use crate::build::Origin;
+use crate::{ast::Deprecation, build::Origin};
 
-use super::{Module, Type, TypeConstructor, TypeVar, ValueConstructor, ValueConstructorVariant};
-use std::{cell::RefCell, collections::HashMap, sync::Arc};
+use super::{
+    Module, Type, TypeConstructor, TypeVar, TypeVarCell, UidGenerator, ValueConstructor,
+    ValueConstructorVariant,
+};
+use std::{collections::HashMap, sync::Arc};
 
 pub fn int() -> Arc<Type> {
     Arc::new(Type::App {
@@ -94,35 +97,32 @@ pub fn utf_codepoint() -> Arc<Type> {
 
 pub fn generic_var(id: usize) -> Arc<Type> {
     Arc::new(Type::Var {
-        type_: Arc::new(RefCell::new(TypeVar::Generic { id })),
+        type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id })),
     })
 }
 
 pub fn unbound_var(id: usize, level: usize) -> Arc<Type> {
     Arc::new(Type::Var {
-        type_: Arc::new(RefCell::new(TypeVar::Unbound { id, level })),
+        type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id, level })),
     })
 }
 
 #[cfg(test)]
 pub fn link(type_: Arc<Type>) -> Arc<Type> {
     Arc::new(Type::Var {
-        type_: Arc::new(RefCell::new(TypeVar::Link { type_ })),
+        type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_ })),
     })
 }
 
-pub fn build_prelude(uid: &mut usize) -> Module {
-    let mut new_generic_var = || {
-        let t = generic_var(*uid);
-        *uid += 1;
-        t
-    };
+pub fn build_prelude(uid: &mut UidGenerator) -> Module {
+    let mut new_generic_var = || generic_var(uid.next_uid());
 
     let value = |variant, type_| ValueConstructor {
         public: true,
         origin: Default::default(),
         variant,
         type_,
+        deprecation: Deprecation::NotDeprecated,
     };
 
     let mut prelude = Module {
@@ -142,6 +142,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             origin: Default::default(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -175,6 +176,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: bool(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -187,6 +189,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: list(list_parameter),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -198,6 +201,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: float(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -209,6 +213,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: string(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -222,6 +227,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: result(result_value, result_error),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -244,6 +250,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: nil(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -255,6 +262,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: bit_string(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -266,6 +274,7 @@ pub fn build_prelude(uid: &mut usize) -> Module {
             typ: utf_codepoint(),
             module: vec![],
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
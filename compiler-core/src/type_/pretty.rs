@@ -5,9 +5,6 @@ use std::sync::Arc;
 
 #[cfg(test)]
 use super::*;
-#[cfg(test)]
-use std::cell::RefCell;
-
 #[cfg(test)]
 use pretty_assertions::assert_eq;
 
@@ -65,7 +62,7 @@ impl Printer {
                         .group(),
                 ),
 
-            Type::Var { type_: typ, .. } => self.type_var_doc(&typ.borrow()),
+            Type::Var { type_: typ, .. } => self.type_var_doc(&typ.read()),
 
             Type::Tuple { elems, .. } => self.args_to_gleam_doc(elems).surround("#(", ")"),
         }
@@ -278,7 +275,7 @@ fn pretty_print_test() {
     );
     assert_string!(
         Type::Var {
-            type_: Arc::new(RefCell::new(TypeVar::Link {
+            type_: Arc::new(TypeVarCell::new(TypeVar::Link {
                 type_: Arc::new(Type::App {
                     args: vec![],
                     module: vec!["whatever".to_string()],
@@ -291,17 +288,17 @@ fn pretty_print_test() {
     );
     assert_string!(
         Type::Var {
-            type_: Arc::new(RefCell::new(TypeVar::Unbound { level: 1, id: 2231 })),
+            type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { level: 1, id: 2231 })),
         },
         "a",
     );
     assert_string!(
         fn_(
             vec![Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Unbound { level: 1, id: 78 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { level: 1, id: 78 })),
             })],
             Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Unbound { level: 1, id: 2 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { level: 1, id: 2 })),
             }),
         ),
         "fn(a) -> b",
@@ -309,10 +306,10 @@ fn pretty_print_test() {
     assert_string!(
         fn_(
             vec![Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Generic { id: 78 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 78 })),
             })],
             Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Generic { id: 2 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 2 })),
             }),
         ),
         "fn(a) -> b",
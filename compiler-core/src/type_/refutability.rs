@@ -0,0 +1,177 @@
+use super::*;
+
+/// Returns `true` if the pattern matches every value of its type, meaning it
+/// can be used with `let` rather than `assert`.
+///
+/// A record pattern is only irrefutable if its type has a single constructor
+/// and each of its arguments are irrefutable.
+///
+pub fn is_irrefutable(pattern: &TypedPattern, environment: &Environment<'_, '_>) -> bool {
+    match pattern {
+        Pattern::Var { .. } | Pattern::Discard { .. } => true,
+
+        Pattern::Assign { pattern, .. } => is_irrefutable(pattern, environment),
+
+        Pattern::Tuple { elems, .. } => elems.iter().all(|elem| is_irrefutable(elem, environment)),
+
+        // A list pattern without elements matches every list if its tail
+        // does, e.g. `[..rest]`
+        Pattern::List { elements, tail, .. } => {
+            elements.is_empty() && matches!(tail, Some(tail) if is_irrefutable(tail, environment))
+        }
+
+        Pattern::Constructor {
+            arguments, type_, ..
+        } => {
+            let single_constructor = type_
+                .constructed_type()
+                .and_then(|(module, name)| environment.type_constructors_count(&module, &name))
+                == Some(1);
+            single_constructor
+                && arguments
+                    .iter()
+                    .all(|arg| is_irrefutable(&arg.value, environment))
+        }
+
+        Pattern::Int { .. }
+        | Pattern::Float { .. }
+        | Pattern::String { .. }
+        | Pattern::VarUsage { .. }
+        | Pattern::BitString { .. } => false,
+    }
+}
+
+/// Returns `true` if the pattern can be seen to never match the value, for
+/// example `assert Ok(x) = Error(Nil)`. Only literals, tuples, lists and
+/// records written in the value are compared.
+///
+pub fn never_matches(pattern: &TypedPattern, value: &TypedExpr) -> bool {
+    match (pattern, value) {
+        (Pattern::Assign { pattern, .. }, _) => never_matches(pattern, value),
+
+        (
+            Pattern::Int {
+                value: expected, ..
+            },
+            TypedExpr::Int { value, .. },
+        ) => match (int_value(expected), int_value(value)) {
+            (Some(expected), Some(value)) => expected != value,
+            _ => false,
+        },
+
+        (
+            Pattern::Float {
+                value: expected, ..
+            },
+            TypedExpr::Float { value, .. },
+        ) => match (float_value(expected), float_value(value)) {
+            (Some(expected), Some(value)) => (expected - value).abs() > f64::EPSILON,
+            _ => false,
+        },
+
+        // Escape sequences can spell the same string in different ways so
+        // only strings without any are compared.
+        (
+            Pattern::String {
+                value: expected, ..
+            },
+            TypedExpr::String { value, .. },
+        ) => !expected.contains('\\') && !value.contains('\\') && expected != value,
+
+        (
+            Pattern::Tuple {
+                elems: patterns, ..
+            },
+            TypedExpr::Tuple { elems: values, .. },
+        ) => patterns
+            .iter()
+            .zip(values)
+            .any(|(pattern, value)| never_matches(pattern, value)),
+
+        (
+            Pattern::List {
+                elements: patterns,
+                tail: pattern_tail,
+                ..
+            },
+            TypedExpr::List {
+                elements: values,
+                tail: value_tail,
+                ..
+            },
+        ) => {
+            let wrong_length = match (pattern_tail, value_tail) {
+                (None, None) => patterns.len() != values.len(),
+                (Some(_), None) => patterns.len() > values.len(),
+                (None, Some(_)) => patterns.len() < values.len(),
+                (Some(_), Some(_)) => false,
+            };
+            wrong_length
+                || patterns
+                    .iter()
+                    .zip(values)
+                    .any(|(pattern, value)| never_matches(pattern, value))
+        }
+
+        (
+            Pattern::Constructor {
+                name, arguments, ..
+            },
+            _,
+        ) => match record_value(value) {
+            Some((value_name, _)) if value_name != name => true,
+            Some((_, values)) if values.len() == arguments.len() => arguments
+                .iter()
+                .zip(values)
+                .any(|(pattern, value)| never_matches(&pattern.value, &value.value)),
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
+
+/// The name of the record constructor and the arguments, if the value is a
+/// record written out in full.
+fn record_value(value: &TypedExpr) -> Option<(&str, &[CallArg<TypedExpr>])> {
+    match value {
+        TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant: ValueConstructorVariant::Record { name, .. },
+                    ..
+                },
+            ..
+        }
+        | TypedExpr::ModuleSelect {
+            constructor: ModuleValueConstructor::Record { name, .. },
+            ..
+        } => Some((name, &[])),
+
+        TypedExpr::Call { fun, args, .. } => {
+            record_value(fun).map(|(name, _)| (name, args.as_slice()))
+        }
+
+        _ => None,
+    }
+}
+
+fn int_value(value: &str) -> Option<i128> {
+    let value = value.replace('_', "");
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.as_str()),
+    };
+    let (radix, digits) = match value.get(0..2) {
+        Some("0b") => (2, value.get(2..)?),
+        Some("0o") => (8, value.get(2..)?),
+        Some("0x") => (16, value.get(2..)?),
+        _ => (10, value),
+    };
+    let value = i128::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn float_value(value: &str) -> Option<f64> {
+    value.replace('_', "").parse().ok()
+}
@@ -0,0 +1,183 @@
+use super::*;
+
+/// A reference from the body of a module function to another module
+/// function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionReference {
+    /// The name the function was referred to by
+    pub name: String,
+    /// Whether the function was referred to without a module qualifier. If so
+    /// it may be defined in the module being checked, in which case its
+    /// targets are not known until every function in the module has been
+    /// inferred.
+    pub unqualified: bool,
+    /// The targets of the function as known when it was referenced
+    pub targets: Vec<Target>,
+    pub location: SrcSpan,
+}
+
+#[derive(Debug)]
+struct Function {
+    name: String,
+    public: bool,
+    targets: Vec<Target>,
+    references: Vec<FunctionReference>,
+}
+
+/// Tracks which targets each function in a module can be compiled for.
+///
+/// External functions are only supported on the targets they have an
+/// implementation for, and a function only supports the targets that every
+/// function it references from the same module supports. Functions from other
+/// modules have to support every target the function is used on.
+///
+#[derive(Debug, Default)]
+pub struct TargetSupport {
+    functions: Vec<Function>,
+    indexes: HashMap<String, usize>,
+}
+
+impl TargetSupport {
+    /// Registers a function along with the targets it supports on its own and
+    /// the functions referenced in its body.
+    pub fn register(
+        &mut self,
+        name: &str,
+        public: bool,
+        targets: Vec<Target>,
+        references: Vec<FunctionReference>,
+    ) {
+        let _ = self.indexes.insert(name.to_string(), self.functions.len());
+        self.functions.push(Function {
+            name: name.to_string(),
+            public,
+            targets,
+            references,
+        });
+    }
+
+    /// Narrows the targets of each function to those supported by every
+    /// function of this module it references, returning the targets of each
+    /// function.
+    pub fn resolve(&mut self) -> HashMap<String, Vec<Target>> {
+        // Recursive functions can refer to each other so the targets are
+        // narrowed until they no longer change.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..self.functions.len() {
+                let supported: Vec<Target> = match self.functions.get(i) {
+                    Some(function) => function
+                        .targets
+                        .iter()
+                        .filter(|target| {
+                            function
+                                .references
+                                .iter()
+                                .all(|reference| self.supports(reference, **target))
+                        })
+                        .copied()
+                        .collect(),
+                    None => continue,
+                };
+                if let Some(function) = self.functions.get_mut(i) {
+                    if function.targets.len() != supported.len() {
+                        function.targets = supported;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        self.functions
+            .iter()
+            .map(|function| (function.name.clone(), function.targets.clone()))
+            .collect()
+    }
+
+    /// Returns an error for the first function from another module that is
+    /// used on a target it does not support, naming the chain of functions
+    /// that lead to it from a public function of this module.
+    ///
+    /// Functions of this module that cannot be compiled for the target are not
+    /// generated, so only the functions that can be are checked.
+    ///
+    /// Must be called after `resolve`.
+    pub fn check_references(&self, target: Target) -> Result<(), Error> {
+        let mut visited = HashSet::new();
+        for function in self
+            .functions
+            .iter()
+            .filter(|function| function.public && function.targets.contains(&target))
+        {
+            let mut chain = self
+                .unsupported_chain(function, target, &mut visited)
+                .unwrap_or_default();
+            if let Some(first) = chain.pop() {
+                return Err(Error::UnsupportedTarget {
+                    location: first.location,
+                    name: first.name.clone(),
+                    via: chain
+                        .iter()
+                        .rev()
+                        .map(|reference| reference.name.clone())
+                        .collect(),
+                    target,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The functions that cannot be compiled for the target. Like external
+    /// functions without an implementation for the target they are left out
+    /// of the generated code.
+    pub fn unsupported_functions(&self, target: Target) -> HashSet<String> {
+        self.functions
+            .iter()
+            .filter(|function| !function.targets.contains(&target))
+            .map(|function| function.name.clone())
+            .collect()
+    }
+
+    fn supports(&self, reference: &FunctionReference, target: Target) -> bool {
+        self.local_function(reference)
+            .is_none_or(|function| function.targets.contains(&target))
+    }
+
+    fn local_function(&self, reference: &FunctionReference) -> Option<&Function> {
+        if !reference.unqualified {
+            return None;
+        }
+        self.indexes
+            .get(&reference.name)
+            .and_then(|index| self.functions.get(*index))
+    }
+
+    /// The references leading from the function to a function from another
+    /// module that does not support the target, last reference first.
+    fn unsupported_chain<'a>(
+        &'a self,
+        function: &'a Function,
+        target: Target,
+        visited: &mut HashSet<&'a str>,
+    ) -> Option<Vec<&'a FunctionReference>> {
+        if !visited.insert(function.name.as_str()) {
+            return None;
+        }
+        for reference in &function.references {
+            let chain = if let Some(function) = self.local_function(reference) {
+                self.unsupported_chain(function, target, visited)
+            } else if reference.targets.contains(&target) {
+                None
+            } else {
+                Some(vec![])
+            };
+            if let Some(mut chain) = chain {
+                chain.push(reference);
+                return Some(chain);
+            }
+        }
+        None
+    }
+}
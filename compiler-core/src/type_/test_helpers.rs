@@ -1,5 +1,46 @@
 use super::*;
 
+/// Infers `src` as the module `my_module` compiled for `target`, which can
+/// import the module `dep` defined by `dep_src`. The typed module is returned
+/// along with the warnings emitted for `my_module`.
+pub fn infer_with_dependency(
+    target: Target,
+    dep_src: &str,
+    src: &str,
+) -> Result<(TypedModule, Vec<Warning>), Error> {
+    let mut uid = UidGenerator::default();
+    let mut modules = HashMap::new();
+    let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
+
+    let (mut dep, _) = crate::parse::parse_module(dep_src).expect("syntax error");
+    dep.name = vec!["dep".to_string()];
+    let dep = infer_module(
+        target,
+        &mut uid,
+        dep,
+        Origin::Src,
+        "thepackage",
+        &modules,
+        &mut vec![],
+    )
+    .expect("should successfully infer dependency");
+    let _ = modules.insert("dep".to_string(), dep.type_info);
+
+    let (mut ast, _) = crate::parse::parse_module(src).expect("syntax error");
+    ast.name = vec!["my_module".to_string()];
+    let mut warnings = vec![];
+    let module = infer_module(
+        target,
+        &mut uid,
+        ast,
+        Origin::Src,
+        "thepackage",
+        &modules,
+        &mut warnings,
+    )?;
+    Ok((module, warnings))
+}
+
 pub fn env_types_with(things: &[&str]) -> Vec<String> {
     let mut types: Vec<_> = env_types();
     for &thing in things {
@@ -10,17 +51,23 @@ pub fn env_types_with(things: &[&str]) -> Vec<String> {
 
 pub fn env_types() -> Vec<String> {
     let mut modules = HashMap::new();
-    let mut uid = 0;
+    let mut uid = UidGenerator::default();
     // DUPE: preludeinsertion
     // TODO: Currently we do this here and also in the tests. It would be better
     // to have one place where we create all this required state for use in each
     // place.
     let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
-    Environment::new(&mut 0, &[], &modules, &mut vec![])
-        .module_types
-        .keys()
-        .map(|s| s.to_string())
-        .collect()
+    Environment::new(
+        &mut UidGenerator::default(),
+        &[],
+        Target::Erlang,
+        &modules,
+        &mut vec![],
+    )
+    .module_types
+    .keys()
+    .map(|s| s.to_string())
+    .collect()
 }
 
 pub fn env_vars_with(things: &[&str]) -> Vec<String> {
@@ -33,17 +80,23 @@ pub fn env_vars_with(things: &[&str]) -> Vec<String> {
 
 pub fn env_vars() -> Vec<String> {
     let mut modules = HashMap::new();
-    let mut uid = 0;
+    let mut uid = UidGenerator::default();
     // DUPE: preludeinsertion
     // TODO: Currently we do this here and also in the tests. It would be better
     // to have one place where we create all this required state for use in each
     // place.
     let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
-    Environment::new(&mut 0, &[], &modules, &mut vec![])
-        .local_values
-        .keys()
-        .map(|s| s.to_string())
-        .collect()
+    Environment::new(
+        &mut UidGenerator::default(),
+        &[],
+        Target::Erlang,
+        &modules,
+        &mut vec![],
+    )
+    .local_values
+    .keys()
+    .map(|s| s.to_string())
+    .collect()
 }
 
 pub fn sort_options(e: Error) -> Error {
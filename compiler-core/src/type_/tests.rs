@@ -5,8 +5,6 @@ use crate::{
     bit_string,
 };
 
-mod statement_if;
-
 #[macro_export]
 macro_rules! assert_infer {
     ($src:expr, $typ:expr $(,)?) => {
@@ -15,15 +13,21 @@ macro_rules! assert_infer {
         let ast = crate::parse::parse_expression_sequence($src).expect("syntax error");
 
         let mut modules = HashMap::new();
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
         // to have one place where we create all this required state for use in each
         // place.
         let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
-        let result = ExprTyper::new(&mut Environment::new(&mut uid, &[], &modules, &mut vec![]))
-            .infer(ast)
-            .expect("should successfully infer");
+        let result = ExprTyper::new(&mut Environment::new(
+            &mut uid,
+            &[],
+            Target::Erlang,
+            &modules,
+            &mut vec![],
+        ))
+        .infer(ast)
+        .expect("should successfully infer");
         assert_eq!(
             ($src, printer.pretty_print(result.type_().as_ref(), 0),),
             ($src, $typ.to_string()),
@@ -36,7 +40,7 @@ macro_rules! assert_module_error {
         let (mut ast, _) = crate::parse::parse_module($src).expect("syntax error");
         ast.name = vec!["my_module".to_string()];
         let mut modules = HashMap::new();
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
         // to have one place where we create all this required state for use in each
@@ -58,7 +62,7 @@ macro_rules! assert_module_error {
     ($src:expr) => {
         let (ast, _) = crate::parse::parse_module($src).expect("syntax error");
         let mut modules = HashMap::new();
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
         // to have one place where we create all this required state for use in each
@@ -80,7 +84,7 @@ macro_rules! assert_module_error {
 macro_rules! assert_error {
     ($src:expr, $error:expr $(,)?) => {
         let ast = crate::parse::parse_expression_sequence($src).expect("syntax error");
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         let mut modules = HashMap::new();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
@@ -91,6 +95,7 @@ macro_rules! assert_error {
         let result = ExprTyper::new(&mut Environment::new(
             &mut uid,
             &["somemod".to_string()],
+            Target::Erlang,
             &modules,
             &mut vec![],
         ))
@@ -103,11 +108,11 @@ macro_rules! assert_error {
 #[macro_export]
 macro_rules! assert_module_infer {
     ($src:expr, $module:expr $(,)?) => {{
-        use crate::type_::{build_prelude, infer_module};
+        use crate::type_::{build_prelude, infer_module, UidGenerator};
         use itertools::Itertools;
         use std::collections::HashMap;
         let (ast, _) = crate::parse::parse_module($src).expect("syntax error");
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         let mut modules = HashMap::new();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
@@ -147,7 +152,7 @@ macro_rules! assert_warning {
         let (mut ast, _) = crate::parse::parse_module($src).expect("syntax error");
         ast.name = vec!["my_module".to_string()];
         let mut warnings = vec![];
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         let mut modules = HashMap::new();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
@@ -170,13 +175,41 @@ macro_rules! assert_warning {
     };
 }
 
+macro_rules! assert_warnings {
+    ($src:expr, $warnings:expr $(,)?) => {
+        let (mut ast, _) = crate::parse::parse_module($src).expect("syntax error");
+        ast.name = vec!["my_module".to_string()];
+        let expected: Vec<Warning> = $warnings;
+        let mut warnings = vec![];
+        let mut uid = UidGenerator::default();
+        let mut modules = HashMap::new();
+        // DUPE: preludeinsertion
+        // TODO: Currently we do this here and also in the tests. It would be better
+        // to have one place where we create all this required state for use in each
+        // place.
+        let _ = modules.insert("gleam".to_string(), build_prelude(&mut uid));
+        let _ = infer_module(
+            Target::Erlang,
+            &mut uid,
+            ast,
+            Origin::Src,
+            "thepackage",
+            &modules,
+            &mut warnings,
+        )
+        .expect("should successfully infer");
+
+        assert_eq!(expected, warnings);
+    };
+}
+
 macro_rules! assert_no_warnings {
     ($src:expr $(,)?) => {
         let (mut ast, _) = crate::parse::parse_module($src).expect("syntax error");
         ast.name = vec!["my_module".to_string()];
         let expected: Vec<Warning> = vec![];
         let mut warnings = vec![];
-        let mut uid = 0;
+        let mut uid = UidGenerator::default();
         let mut modules = HashMap::new();
         // DUPE: preludeinsertion
         // TODO: Currently we do this here and also in the tests. It would be better
@@ -198,6 +231,12 @@ macro_rules! assert_no_warnings {
     };
 }
 
+mod deprecation;
+mod refutability;
+mod statement_if;
+mod target;
+mod unreachable_code;
+
 #[test]
 fn field_map_reorder_test() {
     let int = |value: &str| UntypedExpr::Int {
@@ -242,16 +281,19 @@ fn field_map_reorder_test() {
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("1"),
             },
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("2"),
             },
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("3"),
             },
         ],
@@ -260,16 +302,19 @@ fn field_map_reorder_test() {
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("1"),
             },
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("2"),
             },
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("3"),
             },
         ],
@@ -283,16 +328,19 @@ fn field_map_reorder_test() {
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("1"),
             },
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("2"),
             },
             CallArg {
                 location: Default::default(),
                 label: Some("last".to_string()),
+                shorthand: false,
                 value: int("3"),
             },
         ],
@@ -301,16 +349,19 @@ fn field_map_reorder_test() {
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("1"),
             },
             CallArg {
                 location: Default::default(),
                 label: None,
+                shorthand: false,
                 value: int("2"),
             },
             CallArg {
                 location: Default::default(),
                 label: Some("last".to_string()),
+                shorthand: false,
                 value: int("3"),
             },
         ],
@@ -326,7 +377,7 @@ fn infer_module_type_retention_test() {
         statements: vec![],
         type_info: (),
     };
-    let mut uid = 0;
+    let mut uid = UidGenerator::default();
     let mut modules = HashMap::new();
     // DUPE: preludeinsertion
     // TODO: Currently we do this here and also in the tests. It would be better
@@ -373,6 +424,11 @@ fn simple_exprs() {
     assert_infer!("4 >= 1", "Bool");
     assert_infer!("4 <= 1", "Bool");
     assert_infer!("4 < 1", "Bool");
+    assert_infer!("\"a\" <> \"b\" <> \"c\"", "String");
+    assert_infer!("!True", "Bool");
+    assert_infer!("!True && False", "Bool");
+    assert_infer!("-todo", "Int");
+    assert_infer!("- 1 * 2", "Int");
 
     // Numbers with _'s
     assert_infer!("1000_000", "Int");
@@ -413,25 +469,26 @@ fn let_() {
         "let x: tuple(Int, Float) = tuple(5, 5.0) x",
         "#(Int, Float)",
     );
-    assert_infer!("let [1, 2, ..x]: List(Int) = [1,2,3] x", "List(Int)",);
+    assert_infer!("assert [1, 2, ..x]: List(Int) = [1,2,3] x", "List(Int)",);
     assert_infer!(
-        "let tuple(5, [..x]): tuple(Int, List(Int)) = tuple(5, [1,2,3]) x",
+        "assert tuple(5, [..x]): tuple(Int, List(Int)) = tuple(5, [1,2,3]) x",
         "List(Int)",
     );
     assert_infer!(
-        "let tuple(5.0, [..x]): tuple(Float, List(Int)) = tuple(5.0, [1,2,3]) x",
+        "assert tuple(5.0, [..x]): tuple(Float, List(Int)) = tuple(5.0, [1,2,3]) x",
         "List(Int)",
     );
     assert_infer!("let x: List(_) = [] x", "List(a)");
     assert_infer!("let x: List(_) = [1] x", "List(Int)");
 
-    assert_infer!("let [] = [] 1", "Int");
-    assert_infer!("let [a] = [1] a", "Int");
-    assert_infer!("let [a, 2] = [1] a", "Int");
-    assert_infer!("let [a, .. b] = [1] a", "Int");
-    assert_infer!("let [a, .. _] = [1] a", "Int");
-    assert_infer!("fn(x) { let [a] = x a }", "fn(List(a)) -> a");
-    assert_infer!("fn(x) { let [a] = x a + 1 }", "fn(List(Int)) -> Int");
+    assert_infer!("let [..a] = [1] a", "List(Int)");
+    assert_infer!("assert [] = [] 1", "Int");
+    assert_infer!("assert [a] = [1] a", "Int");
+    assert_infer!("assert [a, 2] = [1, 2] a", "Int");
+    assert_infer!("assert [a, .. b] = [1] a", "Int");
+    assert_infer!("assert [a, .. _] = [1] a", "Int");
+    assert_infer!("fn(x) { assert [a] = x a }", "fn(List(a)) -> a");
+    assert_infer!("fn(x) { assert [a] = x a + 1 }", "fn(List(Int)) -> Int");
     assert_infer!("let _x = 1 2.0", "Float");
     assert_infer!("let _ = 1 2.0", "Float");
     assert_infer!("let tuple(tag, x) = tuple(1.0, 1) x", "Int");
@@ -440,7 +497,7 @@ fn let_() {
     // assert
     assert_infer!("assert [] = [] 1", "Int");
     assert_infer!("assert [a] = [1] a", "Int");
-    assert_infer!("assert [a, 2] = [1] a", "Int");
+    assert_infer!("assert [a, 2] = [1, 2] a", "Int");
     assert_infer!("assert [a, .._] = [1] a", "Int");
     assert_infer!("assert [a, .._,] = [1] a", "Int");
     assert_infer!("fn(x) { assert [a] = x a }", "fn(List(a)) -> a");
@@ -607,26 +664,29 @@ fn pipe() {
 
 #[test]
 fn bit_strings() {
-    assert_infer!("let <<x>> = <<1>> x", "Int");
-    assert_infer!("let <<x>> = <<1>> x", "Int");
-    assert_infer!("let <<x:float>> = <<1>> x", "Float");
-    assert_infer!("let <<x:binary>> = <<1>> x", "BitString");
-    assert_infer!("let <<x:bytes>> = <<1>> x", "BitString");
-    assert_infer!("let <<x:bit_string>> = <<1>> x", "BitString");
-    assert_infer!("let <<x:bits>> = <<1>> x", "BitString");
-
-    assert_infer!("let <<x:utf8_codepoint>> = <<128013:32>> x", "UtfCodepoint");
+    assert_infer!("assert <<x>> = <<1>> x", "Int");
+    assert_infer!("assert <<x>> = <<1>> x", "Int");
+    assert_infer!("assert <<x:float>> = <<1>> x", "Float");
+    assert_infer!("assert <<x:binary>> = <<1>> x", "BitString");
+    assert_infer!("assert <<x:bytes>> = <<1>> x", "BitString");
+    assert_infer!("assert <<x:bit_string>> = <<1>> x", "BitString");
+    assert_infer!("assert <<x:bits>> = <<1>> x", "BitString");
+
     assert_infer!(
-        "let <<x:utf16_codepoint>> = <<128013:32>> x",
+        "assert <<x:utf8_codepoint>> = <<128013:32>> x",
         "UtfCodepoint"
     );
     assert_infer!(
-        "let <<x:utf32_codepoint>> = <<128013:32>> x",
+        "assert <<x:utf16_codepoint>> = <<128013:32>> x",
+        "UtfCodepoint"
+    );
+    assert_infer!(
+        "assert <<x:utf32_codepoint>> = <<128013:32>> x",
         "UtfCodepoint"
     );
 
     assert_infer!(
-        "let a = <<1>> let <<x:binary>> = <<1, a:2-bit_string>> x",
+        "let a = <<1>> assert <<x:binary>> = <<1, a:2-bit_string>> x",
         "BitString"
     );
     assert_infer!(
@@ -1048,6 +1108,36 @@ fn binop_unification_errors() {
         },
     );
 
+    assert_error!(
+        "!1",
+        Error::CouldNotUnify {
+            situation: None,
+            location: SrcSpan { start: 1, end: 2 },
+            expected: bool(),
+            given: int(),
+        },
+    );
+
+    assert_error!(
+        "- 1.0",
+        Error::CouldNotUnify {
+            situation: None,
+            location: SrcSpan { start: 2, end: 5 },
+            expected: int(),
+            given: float(),
+        },
+    );
+
+    assert_error!(
+        "\"a\" <> 1",
+        Error::CouldNotUnify {
+            situation: Some(UnifyErrorSituation::Operator(BinOp::Concatenate)),
+            location: SrcSpan { start: 7, end: 8 },
+            expected: string(),
+            given: int(),
+        },
+    );
+
     assert_error!(
         "fn() { 1 } == fn(x) { x + 1 }",
         Error::CouldNotUnify {
@@ -1059,7 +1149,7 @@ fn binop_unification_errors() {
             }),
             given: Arc::new(Type::Fn {
                 args: vec![Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
                 })],
                 retrn: int(),
             }),
@@ -1256,7 +1346,7 @@ fn pipe_mismatch_error() {
                     args: vec![],
                 }),],
                 retrn: Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 7, level: 3 })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 7, level: 3 })),
                 })
             })
         },
@@ -1379,10 +1469,10 @@ fn the_rest() {
             expected: int(),
             given: tuple(vec![
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 7, level: 1 })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 7, level: 1 })),
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 8, level: 1 })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 8, level: 1 })),
                 })
             ]),
         },
@@ -1394,10 +1484,10 @@ fn the_rest() {
             situation: None,
             location: SrcSpan { start: 9, end: 12 },
             expected: list(Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Link { type_: float() }))
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: float() }))
             })),
             given: list(Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() }))
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() }))
             }))
         },
     );
@@ -1743,7 +1833,7 @@ fn the_rest() {
             label: "field".to_string(),
             fields: vec![],
             typ: Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Generic { id: 7 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 7 })),
             }),
         },
     );
@@ -1755,26 +1845,26 @@ fn the_rest() {
             location: SrcSpan { start: 35, end: 40 },
             expected: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link {
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link {
                         type_: Arc::new(Type::Var {
-                            type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 8, level: 1 }))
+                            type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 8, level: 1 }))
                         })
                     })),
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
                 }),
             ),
             given: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link {
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link {
                         type_: Arc::new(Type::Var {
-                            type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 8, level: 1 }))
+                            type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 8, level: 1 }))
                         })
                     })),
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: float() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: float() })),
                 }),
             ),
         },
@@ -1787,22 +1877,25 @@ fn the_rest() {
             location: SrcSpan { start: 17, end: 26 },
             expected: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link {
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link {
                         type_: Arc::new(Type::Var {
-                            type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 12, level: 1 }))
+                            type_: Arc::new(TypeVarCell::new(TypeVar::Unbound {
+                                id: 12,
+                                level: 1
+                            }))
                         })
                     })),
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
                 }),
             ),
             given: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 12, level: 1 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 12, level: 1 }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: float() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: float() })),
                 }),
             ),
         },
@@ -1815,10 +1908,10 @@ fn the_rest() {
             location: SrcSpan { start: 17, end: 18 },
             expected: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 11, level: 1 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 11, level: 1 }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
                 }),
             ),
             given: int(),
@@ -1832,18 +1925,18 @@ fn the_rest() {
             location: SrcSpan { start: 35, end: 40 },
             expected: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
                 }),
             ),
             given: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: float() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: float() })),
                 }),
             ),
         },
@@ -1856,22 +1949,25 @@ fn the_rest() {
             location: SrcSpan { start: 39, end: 56 },
             expected: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link {
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link {
                         type_: Arc::new(Type::Var {
-                            type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 14, level: 1 }))
+                            type_: Arc::new(TypeVarCell::new(TypeVar::Unbound {
+                                id: 14,
+                                level: 1
+                            }))
                         })
                     }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
                 }),
             ),
             given: result(
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Unbound { id: 14, level: 1 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 14, level: 1 }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Link { type_: string() })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: string() })),
                 }),
             ),
         },
@@ -2488,10 +2584,10 @@ fn demo() {
             location: SrcSpan { start: 33, end: 46 },
             expected: tuple(vec![
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 8 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 8 }))
                 }),
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 8 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 8 }))
                 })
             ]),
             given: tuple(vec![int(), float()]),
@@ -2518,10 +2614,10 @@ fn demo() {
             location: SrcSpan { start: 65, end: 86 },
             expected: tuple(vec![
                 Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 9 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 9 }))
                 }),
                 list(Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 9 }))
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 9 }))
                 }))
             ]),
             given: tuple(vec![string(), list(link(int()))]),
@@ -2777,7 +2873,7 @@ pub fn main(box: Box(Box(Int))) { box.inner.unknown }
             label: "unknown".to_string(),
             fields: vec!["inner".to_string()],
             typ: Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Link {
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link {
                     type_: Arc::new(Type::App {
                         args: vec![int()],
                         public: true,
@@ -2885,7 +2981,7 @@ fn main() {
                 module: vec!["my_module".to_string()],
                 name: "Box".to_string(),
                 args: vec![Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 8 })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 8 })),
                 })]
             }),
             given: Arc::new(Type::App {
@@ -2893,7 +2989,7 @@ fn main() {
                 module: vec!["my_module".to_string()],
                 name: "Box".to_string(),
                 args: vec![Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 10 })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 10 })),
                 })]
             }),
         },
@@ -3072,10 +3168,10 @@ fn module_constants() {
             situation: None,
             location: SrcSpan { start: 31, end: 46 },
             expected: list(Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() }))
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() }))
             })),
             given: list(Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Link { type_: float() }))
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: float() }))
             }))
         }
     );
@@ -3136,6 +3232,66 @@ fn module_constants() {
     );
 }
 
+#[test]
+fn imported_constants_with_unbound_types() {
+    // Each use of an imported constant gets its own instance of any type
+    // variable that was not inferred, rather than linking the shared one
+    let (module, _) = infer_with_dependency(
+        Target::Erlang,
+        "pub const empty = [] pub fn also_empty() { empty }",
+        r#"import dep
+pub fn go() { #([1, ..dep.empty], ["a", ..dep.empty], [1.0, ..dep.also_empty()]) }"#,
+    )
+    .expect("should successfully infer");
+    let go = module.type_info.values.get("go").expect("go is exported");
+    assert_eq!(
+        pretty::Printer::new().pretty_print(&go.type_, 0),
+        "fn() -> #(List(Int), List(String), List(Float))"
+    );
+}
+
+#[test]
+fn constants_with_unbound_types_in_their_own_module() {
+    // Within the module that defines it a constant has a single type, so its
+    // uses there decide the type that is exported
+    assert_module_error!("pub const empty = []\npub fn go() { #([1, ..empty], [\"a\", ..empty]) }");
+
+    assert!(matches!(
+        infer_with_dependency(
+            Target::Erlang,
+            "pub const empty = [] pub fn go() { [1, ..empty] }",
+            r#"import dep
+pub fn go() { ["a", ..dep.empty] }"#,
+        ),
+        Err(Error::CouldNotUnify {
+            location: SrcSpan { start: 25, end: 43 },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn interleaved_uids() {
+    let mut uid = UidGenerator::default();
+    assert_eq!(uid.next_uid(), 0);
+
+    // Modules checked together get distinct ids
+    let mut modules = uid.interleave(3);
+    let ids: Vec<_> = modules
+        .iter_mut()
+        .map(|module| (module.next_uid(), module.next_uid()))
+        .collect();
+    assert_eq!(ids, vec![(1, 4), (2, 5), (3, 6)]);
+
+    // Allocation continues after the ids used by any of the modules
+    if let Some(module) = modules.first_mut() {
+        assert_eq!(module.next_uid(), 7);
+    }
+    uid.resume(modules);
+    assert_eq!(uid.next_uid(), 10);
+    assert_eq!(uid.next_uid(), 11);
+}
+
 #[test]
 fn custom_type_module_constants() {
     assert_module_infer!(
@@ -3209,7 +3365,7 @@ fn module_update() {
                 module: vec!["my_module".to_string()],
                 name: "Box".to_string(),
                 args: vec![Arc::new(Type::Var {
-                    type_: Arc::new(RefCell::new(TypeVar::Generic { id: 8 })),
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 8 })),
                 })]
             }),
         },
@@ -3360,10 +3516,10 @@ fn module_update() {
                 end: 158,
             },
             expected: Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Generic { id: 8 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 8 })),
             }),
             given: Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Generic { id: 10 })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id: 10 })),
             }),
         },
     );
@@ -3422,7 +3578,7 @@ fn todo_warning_test() {
         Warning::Todo {
             location: SrcSpan { start: 17, end: 21 },
             typ: Arc::new(Type::Var {
-                type_: Arc::new(RefCell::new(TypeVar::Link { type_: int() })),
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
             }),
         },
     );
@@ -3866,7 +4022,7 @@ fn bit_pattern_var_use() {
     assert_no_warnings!(
         "
 pub fn main(x) {
-  let <<name_size:8, name:binary-size(name_size)>> = x
+  assert <<name_size:8, name:binary-size(name_size)>> = x
   name
 }",
     );
@@ -3959,3 +4115,59 @@ fn module_name_validation() {
 
     assert!(validate_module_name(&["ok".to_string(), "pub".to_string()]).is_err());
 }
+
+#[test]
+fn label_shorthand() {
+    assert_module_infer!(
+        "pub type Person { Person(name: String, age: Int) }
+pub fn make(name name: String, age age: Int) { Person(age:, name:) }
+pub fn rename(person: Person, name: String) { Person(..person, name:) }
+pub fn remake(name: String) { make(name:, age: 1) }",
+        vec![
+            ("Person", "fn(String, Int) -> Person"),
+            ("make", "fn(String, Int) -> Person"),
+            ("remake", "fn(String) -> Person"),
+            ("rename", "fn(Person, String) -> Person"),
+        ],
+    );
+}
+
+#[test]
+fn shared_record_field_access() {
+    // Fields shared by every constructor can be accessed
+    assert_module_infer!(
+        "pub type Shape(a) {
+  Circle(name: String, radius: Float, tag: a)
+  Square(name: String, side: Float, tag: a)
+}
+pub fn name(shape: Shape(a)) { shape.name }
+pub fn tag(shape: Shape(a)) { shape.tag }",
+        vec![
+            ("Circle", "fn(String, Float, a) -> Shape(a)"),
+            ("Square", "fn(String, Float, a) -> Shape(a)"),
+            ("name", "fn(Shape(a)) -> String"),
+            ("tag", "fn(Shape(a)) -> a"),
+        ],
+    );
+
+    // Fields at a different position or with a different type cannot be
+    // accessed
+    assert_module_error!(
+        "pub type Shape { Circle(r: Float, name: String) Square(name: String, r: Int) }
+pub fn main(shape: Shape) { shape.r }",
+        Error::UnknownField {
+            location: SrcSpan {
+                start: 112,
+                end: 114
+            },
+            label: "r".to_string(),
+            fields: vec![],
+            typ: Arc::new(Type::App {
+                args: vec![],
+                public: true,
+                module: vec!["my_module".to_string()],
+                name: "Shape".to_string(),
+            }),
+        },
+    );
+}
@@ -0,0 +1,138 @@
+use super::*;
+
+fn deprecated(start: usize, end: usize, name: &str) -> Warning {
+    Warning::DeprecatedItem {
+        location: SrcSpan { start, end },
+        name: name.to_string(),
+        message: "do not use".to_string(),
+    }
+}
+
+#[test]
+fn qualified_function() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            r#"@deprecated("do not use")
+pub fn old() { 1 }"#,
+            "import dep
+pub fn main() { dep.old() }"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![deprecated(27, 34, "old")]),
+    );
+}
+
+#[test]
+fn unqualified_function() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            r#"@deprecated("do not use")
+pub fn old() { 1 }"#,
+            "import dep.{old}
+pub fn main() { old() }"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![deprecated(33, 36, "old")]),
+    );
+}
+
+#[test]
+fn external_function() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            r#"@deprecated("do not use")
+pub external fn old() -> Int = "a" "b""#,
+            "import dep
+pub fn main() { dep.old() }"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![deprecated(27, 34, "old")]),
+    );
+}
+
+#[test]
+fn constant() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            r#"@deprecated("do not use")
+pub const old = 1"#,
+            "import dep
+pub fn main() { dep.old }"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![deprecated(27, 34, "old")]),
+    );
+}
+
+#[test]
+fn custom_type_and_constructor() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            r#"@deprecated("do not use")
+pub type Old { Old(Int) }"#,
+            "import dep
+pub fn main(x: dep.Old) {
+  case x { dep.Old(_) -> dep.Old(1) }
+}"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![
+            deprecated(26, 33, "Old"),
+            deprecated(48, 58, "Old"),
+            deprecated(62, 69, "Old"),
+        ]),
+    );
+}
+
+#[test]
+fn type_alias() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            r#"@deprecated("do not use")
+pub type Old = Int"#,
+            "import dep
+pub fn main(x: dep.Old) { x }"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![deprecated(26, 33, "Old")]),
+    );
+}
+
+#[test]
+fn not_deprecated() {
+    assert_eq!(
+        infer_with_dependency(
+            Target::Erlang,
+            "pub fn new() { 1 }",
+            "import dep
+pub fn main() { dep.new() }"
+        )
+        .map(|(_, warnings)| warnings),
+        Ok(vec![]),
+    );
+}
+
+#[test]
+fn deprecated_items_used_in_their_own_module() {
+    assert_no_warnings!(
+        r#"@deprecated("do not use")
+pub type Old { Old }
+
+@deprecated("do not use")
+pub const old_const = 1
+
+@deprecated("do not use")
+pub fn old() { Old }
+
+pub fn main() -> Old {
+  let _ = old_const
+  old()
+}"#
+    );
+}
@@ -0,0 +1,113 @@
+use super::*;
+use crate::assert_module_infer;
+
+#[test]
+fn let_with_irrefutable_pattern() {
+    assert_module_infer!(
+        r#"
+pub type Box(a) { Box(a) }
+pub fn unbox(box) {
+  let Box(#(a, _) as pair) = box
+  let [..rest] = [a]
+  let Nil = Nil
+  #(pair, rest)
+}"#,
+        vec![
+            ("Box", "fn(a) -> Box(a)"),
+            ("unbox", "fn(Box(#(a, b))) -> #(#(a, b), List(a))"),
+        ],
+    );
+}
+
+#[test]
+fn let_with_refutable_pattern() {
+    assert_module_error!(
+        "pub fn go(x) { let Ok(y) = x y }",
+        Error::InexhaustiveLetAssignment {
+            location: SrcSpan { start: 19, end: 24 },
+        },
+    );
+
+    assert_module_error!(
+        "pub fn go(x) { let #(1, y) = x y }",
+        Error::InexhaustiveLetAssignment {
+            location: SrcSpan { start: 19, end: 26 },
+        },
+    );
+
+    assert_module_error!(
+        "type Pet { Cat Dog } pub fn go(x) { let Cat = x }",
+        Error::InexhaustiveLetAssignment {
+            location: SrcSpan { start: 40, end: 43 },
+        },
+    );
+}
+
+#[test]
+fn let_with_imported_types() {
+    let dep = "pub type Box { Box(Int) } pub type Pet { Cat Dog }";
+
+    assert_eq!(
+        Ok(vec![]),
+        infer_with_dependency(
+            Target::Erlang,
+            dep,
+            "import dep pub fn go(x) { let dep.Box(y) = x y }"
+        )
+        .map(|(_, warnings)| warnings)
+    );
+
+    assert_eq!(
+        Err(Error::InexhaustiveLetAssignment {
+            location: SrcSpan { start: 30, end: 37 },
+        }),
+        infer_with_dependency(
+            Target::Erlang,
+            dep,
+            "import dep pub fn go(x) { let dep.Cat = x }"
+        )
+        .map(|(_, warnings)| warnings)
+    );
+}
+
+#[test]
+fn assert_with_irrefutable_pattern() {
+    assert_warning!(
+        "pub fn go(x) { assert #(a, _) = x a }",
+        Warning::IrrefutableAssert {
+            location: SrcSpan { start: 22, end: 29 },
+        },
+    );
+
+    assert_no_warnings!("pub fn go(x) { assert #(1, a) = x a }");
+}
+
+#[test]
+fn assert_pattern_never_matches() {
+    assert_module_error!(
+        "pub fn go() { assert Ok(x) = Error(Nil) x }",
+        Error::PatternNeverMatches {
+            location: SrcSpan { start: 21, end: 26 },
+        },
+    );
+
+    assert_module_error!(
+        "pub fn go() { assert [x, _] = [1] x }",
+        Error::PatternNeverMatches {
+            location: SrcSpan { start: 21, end: 26 },
+        },
+    );
+
+    assert_module_error!(
+        r#"pub fn go() { assert #("a", x) = #("b", 1) x }"#,
+        Error::PatternNeverMatches {
+            location: SrcSpan { start: 21, end: 30 },
+        },
+    );
+
+    // Literals written differently can still match
+    assert_module_infer!(
+        "pub fn go() { assert #(0x10, [x, .._]) = #(16, [1, 2]) x }",
+        vec![("go", "fn() -> Int")],
+    );
+}
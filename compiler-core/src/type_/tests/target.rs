@@ -0,0 +1,166 @@
+use super::*;
+
+fn check(target: Target, dep_src: &str, src: &str) -> Result<(), Error> {
+    infer_with_dependency(target, dep_src, src).map(|_| ())
+}
+
+fn unsupported(
+    start: usize,
+    end: usize,
+    name: &str,
+    via: &[&str],
+    target: Target,
+) -> Result<(), Error> {
+    Err(Error::UnsupportedTarget {
+        location: SrcSpan { start, end },
+        name: name.to_string(),
+        via: via.iter().map(|name| name.to_string()).collect(),
+        target,
+    })
+}
+
+const ERLANG_ONLY: &str = r#"pub external fn now() -> Int =
+  erlang "os" "system_time""#;
+
+const ERLANG_ONLY_WRAPPER: &str = r#"external fn system_time() -> Int =
+  erlang "os" "system_time"
+pub fn now() { system_time() }"#;
+
+#[test]
+fn untargeted_external_supports_all_targets() {
+    let dep = r#"pub external fn now() -> Int = "os" "system_time""#;
+    let src = "import dep
+pub fn main() { dep.now() }";
+    assert_eq!(check(Target::Erlang, dep, src), Ok(()));
+    assert_eq!(check(Target::JavaScript, dep, src), Ok(()));
+}
+
+#[test]
+fn supported_target() {
+    assert_eq!(
+        check(
+            Target::Erlang,
+            ERLANG_ONLY,
+            "import dep
+pub fn main() { dep.now() }"
+        ),
+        Ok(()),
+    );
+}
+
+#[test]
+fn unsupported_qualified() {
+    assert_eq!(
+        check(
+            Target::JavaScript,
+            ERLANG_ONLY,
+            "import dep
+pub fn main() { dep.now() }"
+        ),
+        unsupported(27, 34, "dep.now", &[], Target::JavaScript),
+    );
+}
+
+#[test]
+fn unsupported_unqualified() {
+    assert_eq!(
+        check(
+            Target::JavaScript,
+            ERLANG_ONLY,
+            "import dep.{now}
+pub fn main() { now() }"
+        ),
+        unsupported(33, 36, "now", &[], Target::JavaScript),
+    );
+}
+
+#[test]
+fn unused_public_wrapper() {
+    assert_eq!(check(Target::JavaScript, "", ERLANG_ONLY_WRAPPER), Ok(()));
+}
+
+#[test]
+fn used_public_wrapper() {
+    let src = "import dep
+pub fn main() { dep.now() }";
+    assert_eq!(check(Target::Erlang, ERLANG_ONLY_WRAPPER, src), Ok(()));
+    assert_eq!(
+        check(Target::JavaScript, ERLANG_ONLY_WRAPPER, src),
+        unsupported(27, 34, "dep.now", &[], Target::JavaScript),
+    );
+}
+
+#[test]
+fn both_targets() {
+    let src = r#"external fn now() -> Int =
+  erlang "os" "system_time"
+  javascript "./time.mjs" "now"
+pub fn main() { now() }"#;
+    assert_eq!(check(Target::Erlang, "", src), Ok(()));
+    assert_eq!(check(Target::JavaScript, "", src), Ok(()));
+}
+
+#[test]
+fn unsupported_through_private_functions() {
+    assert_eq!(
+        check(
+            Target::JavaScript,
+            ERLANG_ONLY,
+            "import dep
+fn outer() { inner() }
+fn inner() { dep.now() }
+pub fn main() { outer() }"
+        ),
+        unsupported(75, 80, "outer", &["inner", "dep.now"], Target::JavaScript),
+    );
+}
+
+#[test]
+fn unsupported_through_recursive_functions() {
+    assert_eq!(
+        check(
+            Target::JavaScript,
+            ERLANG_ONLY,
+            "import dep
+fn ping(x) { pong(x) }
+fn pong(x) { ping(x) dep.now() }
+pub fn main() { ping(1) }"
+        ),
+        unsupported(83, 87, "ping", &["pong", "dep.now"], Target::JavaScript),
+    );
+}
+
+#[test]
+fn unreachable_private_function() {
+    assert_eq!(
+        check(
+            Target::JavaScript,
+            ERLANG_ONLY,
+            "import dep
+fn now() { dep.now() }
+pub fn main() { 1 }"
+        ),
+        Ok(()),
+    );
+}
+
+#[test]
+fn public_external_without_target_implementation() {
+    assert_eq!(check(Target::JavaScript, "", ERLANG_ONLY), Ok(()));
+}
+
+#[test]
+fn targets_are_recorded() {
+    let (module, _) = infer_with_dependency(
+        Target::JavaScript,
+        "",
+        &format!("{}\npub fn one() {{ 1 }}", ERLANG_ONLY_WRAPPER),
+    )
+    .expect("should successfully infer");
+    let targets = |name: &str| match module.type_info.values.get(name).map(|v| &v.variant) {
+        Some(ValueConstructorVariant::ModuleFn { targets, .. }) => targets.clone(),
+        _ => panic!("{} is not a module function", name),
+    };
+    assert_eq!(targets("now"), vec![Target::Erlang]);
+    assert_eq!(targets("one"), Target::all());
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn after_todo() {
+    assert_warnings!(
+        "pub fn main() -> Int {\n  todo\n  main()\n}",
+        vec![
+            Warning::Todo {
+                location: SrcSpan { start: 25, end: 29 },
+                typ: Arc::new(Type::Var {
+                    type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id: 7, level: 3 })),
+                }),
+            },
+            Warning::UnreachableCode {
+                location: SrcSpan { start: 32, end: 38 },
+            },
+        ],
+    );
+
+    assert_warnings!(
+        "pub fn main() -> Int {\n  main()\n  todo\n}",
+        vec![Warning::Todo {
+            location: SrcSpan { start: 34, end: 38 },
+            typ: Arc::new(Type::Var {
+                type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_: int() })),
+            }),
+        }],
+    );
+}
+
+#[test]
+fn after_catch_all_clause() {
+    assert_warning!(
+        "pub fn main(x) {\n  case x {\n    1 -> 1\n    _ -> 2\n    2 -> 3\n    _ -> 4\n  }\n}",
+        Warning::UnreachableCode {
+            location: SrcSpan { start: 54, end: 71 },
+        },
+    );
+
+    assert_warning!(
+        "pub fn main(x, y) { case x, y { 1, _ -> 1 _, #(a, _) -> a 2, _ -> 3 } }",
+        Warning::UnreachableCode {
+            location: SrcSpan { start: 58, end: 67 },
+        },
+    );
+
+    assert_warning!(
+        "pub fn main(x) { case x { 1 | _ -> 1 2 -> 2 } }",
+        Warning::UnreachableCode {
+            location: SrcSpan { start: 37, end: 43 },
+        },
+    );
+}
+
+#[test]
+fn after_guarded_or_refutable_clause() {
+    assert_no_warnings!("pub fn main(x) { case x { _ if x > 1 -> 1 _ -> 2 } }");
+    assert_no_warnings!("pub fn main(x, y) { case x, y { _, 1 -> 1 _, _ -> 2 } }");
+}
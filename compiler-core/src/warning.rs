@@ -2,14 +2,18 @@ use crate::{
     diagnostic::{write, Diagnostic, Severity},
     type_,
     type_::pretty::Printer,
+    Error,
 };
+use serde::{de, Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
+use strum::{EnumString, EnumVariantNames, VariantNames};
 use termcolor::Buffer;
 
 pub type Src = String;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Warning {
     Type {
         path: PathBuf,
@@ -19,6 +23,31 @@ pub enum Warning {
 }
 
 impl Warning {
+    pub fn kind(&self) -> WarningKind {
+        match self {
+            Self::Type { warning, .. } => match warning {
+                type_::Warning::Todo { .. } => WarningKind::Todo,
+                type_::Warning::ImplicitlyDiscardedResult { .. } => {
+                    WarningKind::ImplicitlyDiscardedResult
+                }
+                type_::Warning::UnusedLiteral { .. } => WarningKind::UnusedLiteral,
+                type_::Warning::NoFieldsRecordUpdate { .. } => WarningKind::NoFieldsRecordUpdate,
+                type_::Warning::AllFieldsRecordUpdate { .. } => WarningKind::AllFieldsRecordUpdate,
+                type_::Warning::UnusedType { .. } => WarningKind::UnusedType,
+                type_::Warning::UnusedConstructor { .. } => WarningKind::UnusedConstructor,
+                type_::Warning::UnusedImportedValue { .. } => WarningKind::UnusedImportedValue,
+                type_::Warning::UnusedPrivateModuleConstant { .. } => {
+                    WarningKind::UnusedPrivateModuleConstant
+                }
+                type_::Warning::UnusedPrivateFunction { .. } => WarningKind::UnusedPrivateFunction,
+                type_::Warning::UnusedVariable { .. } => WarningKind::UnusedVariable,
+                type_::Warning::DeprecatedItem { .. } => WarningKind::DeprecatedItem,
+                type_::Warning::IrrefutableAssert { .. } => WarningKind::IrrefutableAssert,
+                type_::Warning::UnreachableCode { .. } => WarningKind::UnreachableCode,
+            },
+        }
+    }
+
     pub fn to_diagnostic(&self) -> (Diagnostic, String) {
         #[allow(clippy::unwrap_used)]
         match self {
@@ -183,6 +212,45 @@ your program.",
                     },
                     format!("Hint: you can ignore it with an underscore: `_{}`.", name),
                 ),
+
+                type_::Warning::DeprecatedItem {
+                    location,
+                    name,
+                    message,
+                } => (
+                    Diagnostic {
+                        title: "Deprecated item used".to_string(),
+                        label: format!("`{}` has been deprecated.", name),
+                        file: path.to_str().unwrap().to_string(),
+                        src: src.to_string(),
+                        location: *location,
+                    },
+                    format!("Hint: {}", message),
+                ),
+
+                type_::Warning::IrrefutableAssert { location } => (
+                    Diagnostic {
+                        title: "Redundant assert".to_string(),
+                        label: "This pattern always matches".to_string(),
+                        file: path.to_str().unwrap().to_string(),
+                        src: src.to_string(),
+                        location: *location,
+                    },
+                    "Hint: This assertion can never fail, use `let` instead.".to_string(),
+                ),
+
+                type_::Warning::UnreachableCode { location } => (
+                    Diagnostic {
+                        title: "Unreachable code".to_string(),
+                        label: "This code is never run".to_string(),
+                        file: path.to_str().unwrap().to_string(),
+                        src: src.to_string(),
+                        location: *location,
+                    },
+                    "Hint: It follows a `todo` or a case clause that matches every value,
+so it can be safely removed."
+                        .to_string(),
+                ),
             },
         }
     }
@@ -199,3 +267,157 @@ your program.",
         }
     }
 }
+
+/// The kinds of warning, named in the `[warnings]` table of `gleam.toml` to
+/// configure how each is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+pub enum WarningKind {
+    Todo,
+    ImplicitlyDiscardedResult,
+    UnusedLiteral,
+    NoFieldsRecordUpdate,
+    AllFieldsRecordUpdate,
+    UnusedType,
+    UnusedConstructor,
+    UnusedImportedValue,
+    UnusedPrivateModuleConstant,
+    UnusedPrivateFunction,
+    UnusedVariable,
+    DeprecatedItem,
+    IrrefutableAssert,
+    UnreachableCode,
+}
+
+// Deserialised from a string as the TOML parser does not support enums as the
+// keys of tables.
+impl<'de> Deserialize<'de> for WarningKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse()
+            .map_err(|_| de::Error::unknown_variant(&name, Self::VARIANTS))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningLevel {
+    /// The warning is not reported.
+    Allow,
+    /// The warning is reported.
+    #[default]
+    Warn,
+    /// The warning is reported and compilation fails.
+    Deny,
+}
+
+/// The warnings emitted while compiling, with any kinds of warning that are
+/// allowed left out.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Warnings {
+    warnings: Vec<Warning>,
+    levels: HashMap<WarningKind, WarningLevel>,
+}
+
+impl Warnings {
+    pub fn new(levels: HashMap<WarningKind, WarningLevel>) -> Self {
+        Self {
+            warnings: vec![],
+            levels,
+        }
+    }
+
+    pub fn level(&self, kind: WarningKind) -> WarningLevel {
+        self.levels.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn push(&mut self, warning: Warning) {
+        if self.level(warning.kind()) != WarningLevel::Allow {
+            self.warnings.push(warning);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.warnings.iter()
+    }
+
+    /// Return an error if any of the warnings are denied, or if there are any
+    /// warnings at all when they are to be treated as errors.
+    pub fn check(&self, warnings_as_errors: bool) -> Result<(), Error> {
+        let count = if warnings_as_errors {
+            self.len()
+        } else {
+            self.iter()
+                .filter(|warning| self.level(warning.kind()) == WarningLevel::Deny)
+                .count()
+        };
+        if count == 0 {
+            Ok(())
+        } else {
+            Err(Error::ForbiddenWarnings { count })
+        }
+    }
+}
+
+impl Extend<Warning> for Warnings {
+    fn extend<T: IntoIterator<Item = Warning>>(&mut self, warnings: T) {
+        for warning in warnings {
+            self.push(warning);
+        }
+    }
+}
+
+#[test]
+fn warnings_levels() {
+    let warning = |warning| Warning::Type {
+        path: PathBuf::from("/src/one.gleam"),
+        src: String::new(),
+        warning,
+    };
+    let unused_variable = warning(type_::Warning::UnusedVariable {
+        location: Default::default(),
+        name: "x".to_string(),
+    });
+    let unused_literal = warning(type_::Warning::UnusedLiteral {
+        location: Default::default(),
+    });
+    let todo = warning(type_::Warning::Todo {
+        location: Default::default(),
+        typ: crate::type_::int(),
+    });
+
+    let mut warnings = Warnings::new(
+        vec![
+            (WarningKind::UnusedVariable, WarningLevel::Allow),
+            (WarningKind::Todo, WarningLevel::Deny),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    warnings.extend(vec![unused_variable, unused_literal.clone()]);
+    assert_eq!(warnings.iter().collect::<Vec<_>>(), vec![&unused_literal]);
+    assert_eq!(warnings.check(false), Ok(()));
+    assert_eq!(
+        warnings.check(true),
+        Err(Error::ForbiddenWarnings { count: 1 })
+    );
+
+    warnings.push(todo);
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(
+        warnings.check(false),
+        Err(Error::ForbiddenWarnings { count: 1 })
+    );
+    assert_eq!(
+        warnings.check(true),
+        Err(Error::ForbiddenWarnings { count: 2 })
+    );
+}
@@ -1,6 +1,202 @@
+//! A JavaScript API to the Gleam compiler for use in the browser and other
+//! WebAssembly environments, such as the playground and live documentation
+//! examples.
+//!
+//! Source code is compiled using an in memory file system, with the results
+//! returned to JavaScript as plain objects.
+
+#[cfg(test)]
+mod tests;
+
+use gleam_core::{
+    build::{manifest, package_compiler, Target},
+    io::{memory::InMemoryFileSystem, FileSystemReader, FileSystemWriter},
+    Error, Warning,
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use termcolor::Buffer;
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen]
-pub fn name() -> String {
-    "Louis".to_string()
+const PACKAGE_NAME: &str = "gleam_wasm";
+const SRC_PATH: &str = "/src";
+const OUT_PATH: &str = "/build";
+
+/// The result of compiling a set of modules.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Compiled {
+    /// The generated files, keyed by their path relative to the output
+    /// directory.
+    pub files: BTreeMap<String, String>,
+    pub warnings: Vec<Diagnostic>,
+    pub errors: Vec<Diagnostic>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    /// The path of the module the problem is in, if there is one.
+    pub path: Option<String>,
+    pub location: Option<Location>,
+    /// The problem rendered as it would be shown by the command line.
+    pub text: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Location {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Compile a map of module names to Gleam source code for the given target.
+pub fn compile(modules: HashMap<String, String>, target: Target) -> Compiled {
+    let io = InMemoryFileSystem::new();
+    let mut compiled = Compiled::default();
+
+    for (name, code) in modules {
+        let path = Path::new(SRC_PATH).join(format!("{}.gleam", name));
+        if let Err(error) = io
+            .open(&path)
+            .and_then(|mut file| file.write(code.as_bytes()))
+        {
+            compiled.errors.push(error_diagnostic(&error));
+            return compiled;
+        }
+    }
+
+    let options = package_compiler::Options {
+        target,
+        name: PACKAGE_NAME.to_string(),
+        src_paths: vec![PathBuf::from(SRC_PATH)],
+        test_path: None,
+        out_path: PathBuf::from(OUT_PATH),
+    };
+    let mut warnings = vec![];
+    let result = options.into_compiler(io.clone()).and_then(|compiler| {
+        compiler.compile(&mut warnings, &mut HashMap::new(), &mut HashMap::new())
+    });
+
+    compiled.warnings = warnings.iter().map(warning_diagnostic).collect();
+    if let Err(error) = result {
+        compiled.errors.push(error_diagnostic(&error));
+        return compiled;
+    }
+
+    for path in io.paths() {
+        if let Ok(relative) = path.strip_prefix(OUT_PATH) {
+            if relative == Path::new(manifest::FILE_NAME) {
+                continue;
+            }
+            match io.read(&path) {
+                Ok(text) => {
+                    let _ = compiled
+                        .files
+                        .insert(relative.to_string_lossy().to_string(), text);
+                }
+                Err(error) => compiled.errors.push(error_diagnostic(&error)),
+            }
+        }
+    }
+    compiled
+}
+
+/// Format Gleam source code, returning the rendered error if it could not be
+/// parsed.
+pub fn format(src: &str) -> Result<String, String> {
+    let mut formatted = String::new();
+    match gleam_core::format::pretty(&mut formatted, src) {
+        Ok(()) => Ok(formatted),
+        Err(error) => Err(render(|buffer| error.pretty(buffer))),
+    }
+}
+
+/// Compile an object of module names to Gleam source code. The target is
+/// either `"erlang"` or `"javascript"`.
+///
+/// Returns an object with `files`, `warnings` and `errors` properties.
+#[wasm_bindgen(js_name = compile)]
+pub fn compile_js(modules: &JsValue, target: &str) -> Result<JsValue, JsValue> {
+    let target = Target::from_str(target)
+        .map_err(|_| JsValue::from_str(&format!("Unknown target `{}`", target)))?;
+
+    let mut sources = HashMap::new();
+    for entry in js_sys::Object::entries(&js_sys::Object::from(modules.clone())).iter() {
+        let entry = js_sys::Array::from(&entry);
+        match (entry.get(0).as_string(), entry.get(1).as_string()) {
+            (Some(name), Some(code)) => {
+                let _ = sources.insert(name, code);
+            }
+            _ => return Err(JsValue::from_str("Module sources must be strings")),
+        }
+    }
+
+    to_js(&compile(sources, target))
+}
+
+/// Format Gleam source code, throwing the rendered error if it could not be
+/// parsed.
+#[wasm_bindgen(js_name = format)]
+pub fn format_js(src: &str) -> Result<String, JsValue> {
+    format(src).map_err(|error| JsValue::from_str(&error))
+}
+
+fn to_js(value: &impl Serialize) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
+
+fn warning_diagnostic(warning: &Warning) -> Diagnostic {
+    let (diagnostic, _) = warning.to_diagnostic();
+    Diagnostic {
+        path: Some(diagnostic.file),
+        location: Some(Location {
+            start: diagnostic.location.start,
+            end: diagnostic.location.end,
+        }),
+        text: render(|buffer| warning.pretty(buffer)),
+    }
+}
+
+fn error_diagnostic(error: &Error) -> Diagnostic {
+    let (path, location) = match error {
+        Error::Parse { path, error, .. } => (
+            Some(path),
+            Some(Location {
+                start: error.location.start,
+                end: error.location.end,
+            }),
+        ),
+        Error::UnknownImport { path, location, .. }
+        | Error::SrcImportingTest { path, location, .. } => (
+            Some(path),
+            Some(Location {
+                start: location.start,
+                end: location.end,
+            }),
+        ),
+        Error::Type { path, error, .. } => (
+            Some(path),
+            error.location().map(|location| Location {
+                start: location.start,
+                end: location.end,
+            }),
+        ),
+        _ => (None, None),
+    };
+    Diagnostic {
+        path: path.map(|path| path.to_string_lossy().to_string()),
+        location,
+        text: render(|buffer| error.pretty(buffer)),
+    }
+}
+
+fn render(pretty: impl FnOnce(&mut Buffer)) -> String {
+    let mut buffer = Buffer::no_color();
+    pretty(&mut buffer);
+    String::from_utf8_lossy(buffer.as_slice())
+        .trim()
+        .to_string()
 }
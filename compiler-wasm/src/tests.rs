@@ -0,0 +1,107 @@
+// These tests run natively with `cargo test` and in Node with
+// `wasm-pack test --node`.
+
+use super::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn modules(modules: &[(&str, &str)]) -> HashMap<String, String> {
+    modules
+        .iter()
+        .map(|(name, code)| (name.to_string(), code.to_string()))
+        .collect()
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[wasm_bindgen_test]
+fn compile_erlang() {
+    let compiled = compile(
+        modules(&[
+            ("one", "pub fn one() { 1 }"),
+            ("two/three", "import one\npub fn three() { one.one() + 2 }"),
+        ]),
+        Target::Erlang,
+    );
+    assert_eq!(compiled.errors, vec![]);
+    assert_eq!(compiled.warnings, vec![]);
+    assert_eq!(
+        compiled.files.keys().collect::<Vec<_>>(),
+        vec!["one.erl", "two@three.erl"]
+    );
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[wasm_bindgen_test]
+fn compile_javascript() {
+    let compiled = compile(
+        modules(&[("main", "pub fn main() { 1 }")]),
+        Target::JavaScript,
+    );
+    assert_eq!(compiled.errors, vec![]);
+    assert_eq!(compiled.files.keys().collect::<Vec<_>>(), vec!["main.js"]);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[wasm_bindgen_test]
+fn compile_with_warnings() {
+    let compiled = compile(
+        modules(&[("main", "pub fn main() { todo }")]),
+        Target::Erlang,
+    );
+    assert_eq!(compiled.errors, vec![]);
+    assert_eq!(compiled.warnings.len(), 1);
+    let warning = compiled.warnings.first().expect("warning");
+    assert_eq!(warning.path, Some("/src/main.gleam".to_string()));
+    assert!(warning.text.contains("Todo found"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[wasm_bindgen_test]
+fn compile_with_parse_error() {
+    let compiled = compile(modules(&[("main", "pub fn main( {")]), Target::Erlang);
+    assert_eq!(compiled.files, BTreeMap::new());
+    assert_eq!(compiled.errors.len(), 1);
+    let error = compiled.errors.first().expect("error");
+    assert_eq!(error.path, Some("/src/main.gleam".to_string()));
+    assert!(error.location.is_some());
+    assert!(error.text.starts_with("error: Syntax error"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[wasm_bindgen_test]
+fn compile_with_type_error() {
+    let compiled = compile(
+        modules(&[("main", "pub fn main() { 1 + \"one\" }")]),
+        Target::Erlang,
+    );
+    assert_eq!(compiled.errors.len(), 1);
+    let error = compiled.errors.first().expect("error");
+    assert_eq!(error.path, Some("/src/main.gleam".to_string()));
+    assert_eq!(error.location, Some(Location { start: 20, end: 25 }));
+    assert!(error.text.starts_with("error: Type mismatch"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[wasm_bindgen_test]
+fn format_source() {
+    assert_eq!(
+        format("pub fn main(){1}"),
+        Ok("pub fn main() {\n  1\n}\n".to_string())
+    );
+    assert!(format("pub fn main( {").is_err());
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn compile_from_javascript() {
+    let modules = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(
+        &modules,
+        &JsValue::from_str("main"),
+        &JsValue::from_str("pub fn main() { 1 }"),
+    );
+    let compiled = compile_js(&modules, "javascript").expect("compiled");
+    let files = js_sys::Reflect::get(&compiled, &JsValue::from_str("files")).expect("files");
+    let main = js_sys::Reflect::get(&files, &JsValue::from_str("main.js")).expect("main.js");
+    assert!(main.as_string().is_some());
+    assert!(compile_js(&modules, "cobol").is_err());
+}